@@ -0,0 +1,69 @@
+// Generates the typed feature flags of `src/feature_flags.rs` from the checked-in snapshot of
+// `features.json` (`src/feature_flags.tsv`), as a `feature_flags!` invocation in `OUT_DIR`.
+
+use std::env;
+use std::fs;
+use std::path::Path;
+
+const SNAPSHOT: &str = "src/feature_flags.tsv";
+
+fn main() {
+    println!("cargo:rerun-if-changed={}", SNAPSHOT);
+    let snapshot = fs::read_to_string(SNAPSHOT).expect("failed to read the feature flag snapshot");
+
+    let mut flags = String::from("feature_flags! {\n");
+    for line in snapshot.lines() {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let mut fields = line.splitn(3, '\t');
+        let name = fields.next().unwrap_or_default();
+        let kind = fields.next().unwrap_or_default();
+        let description = fields.next().unwrap_or_default();
+        let value = match kind {
+            "bool" => "bool",
+            "integer" => "Option<i64>",
+            "string" => "Option<String>",
+            "json" => "Value",
+            other => panic!("unknown value type '{}' of feature '{}'", other, name),
+        };
+        let doc = if description.is_empty() {
+            format!("The `{}` feature.", name)
+        } else {
+            description.to_string()
+        };
+        flags.push_str(&format!(
+            "    #[doc = {:?}]\n    {} => {:?}: {};\n",
+            doc,
+            type_name(name),
+            name,
+            value
+        ));
+    }
+    flags.push_str("}\n");
+
+    let out_dir = env::var("OUT_DIR").expect("OUT_DIR is set by cargo");
+    fs::write(Path::new(&out_dir).join("feature_flags.rs"), flags)
+        .expect("failed to write the generated feature flags");
+}
+
+// Turns a feature name into an UpperCamelCase type name, e.g. `theFlattening` into
+// `TheFlattening`. Separators start a new word; a leading digit gets a `Feature` prefix.
+fn type_name(feature: &str) -> String {
+    let mut name = String::new();
+    let mut upper = true;
+    for c in feature.chars() {
+        if !c.is_ascii_alphanumeric() {
+            upper = true;
+        } else if upper {
+            name.push(c.to_ascii_uppercase());
+            upper = false;
+        } else {
+            name.push(c);
+        }
+    }
+    if name.starts_with(|c: char| c.is_ascii_digit()) {
+        name.insert_str(0, "Feature");
+    }
+    name
+}
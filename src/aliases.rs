@@ -0,0 +1,513 @@
+use crate::cached_data::IndexedData;
+use crate::diff::{diff_category, CategoryDiff};
+use crate::error::{Edition, McDataError};
+use crate::features::{resolve_range_bound, RangeBound};
+use crate::version::Version;
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+// Curated renames as (kind, old name, new name, first version using the new name).
+// Bounds use the `features.json` syntax, so "_major" means the oldest version of that major.
+// Some names were reused for other entries (1.12 `grass` is 1.13 `grass_block`, while 1.13
+// `grass` is 1.12 `tallgrass`); `resolve_name` tells them apart by the version they come from.
+const CURATED_PC: &[(AliasKind, &str, &str, &str)] = &[
+    // The Flattening (1.13).
+    (AliasKind::Block, "web", "cobweb", "1.13_major"),
+    (AliasKind::Block, "noteblock", "note_block", "1.13_major"),
+    (
+        AliasKind::Block,
+        "lit_pumpkin",
+        "jack_o_lantern",
+        "1.13_major",
+    ),
+    (AliasKind::Block, "mob_spawner", "spawner", "1.13_major"),
+    (AliasKind::Block, "portal", "nether_portal", "1.13_major"),
+    (AliasKind::Block, "brick_block", "bricks", "1.13_major"),
+    (AliasKind::Block, "stonebrick", "stone_bricks", "1.13_major"),
+    (
+        AliasKind::Block,
+        "quartz_ore",
+        "nether_quartz_ore",
+        "1.13_major",
+    ),
+    (AliasKind::Block, "waterlily", "lily_pad", "1.13_major"),
+    (AliasKind::Block, "deadbush", "dead_bush", "1.13_major"),
+    (AliasKind::Block, "yellow_flower", "dandelion", "1.13_major"),
+    (AliasKind::Block, "red_flower", "poppy", "1.13_major"),
+    (
+        AliasKind::Block,
+        "hardened_clay",
+        "terracotta",
+        "1.13_major",
+    ),
+    (AliasKind::Block, "slime", "slime_block", "1.13_major"),
+    (AliasKind::Block, "magma", "magma_block", "1.13_major"),
+    (
+        AliasKind::Block,
+        "end_bricks",
+        "end_stone_bricks",
+        "1.13_major",
+    ),
+    (AliasKind::Block, "grass", "grass_block", "1.13_major"),
+    (AliasKind::Block, "tallgrass", "grass", "1.13_major"),
+    (AliasKind::Block, "snow", "snow_block", "1.13_major"),
+    (AliasKind::Block, "snow_layer", "snow", "1.13_major"),
+    (AliasKind::Block, "reeds", "sugar_cane", "1.13_major"),
+    (AliasKind::Item, "web", "cobweb", "1.13_major"),
+    (AliasKind::Item, "waterlily", "lily_pad", "1.13_major"),
+    (AliasKind::Item, "deadbush", "dead_bush", "1.13_major"),
+    (AliasKind::Item, "reeds", "sugar_cane", "1.13_major"),
+    (AliasKind::Item, "grass", "grass_block", "1.13_major"),
+    (AliasKind::Item, "tallgrass", "grass", "1.13_major"),
+    (AliasKind::Item, "snow", "snow_block", "1.13_major"),
+    (AliasKind::Item, "snow_layer", "snow", "1.13_major"),
+    (
+        AliasKind::Item,
+        "speckled_melon",
+        "glistering_melon_slice",
+        "1.13_major",
+    ),
+    (
+        AliasKind::Item,
+        "fireworks",
+        "firework_rocket",
+        "1.13_major",
+    ),
+    (AliasKind::Item, "boat", "oak_boat", "1.13_major"),
+    (AliasKind::Entity, "snowman", "snow_golem", "1.13_major"),
+    (
+        AliasKind::Entity,
+        "villager_golem",
+        "iron_golem",
+        "1.13_major",
+    ),
+    (
+        AliasKind::Entity,
+        "ender_crystal",
+        "end_crystal",
+        "1.13_major",
+    ),
+    (AliasKind::Entity, "xp_orb", "experience_orb", "1.13_major"),
+    (
+        AliasKind::Entity,
+        "evocation_illager",
+        "evoker",
+        "1.13_major",
+    ),
+    (
+        AliasKind::Entity,
+        "vindication_illager",
+        "vindicator",
+        "1.13_major",
+    ),
+    (
+        AliasKind::Entity,
+        "illusion_illager",
+        "illusioner",
+        "1.13_major",
+    ),
+    // Later renames.
+    (AliasKind::Block, "sign", "oak_sign", "1.14_major"),
+    (AliasKind::Block, "wall_sign", "oak_wall_sign", "1.14_major"),
+    (AliasKind::Item, "sign", "oak_sign", "1.14_major"),
+    (
+        AliasKind::Entity,
+        "zombie_pigman",
+        "zombified_piglin",
+        "1.16_major",
+    ),
+    (
+        AliasKind::Item,
+        "zombie_pigman_spawn_egg",
+        "zombified_piglin_spawn_egg",
+        "1.16_major",
+    ),
+    (AliasKind::Block, "grass_path", "dirt_path", "1.17_major"),
+    (AliasKind::Item, "grass_path", "dirt_path", "1.17_major"),
+    (AliasKind::Block, "grass", "short_grass", "1.20.3"),
+    (AliasKind::Item, "grass", "short_grass", "1.20.3"),
+    (AliasKind::Item, "scute", "turtle_scute", "1.20.5"),
+];
+
+// The `contents` of a 1.12 `flower_pot` and the block it became in 1.13.
+const LEGACY_FLOWER_POTS: &[(&str, &str)] = &[
+    ("empty", "flower_pot"),
+    ("rose", "potted_poppy"),
+    ("blue_orchid", "potted_blue_orchid"),
+    ("allium", "potted_allium"),
+    ("houstonia", "potted_azure_bluet"),
+    ("red_tulip", "potted_red_tulip"),
+    ("orange_tulip", "potted_orange_tulip"),
+    ("white_tulip", "potted_white_tulip"),
+    ("pink_tulip", "potted_pink_tulip"),
+    ("oxeye_daisy", "potted_oxeye_daisy"),
+    ("dandelion", "potted_dandelion"),
+    ("oak_sapling", "potted_oak_sapling"),
+    ("spruce_sapling", "potted_spruce_sapling"),
+    ("birch_sapling", "potted_birch_sapling"),
+    ("jungle_sapling", "potted_jungle_sapling"),
+    ("acacia_sapling", "potted_acacia_sapling"),
+    ("dark_oak_sapling", "potted_dark_oak_sapling"),
+    ("mushroom_red", "potted_red_mushroom"),
+    ("mushroom_brown", "potted_brown_mushroom"),
+    ("dead_bush", "potted_dead_bush"),
+    ("fern", "potted_fern"),
+    ("cactus", "potted_cactus"),
+];
+
+// Aliases registered with `AliasTable::register`, per edition in registration order.
+static REGISTERED: Lazy<RwLock<HashMap<Edition, Vec<Alias>>>> = Lazy::new(Default::default);
+
+// Curated aliases with resolved versions, per edition.
+static CURATED: Lazy<RwLock<HashMap<Edition, Arc<Vec<Alias>>>>> = Lazy::new(Default::default);
+
+/// The kind of named entry an alias applies to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AliasKind {
+    Block,
+    Item,
+    Entity,
+}
+
+/// A rename of a block, item or entity.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Alias {
+    pub kind: AliasKind,
+    /// The name used before `renamed_in`.
+    pub old_name: String,
+    /// The name used from `renamed_in` onwards.
+    pub new_name: String,
+    /// The first version using the new name.
+    pub renamed_in: Version,
+}
+
+/// The result of resolving a possibly outdated name against a version.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CanonicalName {
+    /// The name used by the version the name was resolved against.
+    pub name: String,
+    /// The version of the last rename followed, or `None` if the name was already current.
+    pub renamed_in: Option<Version>,
+}
+
+/// A set of renames for one edition.
+///
+/// `IndexedData::canonical_name` and the `*_by_alias` lookups consult the registered tables
+/// (later registrations first) followed by the curated table.
+///
+/// Aliases map one name to one name, so pre-1.13 blocks that split into several blocks are
+/// resolved elsewhere: `IndexedData::flower_pot_by_contents` for `flower_pot`, and
+/// `LegacyConverter` for metadata variants such as `wool:14`.
+#[derive(Debug, Clone)]
+pub struct AliasTable {
+    edition: Edition,
+    aliases: Vec<Alias>,
+}
+
+impl AliasTable {
+    /// Creates an empty table for an edition.
+    pub fn new(edition: Edition) -> Self {
+        AliasTable {
+            edition,
+            aliases: Vec::new(),
+        }
+    }
+
+    /// The built-in table of well-known renames. Bedrock currently has no curated entries.
+    ///
+    /// Entries whose version is unknown to the loaded `protocolVersions.json` are skipped.
+    ///
+    /// # Errors
+    /// Returns `McDataError` if the version data of the edition cannot be loaded.
+    pub fn curated(edition: Edition) -> Result<Self, McDataError> {
+        Ok(AliasTable {
+            edition,
+            aliases: curated_aliases(edition)?.to_vec(),
+        })
+    }
+
+    /// Derives renames from the data of several versions of one edition, using the rename
+    /// detection of `diff` between each pair of consecutive versions. The edition is taken
+    /// from the first version; an empty slice yields an empty PC table.
+    ///
+    /// # Errors
+    /// Returns `McDataError::EditionMismatch` if the versions are not all of the same edition.
+    pub fn derive(versions: &[&IndexedData]) -> Result<Self, McDataError> {
+        let Some(first) = versions.first() else {
+            return Ok(AliasTable::new(Edition::Pc));
+        };
+        let edition = first.version.edition;
+        if let Some(other) = versions.iter().find(|d| d.version.edition != edition) {
+            return Err(McDataError::EditionMismatch {
+                left: edition,
+                left_version: first.version.minecraft_version.clone(),
+                right: other.version.edition,
+                right_version: other.version.minecraft_version.clone(),
+            });
+        }
+        let mut sorted = versions.to_vec();
+        sorted.sort_by(|a, b| a.version.cmp(&b.version));
+
+        let mut table = AliasTable::new(edition);
+        for pair in sorted.windows(2) {
+            let (old, new) = (pair[0], pair[1]);
+            let categories = [
+                (
+                    AliasKind::Block,
+                    diff_category(&old.blocks_array, &new.blocks_array),
+                ),
+                (
+                    AliasKind::Item,
+                    diff_category(&old.items_array, &new.items_array),
+                ),
+                (
+                    AliasKind::Entity,
+                    diff_category(&old.entities_array, &new.entities_array),
+                ),
+            ];
+            for (kind, CategoryDiff { renamed, .. }) in categories {
+                for rename in renamed {
+                    table.aliases.push(Alias {
+                        kind,
+                        old_name: rename.from,
+                        new_name: rename.to,
+                        renamed_in: new.version.clone(),
+                    });
+                }
+            }
+        }
+        log::debug!(
+            "Derived {} aliases from {} {:?} versions",
+            table.aliases.len(),
+            sorted.len(),
+            edition
+        );
+        Ok(table)
+    }
+
+    /// Adds a rename.
+    pub fn alias(mut self, alias: Alias) -> Self {
+        self.aliases.push(alias);
+        self
+    }
+
+    /// The renames in this table.
+    pub fn aliases(&self) -> &[Alias] {
+        &self.aliases
+    }
+
+    /// Resolves `name` against `data` using only this table.
+    pub fn resolve(
+        &self,
+        data: &IndexedData,
+        kind: AliasKind,
+        name: &str,
+    ) -> Option<CanonicalName> {
+        resolve_name(&self.aliases, &data.version, kind, name, None, |n| {
+            data.has_name(kind, n)
+        })
+    }
+
+    /// Resolves `name`, as used by the `source` version, against `data` using only this table.
+    pub fn resolve_from(
+        &self,
+        data: &IndexedData,
+        kind: AliasKind,
+        name: &str,
+        source: &Version,
+    ) -> Option<CanonicalName> {
+        resolve_name(
+            &self.aliases,
+            &data.version,
+            kind,
+            name,
+            Some(source),
+            |n| data.has_name(kind, n),
+        )
+    }
+
+    /// Registers the table globally, so `IndexedData` alias lookups consult it before earlier
+    /// registrations and the curated table.
+    ///
+    /// # Errors
+    /// Returns `McDataError::Internal` if the alias registry lock is poisoned.
+    pub fn register(self) -> Result<(), McDataError> {
+        let mut registered = REGISTERED
+            .write()
+            .map_err(|_| McDataError::Internal("Alias registry lock poisoned".to_string()))?;
+        log::debug!(
+            "Registering {} aliases for {:?}",
+            self.aliases.len(),
+            self.edition
+        );
+        registered
+            .entry(self.edition)
+            .or_default()
+            .extend(self.aliases);
+        Ok(())
+    }
+}
+
+/// Removes all registered alias tables for an edition. The curated table is unaffected.
+///
+/// # Errors
+/// Returns `McDataError::Internal` if the alias registry lock is poisoned.
+pub fn clear_aliases(edition: Edition) -> Result<(), McDataError> {
+    REGISTERED
+        .write()
+        .map_err(|_| McDataError::Internal("Alias registry lock poisoned".to_string()))?
+        .remove(&edition);
+    Ok(())
+}
+
+fn curated_aliases(edition: Edition) -> Result<Arc<Vec<Alias>>, McDataError> {
+    {
+        let curated = CURATED
+            .read()
+            .map_err(|_| McDataError::Internal("Curated alias lock poisoned".to_string()))?;
+        if let Some(aliases) = curated.get(&edition) {
+            return Ok(aliases.clone());
+        }
+    } // Read lock is released here.
+
+    let entries = match edition {
+        Edition::Pc => CURATED_PC,
+        Edition::Bedrock => &[],
+    };
+    let mut aliases = Vec::with_capacity(entries.len());
+    for &(kind, old_name, new_name, renamed_in) in entries {
+        match resolve_range_bound(edition, renamed_in, RangeBound::Min) {
+            Ok(version) => aliases.push(Alias {
+                kind,
+                old_name: old_name.to_string(),
+                new_name: new_name.to_string(),
+                renamed_in: version,
+            }),
+            Err(e) => log::warn!(
+                "Skipping curated alias {} -> {}: cannot resolve '{}': {}",
+                old_name,
+                new_name,
+                renamed_in,
+                e
+            ),
+        }
+    }
+    let aliases = Arc::new(aliases);
+    CURATED
+        .write()
+        .map_err(|_| McDataError::Internal("Curated alias lock poisoned".to_string()))?
+        .insert(edition, aliases.clone());
+    Ok(aliases)
+}
+
+/// The registered aliases of an edition (latest registration first) followed by the curated ones.
+pub(crate) fn active_aliases(edition: Edition) -> Result<Vec<Alias>, McDataError> {
+    let registered = REGISTERED
+        .read()
+        .map_err(|_| McDataError::Internal("Alias registry lock poisoned".to_string()))?;
+    let mut aliases: Vec<Alias> = registered
+        .get(&edition)
+        .map(|aliases| aliases.iter().rev().cloned().collect())
+        .unwrap_or_default();
+    drop(registered);
+    aliases.extend(curated_aliases(edition)?.iter().cloned());
+    Ok(aliases)
+}
+
+/// The 1.13 block of a 1.12 `flower_pot` with the given `contents` property value.
+pub(crate) fn legacy_flower_pot(contents: &str) -> Option<&'static str> {
+    LEGACY_FLOWER_POTS
+        .iter()
+        .find(|(legacy, _)| *legacy == contents)
+        .map(|&(_, block)| block)
+}
+
+// Renames are rarely chained more than twice; this bounds cycles in user-supplied tables.
+const MAX_RENAME_CHAIN: usize = 8;
+
+/// Resolves `name` for `version`, where `exists` tells whether a name is used by it.
+///
+/// With a `source` version, the name is read as used by that version and the renames between
+/// the two versions are followed in order, so names reused for different entries (e.g.,
+/// `grass`) resolve by their meaning at the source. Without one, a current name resolves to
+/// itself and any other name is read in its latest meaning, following renames forward or
+/// backward for versions predating it.
+pub(crate) fn resolve_name(
+    aliases: &[Alias],
+    version: &Version,
+    kind: AliasKind,
+    name: &str,
+    source: Option<&Version>,
+    exists: impl Fn(&str) -> bool,
+) -> Option<CanonicalName> {
+    if source.is_none() && exists(name) {
+        return Some(CanonicalName {
+            name: name.to_string(),
+            renamed_in: None,
+        });
+    }
+
+    let candidates: Vec<&Alias> = aliases
+        .iter()
+        .filter(|a| a.kind == kind && a.renamed_in.edition == version.edition)
+        .collect();
+    let directions: &[bool] = match source {
+        Some(source) => &[source <= version],
+        None => &[true, false],
+    };
+    for &forward in directions {
+        let mut current = name;
+        let mut last: Option<&Alias> = None;
+        for _ in 0..MAX_RENAME_CHAIN {
+            // Forward steps apply renames after the previous one, up to the version; backward
+            // steps undo renames before the previous one, down to the version.
+            let applies = |a: &&&Alias| {
+                if forward {
+                    a.old_name == current
+                        && a.renamed_in <= *version
+                        && match (last, source) {
+                            (Some(prev), _) => a.renamed_in > prev.renamed_in,
+                            (None, Some(source)) => a.renamed_in > *source,
+                            (None, None) => true,
+                        }
+                } else {
+                    a.new_name == current
+                        && a.renamed_in > *version
+                        && match (last, source) {
+                            (Some(prev), _) => a.renamed_in < prev.renamed_in,
+                            (None, Some(source)) => a.renamed_in <= *source,
+                            (None, None) => true,
+                        }
+                }
+            };
+            // With a source the renames are taken in order; without one, forward steps take
+            // the latest rename of a name. Ties keep table order, so registrations win.
+            let step = if forward && source.is_some() {
+                candidates
+                    .iter()
+                    .filter(applies)
+                    .min_by(|a, b| a.renamed_in.cmp(&b.renamed_in))
+            } else {
+                candidates
+                    .iter()
+                    .filter(applies)
+                    .min_by(|a, b| b.renamed_in.cmp(&a.renamed_in))
+            };
+            let Some(alias) = step else { break };
+            current = if forward {
+                &alias.new_name
+            } else {
+                &alias.old_name
+            };
+            last = Some(alias);
+        }
+        if (last.is_some() || source.is_some()) && exists(current) {
+            return Some(CanonicalName {
+                name: current.to_string(),
+                renamed_in: last.map(|a| a.renamed_in.clone()),
+            });
+        }
+    }
+    None
+}
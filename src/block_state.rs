@@ -0,0 +1,270 @@
+use crate::error::McDataError;
+use crate::structs::{Block, BlockStateDefinition};
+use std::fmt;
+use std::sync::Arc;
+
+/// A block together with the values of its state properties, e.g.
+/// `oak_stairs[facing=north,half=top,shape=straight,waterlogged=false]`.
+///
+/// Returned by `IndexedData::block_state`. State IDs enumerate property values in the same
+/// mixed-radix order as vanilla: properties are ordered as in `Block.states`, the last one
+/// varies fastest, and boolean properties list `true` before `false`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockState {
+    layout: Arc<StateLayout>,
+    indices: Vec<usize>,
+    state_id: u32,
+}
+
+impl BlockState {
+    /// Decodes a state of `block`, or returns `None` if `state_id` is outside its range.
+    pub(crate) fn decode(block: &Block, state_id: u32) -> Option<Self> {
+        let layout = Arc::new(StateLayout::of(block));
+        let indices = layout.decode(state_id)?;
+        Some(BlockState {
+            layout,
+            indices,
+            state_id,
+        })
+    }
+
+    /// The block name (e.g., "oak_stairs").
+    pub fn name(&self) -> &str {
+        &self.layout.name
+    }
+
+    /// The state ID.
+    pub fn state_id(&self) -> u32 {
+        self.state_id
+    }
+
+    /// Whether this is the block's default state.
+    pub fn is_default(&self) -> bool {
+        self.state_id == self.layout.default_state
+    }
+
+    /// The property names and values, in `Block.states` order.
+    pub fn properties(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.layout
+            .properties
+            .iter()
+            .zip(&self.indices)
+            .map(|((name, values), &index)| (name.as_str(), values[index].as_str()))
+    }
+
+    /// The value of one property, or `None` if the block has no such property.
+    pub fn property(&self, name: &str) -> Option<&str> {
+        self.properties()
+            .find(|(property, _)| *property == name)
+            .map(|(_, value)| value)
+    }
+
+    /// Returns this state with one property changed.
+    ///
+    /// # Errors
+    /// Returns `McDataError::UnknownBlockProperty` if the block has no such property and
+    /// `McDataError::InvalidBlockPropertyValue` if the property does not accept `value`.
+    pub fn with_property(&self, name: &str, value: &str) -> Result<BlockState, McDataError> {
+        let (property, index) = self.layout.value_index(name, value)?;
+        let mut indices = self.indices.clone();
+        indices[property] = index;
+        Ok(BlockState {
+            state_id: self.layout.encode(&indices),
+            layout: self.layout.clone(),
+            indices,
+        })
+    }
+}
+
+/// Formats the state in the bracketed syntax used by commands, e.g.
+/// `minecraft:oak_stairs[facing=north,half=bottom,shape=straight,waterlogged=false]`.
+/// Blocks without properties are formatted without brackets.
+impl fmt::Display for BlockState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "minecraft:{}", self.name())?;
+        if self.indices.is_empty() {
+            return Ok(());
+        }
+        f.write_str("[")?;
+        for (position, (name, value)) in self.properties().enumerate() {
+            if position > 0 {
+                f.write_str(",")?;
+            }
+            write!(f, "{}={}", name, value)?;
+        }
+        f.write_str("]")
+    }
+}
+
+// A block state string split into the block name and its `property=value` pairs.
+pub(crate) type ParsedBlockState<'a> = (&'a str, Vec<(&'a str, &'a str)>);
+
+/// Splits a block state string such as `minecraft:oak_stairs[facing=north,half=top]` into the
+/// block name (without the `minecraft:` namespace) and its properties, checking only syntax.
+pub(crate) fn parse_block_state_string(input: &str) -> Result<ParsedBlockState<'_>, McDataError> {
+    let invalid = |reason: String| McDataError::InvalidBlockStateString {
+        input: input.to_string(),
+        reason,
+    };
+    let trimmed = input.trim();
+    let (name, rest) = match trimmed.find('[') {
+        Some(open) => (&trimmed[..open], Some(&trimmed[open + 1..])),
+        None => (trimmed, None),
+    };
+    let name = name.trim();
+    let name = name.strip_prefix("minecraft:").unwrap_or(name);
+    if name.is_empty() {
+        return Err(invalid("missing block name".to_string()));
+    }
+    if let Some(c) = name
+        .chars()
+        .find(|c| !(c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.' | ':' | '/')))
+    {
+        return Err(invalid(format!("unexpected `{}` in block name", c)));
+    }
+
+    let mut properties: Vec<(&str, &str)> = Vec::new();
+    if let Some(rest) = rest {
+        let body = rest
+            .trim_end()
+            .strip_suffix(']')
+            .ok_or_else(|| invalid("missing closing `]`".to_string()))?;
+        if body.contains('[') || body.contains(']') {
+            return Err(invalid("unexpected bracket in property list".to_string()));
+        }
+        if !body.trim().is_empty() {
+            for pair in body.split(',') {
+                let (property, value) = pair
+                    .split_once('=')
+                    .map(|(property, value)| (property.trim(), value.trim()))
+                    .ok_or_else(|| {
+                        invalid(format!(
+                            "expected `property=value`, found `{}`",
+                            pair.trim()
+                        ))
+                    })?;
+                if property.is_empty() || value.is_empty() {
+                    return Err(invalid(format!(
+                        "expected `property=value`, found `{}`",
+                        pair.trim()
+                    )));
+                }
+                if properties.iter().any(|(existing, _)| *existing == property) {
+                    return Err(invalid(format!(
+                        "property `{}` is given more than once",
+                        property
+                    )));
+                }
+                properties.push((property, value));
+            }
+        }
+    }
+    Ok((name, properties))
+}
+
+/// The state properties of a block and the state ID range they enumerate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct StateLayout {
+    pub(crate) name: String,
+    pub(crate) min_state_id: u32,
+    pub(crate) max_state_id: u32,
+    pub(crate) default_state: u32,
+    // Property names with their values, in state ID order.
+    pub(crate) properties: Vec<(String, Vec<String>)>,
+}
+
+impl StateLayout {
+    pub(crate) fn of(block: &Block) -> Self {
+        StateLayout {
+            name: block.name.clone(),
+            min_state_id: block.min_state_id,
+            max_state_id: block.max_state_id,
+            default_state: block.default_state,
+            properties: block
+                .states
+                .iter()
+                .map(|definition| (definition.name.clone(), property_values(definition)))
+                .collect(),
+        }
+    }
+
+    /// Splits a state ID into value indices, one per property.
+    ///
+    /// Blocks without properties (including pre-1.13 metadata ranges) accept any ID in their range.
+    pub(crate) fn decode(&self, state_id: u32) -> Option<Vec<usize>> {
+        if state_id < self.min_state_id || state_id > self.max_state_id {
+            return None;
+        }
+        let mut remainder = (state_id - self.min_state_id) as usize;
+        let mut indices = vec![0; self.properties.len()];
+        for (index, (_, values)) in indices.iter_mut().zip(&self.properties).rev() {
+            let radix = values.len().max(1);
+            *index = remainder % radix;
+            remainder /= radix;
+        }
+        // IDs past the enumerated combinations do not decode.
+        (remainder == 0 || self.properties.is_empty()).then_some(indices)
+    }
+
+    /// Combines value indices, one per property, into a state ID.
+    pub(crate) fn encode(&self, indices: &[usize]) -> u32 {
+        if self.properties.is_empty() {
+            return self.default_state;
+        }
+        let offset = self
+            .properties
+            .iter()
+            .zip(indices)
+            .fold(0usize, |offset, ((_, values), &index)| {
+                offset * values.len().max(1) + index
+            });
+        self.min_state_id + offset as u32
+    }
+
+    /// The value indices of the default state.
+    pub(crate) fn default_indices(&self) -> Vec<usize> {
+        self.decode(self.default_state)
+            .unwrap_or_else(|| vec![0; self.properties.len()])
+    }
+
+    /// Finds a property by name and the index of one of its values.
+    pub(crate) fn value_index(
+        &self,
+        property: &str,
+        value: &str,
+    ) -> Result<(usize, usize), McDataError> {
+        let (position, values) = self
+            .properties
+            .iter()
+            .enumerate()
+            .find(|(_, (name, _))| name == property)
+            .map(|(position, (_, values))| (position, values))
+            .ok_or_else(|| McDataError::UnknownBlockProperty {
+                block: self.name.clone(),
+                property: property.to_string(),
+            })?;
+        let index = values.iter().position(|v| v == value).ok_or_else(|| {
+            McDataError::InvalidBlockPropertyValue {
+                block: self.name.clone(),
+                property: property.to_string(),
+                value: value.to_string(),
+                expected: values.clone(),
+            }
+        })?;
+        Ok((position, index))
+    }
+}
+
+/// The values of a state property in state ID order. Upstream data lists them for enum and most
+/// int properties; booleans are `true`, `false`, and unlisted ints count up from 0.
+pub(crate) fn property_values(definition: &BlockStateDefinition) -> Vec<String> {
+    if !definition.values.is_empty() {
+        return definition.values.clone();
+    }
+    if definition.state_type == "bool" {
+        return vec!["true".to_string(), "false".to_string()];
+    }
+    (0..definition.num_values.unwrap_or(1))
+        .map(|value| value.to_string())
+        .collect()
+}
@@ -1,13 +1,26 @@
+use crate::aliases::{self, AliasKind, CanonicalName};
+use crate::block_state::{self, BlockState, StateLayout};
+use crate::chunk::{self, ChunkSection, PackedSection, PaletteLayout};
 use crate::data_source;
-use crate::error::McDataError;
-use crate::features;
+use crate::dig::{self, DigOptions};
+use crate::error::{Edition, McDataError};
+use crate::feature_flags::{FeatureFlag, FromFeatureValue, TheFlattening};
+use crate::features::{self, resolve_range_bound, FeatureTable, RangeBound, ResolvedFeature};
 use crate::indexer;
 use crate::loader;
+use crate::paths;
+use crate::shapes::{Face, VoxelShape};
 use crate::structs::*;
+use crate::tints::{self, TintSource};
+use crate::tools::{self, ToolEffect};
 use crate::version::Version;
 use serde_json::Value;
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
+
+// Bits per entry of the global block palette before the flattening (1.9-1.12).
+const LEGACY_GLOBAL_PALETTE_BITS: u32 = 13;
 
 /// Holds all loaded and indexed Minecraft data for a specific version.
 ///
@@ -105,19 +118,38 @@ pub struct IndexedData {
     pub block_collision_shapes_raw: Arc<Option<BlockCollisionShapes>>,
     /// Data from tints.json, if available.
     pub tints: Arc<Option<Tints>>,
+    /// Tool speed multipliers per block material from materials.json, if available.
+    pub materials: Arc<Option<Materials>>,
     /// Data from language.json (typically en_us), if available.
     pub language: Arc<HashMap<String, String>>,
     /// Data from legacy.json (mapping old IDs to new), if available.
     pub legacy: Arc<Option<Legacy>>,
 
+    // Bedrock-specific data (empty or `None` for PC versions).
+    /// Data from blockStates.json, indexed by runtime block state ID.
+    pub block_states: Arc<Vec<BedrockBlockState>>,
+    /// Data from blockMappings.json, pairing Java and Bedrock block states.
+    pub block_mappings: Arc<Vec<BlockMapping>>,
+    /// Data from blocksB2J.json, mapping Bedrock block state strings to Java ones.
+    pub blocks_b2j: Arc<HashMap<String, String>>,
+    /// Data from blocksJ2B.json, mapping Java block state strings to Bedrock ones.
+    pub blocks_j2b: Arc<HashMap<String, String>>,
+    /// Default skin data from steve.json, if available.
+    pub steve: Arc<Option<SteveSkin>>,
+    /// Raw contents of proto.yml (the Bedrock protocol definition source), if available.
+    pub proto_yml: Arc<Option<String>>,
+
     // Raw JSON values for data types that vary significantly across versions
     // or are too complex to represent with stable structs easily.
     pub recipes: Arc<Option<Value>>,
-    pub materials: Arc<Option<Value>>,
     pub commands: Arc<Option<Value>>,
     pub protocol: Arc<Option<Value>>, // Raw protocol.json content
     pub protocol_comments: Arc<Option<Value>>, // Raw protocolComments.json content
     pub login_packet: Arc<Option<Value>>, // Raw loginPacket.json content
+
+    // Feature values precomputed at load time; `None` if features.json could not be evaluated,
+    // in which case feature lookups fall back to live evaluation.
+    pub(crate) feature_table: Arc<Option<FeatureTable>>,
 }
 
 impl IndexedData {
@@ -129,7 +161,9 @@ impl IndexedData {
             version.minecraft_version,
             version.edition
         );
-        let major_version_str = &version.major_version; // Use major version for path lookups
+        // dataPaths.json key holding this version's data (exact version, major version, or an older fallback).
+        let paths_key = paths::data_paths_key(&version)?;
+        let paths_key = paths_key.as_str();
         let edition = version.edition;
 
         // Helper macro to load optional data of a specific type.
@@ -137,7 +171,7 @@ impl IndexedData {
         // Propagates other errors (e.g., parse errors).
         macro_rules! load_optional {
             ($key:expr, $type:ty) => {
-                match loader::load_data::<$type>(edition, major_version_str, $key) {
+                match loader::load_data::<$type>(edition, paths_key, $key) {
                     Ok(data) => {
                         log::trace!("Successfully loaded optional data for key '{}'", $key);
                         Some(data)
@@ -159,7 +193,7 @@ impl IndexedData {
         // Helper macro similar to load_optional!, but for loading raw `serde_json::Value`.
         macro_rules! load_optional_value {
             ($key:expr) => {
-                match loader::load_data::<Value>(edition, major_version_str, $key) {
+                match loader::load_data::<Value>(edition, paths_key, $key) {
                     Ok(data) => {
                         log::trace!("Successfully loaded optional value for key '{}'", $key);
                         Some(data)
@@ -179,8 +213,8 @@ impl IndexedData {
 
         // --- Load Raw Data Arrays/Maps ---
         // Load required data types (expect them to exist for any valid version).
-        let blocks: Vec<Block> = loader::load_data(edition, major_version_str, "blocks")?;
-        let items: Vec<Item> = loader::load_data(edition, major_version_str, "items")?;
+        let blocks: Vec<Block> = loader::load_data(edition, paths_key, "blocks")?;
+        let items: Vec<Item> = loader::load_data(edition, paths_key, "items")?;
 
         // Load optional data types using the helper macro. Use `unwrap_or_default` for Vec/HashMap.
         let biomes: Vec<Biome> = load_optional!("biomes", Vec<Biome>).unwrap_or_default();
@@ -205,17 +239,34 @@ impl IndexedData {
         let block_collision_shapes_raw: Option<BlockCollisionShapes> =
             load_optional!("blockCollisionShapes", BlockCollisionShapes);
         let tints: Option<Tints> = load_optional!("tints", Tints);
+        let materials: Option<Materials> = load_optional!("materials", Materials);
         let language: HashMap<String, String> =
             load_optional!("language", HashMap<String, String>).unwrap_or_default();
 
         // Load optional raw JSON values.
         let recipes: Option<Value> = load_optional_value!("recipes");
-        let materials: Option<Value> = load_optional_value!("materials");
         let commands: Option<Value> = load_optional_value!("commands");
         let protocol: Option<Value> = load_optional_value!("protocol");
         let protocol_comments: Option<Value> = load_optional_value!("protocolComments");
         let login_packet: Option<Value> = load_optional_value!("loginPacket");
 
+        // Load Bedrock-specific data. These keys are absent from PC dataPaths entries.
+        let block_states: Vec<BedrockBlockState> =
+            load_optional!("blockStates", Vec<BedrockBlockState>).unwrap_or_default();
+        let block_mappings: Vec<BlockMapping> =
+            load_optional!("blockMappings", Vec<BlockMapping>).unwrap_or_default();
+        let blocks_b2j: HashMap<String, String> =
+            load_optional!("blocksB2J", HashMap<String, String>).unwrap_or_default();
+        let blocks_j2b: HashMap<String, String> =
+            load_optional!("blocksJ2B", HashMap<String, String>).unwrap_or_default();
+        let steve: Option<SteveSkin> = load_optional!("steve", SteveSkin);
+        let proto_yml: Option<String> = match loader::load_text_data(edition, paths_key, "proto") {
+            Ok(text) => Some(text),
+            Err(McDataError::DataPathNotFound { .. })
+            | Err(McDataError::DataFileNotFound { .. }) => None,
+            Err(e) => return Err(e),
+        };
+
         // Load legacy.json (common data, path constructed differently from versioned data).
         let legacy: Option<Legacy> = {
             match data_source::get_data_root() {
@@ -280,6 +331,9 @@ impl IndexedData {
             (HashMap::new(), HashMap::new())
         };
 
+        // Precompute feature values so feature checks avoid range resolution on every call.
+        let feature_table = build_feature_table(&version);
+
         log::info!(
             "Finished loading and indexing data for {} ({:?})",
             version.minecraft_version,
@@ -343,72 +397,571 @@ impl IndexedData {
             // Other Data
             block_collision_shapes_raw: Arc::new(block_collision_shapes_raw),
             tints: Arc::new(tints),
+            materials: Arc::new(materials),
             language: Arc::new(language),
             legacy: Arc::new(legacy),
+            // Bedrock Data
+            block_states: Arc::new(block_states),
+            block_mappings: Arc::new(block_mappings),
+            blocks_b2j: Arc::new(blocks_b2j),
+            blocks_j2b: Arc::new(blocks_j2b),
+            steve: Arc::new(steve),
+            proto_yml: Arc::new(proto_yml),
             // Raw Values
             recipes: Arc::new(recipes),
-            materials: Arc::new(materials),
             commands: Arc::new(commands),
             protocol: Arc::new(protocol),
             protocol_comments: Arc::new(protocol_comments),
             login_packet: Arc::new(login_packet),
+            feature_table: Arc::new(feature_table),
         })
     }
 
+    /// Returns the precomputed feature table, unless feature overlays changed since it was built.
+    fn current_feature_table(&self) -> Option<&FeatureTable> {
+        self.feature_table
+            .as_ref()
+            .as_ref()
+            .filter(|table| table.is_current())
+    }
+
+    /// Whether feature lookups can use the precomputed table. Data whose table could not be
+    /// built at all is considered current, since rebuilding it would fail the same way.
+    pub(crate) fn has_current_feature_table(&self) -> bool {
+        self.feature_table
+            .as_ref()
+            .as_ref()
+            .is_none_or(|table| table.is_current())
+    }
+
+    /// Returns a copy of this data with its feature table rebuilt for the registered overlays.
+    pub(crate) fn with_refreshed_feature_table(&self) -> IndexedData {
+        IndexedData {
+            feature_table: Arc::new(build_feature_table(&self.version)),
+            ..self.clone()
+        }
+    }
+
     /// Checks if the current data's version is newer than or equal to another version string.
     ///
-    /// Resolves the `other_version_str` and compares using the `Version` struct's `Ord` implementation.
+    /// Resolves the `other_version_str` and compares using `Version::cmp_same_edition`.
     ///
     /// # Errors
-    /// Returns `McDataError::InvalidVersion` if `other_version_str` is invalid.
-    /// Returns `McDataError::Internal` if attempting to compare versions from different editions.
+    /// Returns `McDataError::UnknownVersion` if `other_version_str` cannot be resolved.
+    /// Returns `McDataError::EditionMismatch` if attempting to compare versions from different editions.
     pub fn is_newer_or_equal_to(&self, other_version_str: &str) -> Result<bool, McDataError> {
         let other_version = crate::version::resolve_version(other_version_str)?;
-        // Ensure comparison happens only within the same edition.
-        if self.version.edition == other_version.edition {
-            Ok(self.version >= other_version) // Uses the Ord implementation for Version
-        } else {
-            Err(McDataError::Internal(format!(
-                "Cannot compare versions from different editions: {:?} ({}) and {:?} ({})",
-                self.version.edition,
-                self.version.minecraft_version,
-                other_version.edition,
-                other_version.minecraft_version
-            )))
-        }
+        Ok(self.version.cmp_same_edition(&other_version)?.is_ge())
     }
 
     /// Checks if the current data's version is strictly older than another version string.
     ///
-    /// Resolves the `other_version_str` and compares using the `Version` struct's `Ord` implementation.
+    /// Resolves the `other_version_str` and compares using `Version::cmp_same_edition`.
     ///
     /// # Errors
-    /// Returns `McDataError::InvalidVersion` if `other_version_str` is invalid.
-    /// Returns `McDataError::Internal` if attempting to compare versions from different editions.
+    /// Returns `McDataError::UnknownVersion` if `other_version_str` cannot be resolved.
+    /// Returns `McDataError::EditionMismatch` if attempting to compare versions from different editions.
     pub fn is_older_than(&self, other_version_str: &str) -> Result<bool, McDataError> {
         let other_version = crate::version::resolve_version(other_version_str)?;
-        // Ensure comparison happens only within the same edition.
-        if self.version.edition == other_version.edition {
-            Ok(self.version < other_version) // Uses the Ord implementation for Version
-        } else {
-            Err(McDataError::Internal(format!(
-                "Cannot compare versions from different editions: {:?} ({}) and {:?} ({})",
-                self.version.edition,
-                self.version.minecraft_version,
-                other_version.edition,
-                other_version.minecraft_version
-            )))
-        }
+        Ok(self.version.cmp_same_edition(&other_version)?.is_lt())
     }
 
     /// Checks support for a named feature based on the current data's version.
     ///
     /// Consults the `features.json` data and returns the feature's value (often boolean,
     /// but can be other JSON types) if supported for this version, or `Value::Bool(false)` otherwise.
+    /// Values are precomputed when the data is loaded, so this is a lock-free map lookup.
     ///
     /// # Errors
     /// Returns `McDataError` if feature data or version information cannot be loaded or resolved.
     pub fn support_feature(&self, feature_name: &str) -> Result<Value, McDataError> {
-        features::get_feature_support(&self.version, feature_name)
+        match self.current_feature_table() {
+            Some(table) => Ok(table
+                .get(feature_name)
+                .map_or(Value::Bool(false), |feature| feature.value.clone())),
+            None => features::get_feature_support(&self.version, feature_name),
+        }
+    }
+
+    /// Returns every feature defined in `features.json` with its value for this version
+    /// and the version range that produced it.
+    ///
+    /// # Errors
+    /// Returns `McDataError` if feature data or version information cannot be loaded or resolved.
+    pub fn features(&self) -> Result<Vec<ResolvedFeature>, McDataError> {
+        match self.current_feature_table() {
+            Some(table) => Ok(table.all().to_vec()),
+            None => features::get_all_feature_support(&self.version),
+        }
+    }
+
+    /// Returns the raw value of a named feature for this version, like `support_feature`,
+    /// but fails for names that `features.json` does not define.
+    ///
+    /// # Errors
+    /// Returns `McDataError::UnknownFeature` if the feature name is not defined for this edition,
+    /// or another `McDataError` if feature data or version information cannot be loaded or resolved.
+    pub fn feature_value(&self, feature_name: &str) -> Result<Value, McDataError> {
+        match self.current_feature_table() {
+            Some(table) => table
+                .get(feature_name)
+                .map(|feature| feature.value.clone())
+                .ok_or_else(|| McDataError::UnknownFeature {
+                    name: feature_name.to_string(),
+                    edition: self.version.edition,
+                }),
+            None => features::get_feature_value(&self.version, feature_name),
+        }
+    }
+
+    /// Returns the typed value of a feature flag for this version.
+    ///
+    /// See `FeatureFlag` for the available flags, e.g. `data.feature::<TheFlattening>()`.
+    ///
+    /// # Errors
+    /// Returns `McDataError::UnknownFeature` if upstream data no longer defines the feature,
+    /// `McDataError::InvalidFeatureValue` if its value has an unexpected type,
+    /// or another `McDataError` if feature data or version information cannot be loaded or resolved.
+    pub fn feature<F: FeatureFlag>(&self) -> Result<F::Value, McDataError> {
+        let value = self.feature_value(F::NAME)?;
+        F::Value::from_feature_value(&value).ok_or_else(|| McDataError::InvalidFeatureValue {
+            name: F::NAME.to_string(),
+            expected: <F::Value as FromFeatureValue>::EXPECTED,
+            value,
+        })
+    }
+
+    /// Resolves a block, item or entity name that may be outdated (or newer than this version)
+    /// using the registered and curated alias tables (see `AliasTable`).
+    ///
+    /// Returns `None` if the name cannot be mapped to an entry of this version.
+    ///
+    /// # Errors
+    /// Returns `McDataError` if the curated alias versions cannot be resolved.
+    pub fn canonical_name(
+        &self,
+        kind: AliasKind,
+        name: &str,
+    ) -> Result<Option<CanonicalName>, McDataError> {
+        let aliases = aliases::active_aliases(self.version.edition)?;
+        Ok(aliases::resolve_name(
+            &aliases,
+            &self.version,
+            kind,
+            name,
+            None,
+            |name| self.has_name(kind, name),
+        ))
+    }
+
+    /// Resolves a block, item or entity name as used by the `source` version, following the
+    /// renames between the two versions in order. Unlike `canonical_name`, this tells apart
+    /// names that were reused for different entries, e.g. 1.12 `grass` (now `grass_block`)
+    /// and 1.13 `grass` (now `short_grass`).
+    ///
+    /// Returns `None` if the name cannot be mapped to an entry of this version.
+    ///
+    /// # Errors
+    /// Returns `McDataError::EditionMismatch` if `source` is of another edition, or another
+    /// `McDataError` if the curated alias versions cannot be resolved.
+    pub fn canonical_name_from(
+        &self,
+        kind: AliasKind,
+        name: &str,
+        source: &Version,
+    ) -> Result<Option<CanonicalName>, McDataError> {
+        if source.edition != self.version.edition {
+            return Err(McDataError::EditionMismatch {
+                left: source.edition,
+                left_version: source.minecraft_version.clone(),
+                right: self.version.edition,
+                right_version: self.version.minecraft_version.clone(),
+            });
+        }
+        let aliases = aliases::active_aliases(self.version.edition)?;
+        Ok(aliases::resolve_name(
+            &aliases,
+            &self.version,
+            kind,
+            name,
+            Some(source),
+            |name| self.has_name(kind, name),
+        ))
+    }
+
+    /// Resolves a 1.12 `flower_pot` by the value of its `contents` property (e.g., `rose`) to
+    /// the block of this version: a `potted_*` block from 1.13 onwards, or `flower_pot` before.
+    /// The returned `renamed_in` is the version that introduced the `potted_*` block, or that
+    /// of a later rename.
+    ///
+    /// Returns `None` for unknown contents, for Bedrock, and if this version lacks the block.
+    ///
+    /// # Errors
+    /// Returns `McDataError` if the curated alias versions cannot be resolved.
+    pub fn flower_pot_by_contents(
+        &self,
+        contents: &str,
+    ) -> Result<Option<CanonicalName>, McDataError> {
+        let Some(potted) = aliases::legacy_flower_pot(contents) else {
+            return Ok(None);
+        };
+        if self.version.edition != Edition::Pc {
+            return Ok(None);
+        }
+        let flattening = resolve_range_bound(Edition::Pc, "1.13_major", RangeBound::Min)?;
+        if self.version < flattening {
+            return Ok(self
+                .has_name(AliasKind::Block, "flower_pot")
+                .then(|| CanonicalName {
+                    name: "flower_pot".to_string(),
+                    renamed_in: None,
+                }));
+        }
+        Ok(self
+            .canonical_name_from(AliasKind::Block, potted, &flattening)?
+            .map(|resolved| CanonicalName {
+                renamed_in: Some(resolved.renamed_in.unwrap_or(flattening)),
+                ..resolved
+            }))
+    }
+
+    /// Decodes a block state ID into its block and property values.
+    ///
+    /// Returns `None` if no block of this version uses the state ID.
+    pub fn block_state(&self, state_id: u32) -> Option<BlockState> {
+        let block = self.blocks_by_state_id.get(&state_id)?;
+        BlockState::decode(block, state_id)
+    }
+
+    /// Encodes a block and property values into a state ID. Properties that are not given
+    /// take their values from the block's default state.
+    ///
+    /// # Errors
+    /// Returns `McDataError::UnknownEntry` if the block does not exist in this version,
+    /// `McDataError::UnknownBlockProperty` if it has no property of a given name, and
+    /// `McDataError::InvalidBlockPropertyValue` if a property does not accept a given value.
+    pub fn state_id_for<I, K, V>(&self, block: &str, properties: I) -> Result<u32, McDataError>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        let layout = StateLayout::of(self.blocks_by_name.get(block).ok_or_else(|| {
+            McDataError::UnknownEntry {
+                kind: "block",
+                name: block.to_string(),
+                edition: self.version.edition,
+                version: self.version.minecraft_version.clone(),
+            }
+        })?);
+        let mut indices = layout.default_indices();
+        for (name, value) in properties {
+            let (property, index) = layout.value_index(name.as_ref(), value.as_ref())?;
+            indices[property] = index;
+        }
+        Ok(layout.encode(&indices))
+    }
+
+    /// Parses a block state string such as `minecraft:oak_stairs[facing=north,half=top]`.
+    ///
+    /// The `minecraft:` namespace is optional. Properties that are not given take their values
+    /// from the block's default state; the state ID is available from `BlockState::state_id`.
+    ///
+    /// # Errors
+    /// Returns `McDataError::InvalidBlockStateString` if the string is malformed,
+    /// `McDataError::UnknownEntry` if the block does not exist in this version,
+    /// `McDataError::UnknownBlockProperty` if it has no property of a given name, and
+    /// `McDataError::InvalidBlockPropertyValue` if a property does not accept a given value.
+    pub fn parse_block_state(&self, input: &str) -> Result<BlockState, McDataError> {
+        let (name, properties) = block_state::parse_block_state_string(input)?;
+        let state_id = self.state_id_for(name, properties)?;
+        self.block_state(state_id).ok_or_else(|| {
+            McDataError::Internal(format!(
+                "State ID {} of block {} does not decode",
+                state_id, name
+            ))
+        })
+    }
+
+    /// Formats a block state ID in the bracketed syntax, e.g. `minecraft:stone` or
+    /// `minecraft:oak_stairs[facing=north,half=bottom,shape=straight,waterlogged=false]`.
+    ///
+    /// Returns `None` if no block of this version uses the state ID.
+    pub fn format_block_state(&self, state_id: u32) -> Option<String> {
+        self.block_state(state_id).map(|state| state.to_string())
+    }
+
+    /// The bits per entry of the global block palette: enough for the highest state ID from
+    /// 1.13 on, and 13 before the flattening.
+    ///
+    /// # Errors
+    /// Returns `McDataError` if feature data cannot be loaded or evaluated.
+    pub fn global_palette_bits(&self) -> Result<u32, McDataError> {
+        if !self.feature::<TheFlattening>()? {
+            return Ok(LEGACY_GLOBAL_PALETTE_BITS);
+        }
+        let states = self
+            .blocks_by_state_id
+            .keys()
+            .max()
+            .map_or(0, |max| *max as usize + 1);
+        Ok(chunk::ceil_log2(states))
+    }
+
+    /// Decodes the block states of a chunk section from its palette and packed longs,
+    /// using the long layout of this version (entries span longs before 20w17a, i.e. 1.16).
+    ///
+    /// # Errors
+    /// Returns `McDataError::InvalidChunkSection` if the data has the wrong length, a palette
+    /// index is out of range, or a state ID is unknown to this version, or if this is not a
+    /// Java Edition version of 1.9 or later.
+    pub fn decode_section(&self, packed: &PackedSection) -> Result<ChunkSection, McDataError> {
+        let layout = PaletteLayout::of(&self.version)?;
+        chunk::decode(packed, layout.spanning, |state_id| {
+            self.blocks_by_state_id.contains_key(&state_id)
+        })
+    }
+
+    /// Encodes the block states of a chunk section the way this version's server does:
+    /// a single value (from 21w37a, i.e. 1.18), an indirect palette of 4 to 8 bits, or the
+    /// global palette.
+    ///
+    /// # Errors
+    /// Returns `McDataError::InvalidChunkSection` if a state ID is unknown to this version or
+    /// this is not a Java Edition version of 1.9 or later, and another `McDataError` if feature data cannot be
+    /// loaded or evaluated.
+    pub fn encode_section(&self, section: &ChunkSection) -> Result<PackedSection, McDataError> {
+        let layout = PaletteLayout::of(&self.version)?;
+        if let Some(unknown) = section
+            .state_ids()
+            .iter()
+            .find(|state_id| !self.blocks_by_state_id.contains_key(state_id))
+        {
+            return Err(McDataError::InvalidChunkSection {
+                reason: format!("unknown block state ID {}", unknown),
+            });
+        }
+        Ok(chunk::encode(
+            section,
+            self.global_palette_bits()?,
+            layout.spanning,
+            layout.single_valued,
+        ))
+    }
+
+    /// The block states of a chunk section, in network order (`(y * 16 + z) * 16 + x`).
+    ///
+    /// # Errors
+    /// Returns `McDataError::InvalidChunkSection` if a state ID is unknown to this version.
+    pub fn section_block_states(
+        &self,
+        section: &ChunkSection,
+    ) -> Result<Vec<BlockState>, McDataError> {
+        let mut decoded: HashMap<u32, BlockState> = HashMap::new();
+        section
+            .state_ids()
+            .iter()
+            .map(|state_id| {
+                if let Some(state) = decoded.get(state_id) {
+                    return Ok(state.clone());
+                }
+                let state = self.block_state(*state_id).ok_or_else(|| {
+                    McDataError::InvalidChunkSection {
+                        reason: format!("unknown block state ID {}", state_id),
+                    }
+                })?;
+                decoded.insert(*state_id, state.clone());
+                Ok(state)
+            })
+            .collect()
+    }
+
+    /// Computes how long breaking `block` takes, like `block.digTime` in node-minecraft-data.
+    ///
+    /// Uses the block's hardness, harvest tools and material together with the tool speed
+    /// multipliers of `materials.json`. Returns `None` for unbreakable blocks and
+    /// `Duration::ZERO` for blocks that break instantly.
+    pub fn dig_time(&self, block: &Block, options: &DigOptions) -> Option<Duration> {
+        dig::dig_time(block, self.materials.as_ref().as_ref(), options)
+    }
+
+    /// The items effective on a material (e.g., "mineable/pickaxe") with their speed
+    /// multipliers, fastest first. Items unknown to this version are skipped.
+    pub fn effective_tools(&self, material: &str) -> Vec<(&Item, f32)> {
+        let Some(materials) = self.materials.as_ref() else {
+            return Vec::new();
+        };
+        materials
+            .effective_tools(material)
+            .into_iter()
+            .filter_map(|(id, speed)| Some((self.items_by_id.get(&id)?, speed)))
+            .collect()
+    }
+
+    /// How well an item (`None` for an empty hand) works on a block: its speed multiplier on
+    /// the block's material and whether the block drops.
+    pub fn tool_effect(&self, block: &Block, item_id: Option<u32>) -> ToolEffect {
+        tools::tool_effect(block, self.materials.as_ref().as_ref(), item_id)
+    }
+
+    /// Whether breaking `block` with an item (`None` for an empty hand) makes it drop.
+    pub fn can_harvest(&self, block: &Block, item_id: Option<u32>) -> bool {
+        tools::can_harvest(block, item_id)
+    }
+
+    /// Picks the best item for breaking `block` among `candidates` (e.g., an inventory's item
+    /// IDs): items that make the block drop are preferred, then faster ones. Returns `None` if
+    /// `candidates` is empty.
+    pub fn best_tool(&self, block: &Block, candidates: &[u32]) -> Option<u32> {
+        tools::best_tool(block, self.materials.as_ref().as_ref(), candidates)
+    }
+
+    /// The collision shape of a block state in block-local coordinates, built from
+    /// `block_shapes_by_state_id`. Returns `None` if the version has no shape for the state ID.
+    pub fn collision_shape(&self, state_id: u32) -> Option<VoxelShape> {
+        self.block_shapes_by_state_id
+            .get(&state_id)
+            .map(|boxes| VoxelShape::from_arrays(boxes))
+    }
+
+    /// The collision shape of a block's default state, built from `block_shapes_by_name`.
+    pub fn default_collision_shape(&self, name: &str) -> Option<VoxelShape> {
+        self.block_shapes_by_name
+            .get(name)
+            .map(|boxes| VoxelShape::from_arrays(boxes))
+    }
+
+    /// Whether a block state's collision shape fills its whole block. States without shape
+    /// data count as not full.
+    pub fn is_full_cube(&self, state_id: u32) -> bool {
+        self.collision_shape(state_id)
+            .is_some_and(|shape| shape.is_full_cube())
+    }
+
+    /// Whether a block state's collision shape covers a whole face of its block.
+    pub fn is_face_solid(&self, state_id: u32, face: Face) -> bool {
+        self.collision_shape(state_id)
+            .is_some_and(|shape| shape.is_face_solid(face))
+    }
+
+    /// The grass colour (0xRRGGBB) of a biome, from `tints.json` or else interpolated on the
+    /// grass colormap from the biome's temperature and rainfall. Returns `None` if the biome
+    /// is unknown and `tints.json` has no default.
+    pub fn grass_color(&self, biome: &str) -> Option<i32> {
+        self.biome_color(biome, |tints| &tints.grass, &tints::GRASS_COLORMAP)
+    }
+
+    /// The foliage colour (0xRRGGBB) of a biome, like `grass_color` but for leaves and vines.
+    pub fn foliage_color(&self, biome: &str) -> Option<i32> {
+        self.biome_color(biome, |tints| &tints.foliage, &tints::FOLIAGE_COLORMAP)
+    }
+
+    /// The water colour (0xRRGGBB) of a biome, from `tints.json` or else the default water
+    /// colour.
+    pub fn water_color(&self, biome: &str) -> i32 {
+        let biome = biome.strip_prefix("minecraft:").unwrap_or(biome);
+        let water = self.tints.as_ref().as_ref().map(|tints| &tints.water);
+        water
+            .and_then(|water| tints::listed_color(water, biome).or(water.default))
+            .unwrap_or(tints::DEFAULT_WATER_COLOR)
+    }
+
+    /// The colour (0xRRGGBB) of redstone wire at a power level, from `tints.json` or else
+    /// vanilla's formula. Returns `None` for power levels above 15.
+    pub fn redstone_color(&self, power: u8) -> Option<i32> {
+        if power > 15 {
+            return None;
+        }
+        self.tints
+            .as_ref()
+            .as_ref()
+            .and_then(|tints| tints::listed_color(&tints.redstone, &power.to_string()))
+            .or_else(|| Some(tints::redstone_formula(power)))
+    }
+
+    /// The colour (0xRRGGBB) a block state is tinted with in a biome: grass, foliage or water
+    /// colours for the blocks using them, the redstone colour for wire, and fixed colours
+    /// (e.g., birch leaves, stems). Returns `None` for blocks that are not tinted.
+    pub fn block_tint(&self, state_id: u32, biome: &str) -> Option<i32> {
+        let state = self.block_state(state_id)?;
+        let constant = self.tints.as_ref().as_ref().map(|tints| &tints.constant);
+        match tints::tint_source(&state, constant)? {
+            TintSource::Grass => self.grass_color(biome),
+            TintSource::Foliage => self.foliage_color(biome),
+            TintSource::Water => Some(self.water_color(biome)),
+            TintSource::Redstone(power) => self.redstone_color(power),
+            TintSource::Fixed(color) => Some(color),
+        }
+    }
+
+    // Looks up a grass or foliage colour, accepting namespaced biome names.
+    fn biome_color(
+        &self,
+        biome: &str,
+        list: impl Fn(&Tints) -> &TintData,
+        colormap: &[[f32; 3]; 3],
+    ) -> Option<i32> {
+        let biome = biome.strip_prefix("minecraft:").unwrap_or(biome);
+        tints::biome_color(
+            self.tints.as_ref().as_ref().map(list),
+            biome,
+            self.biomes_by_name.get(biome),
+            colormap,
+        )
+    }
+
+    // Whether this version has a block, item or entity with the given name.
+    pub(crate) fn has_name(&self, kind: AliasKind, name: &str) -> bool {
+        match kind {
+            AliasKind::Block => self.blocks_by_name.contains_key(name),
+            AliasKind::Item => self.items_by_name.contains_key(name),
+            AliasKind::Entity => self.entities_by_name.contains_key(name),
+        }
+    }
+
+    /// Looks up a block by its current name or a known former or later name.
+    ///
+    /// # Errors
+    /// Returns `McDataError` if the curated alias versions cannot be resolved.
+    pub fn block_by_alias(&self, name: &str) -> Result<Option<&Block>, McDataError> {
+        Ok(self
+            .canonical_name(AliasKind::Block, name)?
+            .and_then(|resolved| self.blocks_by_name.get(&resolved.name)))
+    }
+
+    /// Looks up an item by its current name or a known former or later name.
+    ///
+    /// # Errors
+    /// Returns `McDataError` if the curated alias versions cannot be resolved.
+    pub fn item_by_alias(&self, name: &str) -> Result<Option<&Item>, McDataError> {
+        Ok(self
+            .canonical_name(AliasKind::Item, name)?
+            .and_then(|resolved| self.items_by_name.get(&resolved.name)))
+    }
+
+    /// Looks up an entity by its current name or a known former or later name.
+    ///
+    /// # Errors
+    /// Returns `McDataError` if the curated alias versions cannot be resolved.
+    pub fn entity_by_alias(&self, name: &str) -> Result<Option<&Entity>, McDataError> {
+        Ok(self
+            .canonical_name(AliasKind::Entity, name)?
+            .and_then(|resolved| self.entities_by_name.get(&resolved.name)))
+    }
+}
+
+/// Precomputes feature values for a version, or returns `None` (falling back to live evaluation)
+/// if `features.json` cannot be evaluated.
+fn build_feature_table(version: &Version) -> Option<FeatureTable> {
+    match FeatureTable::build(version) {
+        Ok(table) => Some(table),
+        Err(e) => {
+            log::warn!(
+                "Could not precompute features for {}, falling back to live evaluation: {}",
+                version.minecraft_version,
+                e
+            );
+            None
+        }
     }
 }
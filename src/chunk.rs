@@ -0,0 +1,305 @@
+use crate::error::McDataError;
+use crate::version::{Edition, Version};
+
+/// Blocks along each side of a chunk section.
+pub const SECTION_SIZE: usize = 16;
+/// Blocks in a chunk section.
+pub const SECTION_VOLUME: usize = SECTION_SIZE * SECTION_SIZE * SECTION_SIZE;
+
+// Data versions of the snapshots that changed how paletted containers are sent: 20w17a
+// stopped entries spanning two longs, and 21w37a added single-valued containers.
+const NON_SPANNING_DATA_VERSION: i32 = 2529;
+const SINGLE_VALUED_DATA_VERSION: i32 = 2834;
+
+// Narrowest and widest entries of an indirect block palette; sections needing more bits
+// use the global palette.
+const MIN_INDIRECT_BITS: u32 = 4;
+const MAX_INDIRECT_BITS: u32 = 8;
+
+/// How the entries of a paletted container map to block state IDs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Palette {
+    /// Every block has the same state; sent with 0 bits per entry and no data (1.18+).
+    Single(u32),
+    /// Entries index into a list of state IDs.
+    Indirect(Vec<u32>),
+    /// Entries are state IDs of the global palette.
+    Direct,
+}
+
+/// The block states of a chunk section as sent over the network: a palette and entries
+/// packed into longs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackedSection {
+    pub bits_per_entry: u32,
+    pub palette: Palette,
+    pub data: Vec<u64>,
+}
+
+/// How a version sends paletted containers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct PaletteLayout {
+    /// Entries continue into the next long (before 20w17a).
+    pub spanning: bool,
+    /// Single-valued containers are sent with 0 bits per entry (from 21w37a).
+    pub single_valued: bool,
+}
+
+impl PaletteLayout {
+    /// The layout of a Java Edition version. Versions without a data version predate 1.9,
+    /// which introduced paletted sections, and have no layout.
+    pub(crate) fn of(version: &Version) -> Result<Self, McDataError> {
+        if version.edition != Edition::Pc {
+            return Err(McDataError::InvalidChunkSection {
+                reason: format!(
+                    "{:?} version {} does not use Java Edition chunk sections",
+                    version.edition, version.minecraft_version
+                ),
+            });
+        }
+        let Some(data_version) = version.data_version else {
+            return Err(McDataError::InvalidChunkSection {
+                reason: format!(
+                    "version {} predates paletted chunk sections (1.9)",
+                    version.minecraft_version
+                ),
+            });
+        };
+        Ok(PaletteLayout {
+            spanning: data_version < NON_SPANNING_DATA_VERSION,
+            single_valued: data_version >= SINGLE_VALUED_DATA_VERSION,
+        })
+    }
+}
+
+/// The block state IDs of a 16×16×16 chunk section.
+///
+/// Blocks are stored in network order: index `(y * 16 + z) * 16 + x`, using section-local
+/// coordinates.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChunkSection {
+    states: Vec<u32>,
+}
+
+impl ChunkSection {
+    /// A section where every block has the same state (e.g., all air).
+    pub fn filled(state_id: u32) -> Self {
+        ChunkSection {
+            states: vec![state_id; SECTION_VOLUME],
+        }
+    }
+
+    /// Creates a section from 4096 state IDs in network order.
+    ///
+    /// # Errors
+    /// Returns `McDataError::InvalidChunkSection` if `states` does not hold exactly 4096 IDs.
+    pub fn from_state_ids(states: Vec<u32>) -> Result<Self, McDataError> {
+        if states.len() != SECTION_VOLUME {
+            return Err(McDataError::InvalidChunkSection {
+                reason: format!(
+                    "expected {} block states, found {}",
+                    SECTION_VOLUME,
+                    states.len()
+                ),
+            });
+        }
+        Ok(ChunkSection { states })
+    }
+
+    /// All state IDs in network order.
+    pub fn state_ids(&self) -> &[u32] {
+        &self.states
+    }
+
+    /// The state ID at section-local coordinates, or `None` if they are outside the section.
+    pub fn get(&self, x: usize, y: usize, z: usize) -> Option<u32> {
+        index(x, y, z).map(|index| self.states[index])
+    }
+
+    /// Sets the state ID at section-local coordinates, returning the previous one, or `None`
+    /// (leaving the section unchanged) if they are outside the section.
+    pub fn set(&mut self, x: usize, y: usize, z: usize, state_id: u32) -> Option<u32> {
+        index(x, y, z).map(|index| std::mem::replace(&mut self.states[index], state_id))
+    }
+}
+
+fn index(x: usize, y: usize, z: usize) -> Option<usize> {
+    (x < SECTION_SIZE && y < SECTION_SIZE && z < SECTION_SIZE)
+        .then(|| (y * SECTION_SIZE + z) * SECTION_SIZE + x)
+}
+
+/// The bits needed to store `count` distinct values.
+pub(crate) fn ceil_log2(count: usize) -> u32 {
+    if count <= 1 {
+        0
+    } else {
+        usize::BITS - (count - 1).leading_zeros()
+    }
+}
+
+/// Unpacks a paletted container into block state IDs, rejecting IDs for which `is_known`
+/// returns false.
+///
+/// `spanning` selects the pre-1.16 layout, where entries continue into the next long; from
+/// 1.16 on, each long holds `64 / bits` entries and leftover bits are padding.
+pub(crate) fn decode(
+    packed: &PackedSection,
+    spanning: bool,
+    is_known: impl Fn(u32) -> bool,
+) -> Result<ChunkSection, McDataError> {
+    let states = match &packed.palette {
+        Palette::Single(state_id) => vec![*state_id; SECTION_VOLUME],
+        Palette::Indirect(palette) => {
+            let indices = unpack(
+                &packed.data,
+                packed.bits_per_entry,
+                SECTION_VOLUME,
+                spanning,
+            )?;
+            indices
+                .into_iter()
+                .map(|index| {
+                    palette.get(index as usize).copied().ok_or_else(|| {
+                        McDataError::InvalidChunkSection {
+                            reason: format!(
+                                "palette index {} out of range for {} entries",
+                                index,
+                                palette.len()
+                            ),
+                        }
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()?
+        }
+        Palette::Direct => unpack(
+            &packed.data,
+            packed.bits_per_entry,
+            SECTION_VOLUME,
+            spanning,
+        )?,
+    };
+    if let Some(unknown) = states.iter().find(|state_id| !is_known(**state_id)) {
+        return Err(McDataError::InvalidChunkSection {
+            reason: format!("unknown block state ID {}", unknown),
+        });
+    }
+    Ok(ChunkSection { states })
+}
+
+/// Packs a section the way vanilla does: a single value with 0 bits if `single_valued` is
+/// supported, an indirect palette of 4 to 8 bits, or the global palette with `global_bits`.
+pub(crate) fn encode(
+    section: &ChunkSection,
+    global_bits: u32,
+    spanning: bool,
+    single_valued: bool,
+) -> PackedSection {
+    // Palette entries in order of first appearance, as vanilla builds them.
+    let mut palette: Vec<u32> = Vec::new();
+    for state_id in &section.states {
+        if !palette.contains(state_id) {
+            palette.push(*state_id);
+            if palette.len() > 1 << MAX_INDIRECT_BITS {
+                break;
+            }
+        }
+    }
+
+    if single_valued && palette.len() == 1 {
+        return PackedSection {
+            bits_per_entry: 0,
+            palette: Palette::Single(palette[0]),
+            data: Vec::new(),
+        };
+    }
+    let bits = ceil_log2(palette.len()).max(MIN_INDIRECT_BITS);
+    if bits > MAX_INDIRECT_BITS {
+        return PackedSection {
+            bits_per_entry: global_bits,
+            palette: Palette::Direct,
+            data: pack(&section.states, global_bits, spanning),
+        };
+    }
+    let indices: Vec<u32> = section
+        .states
+        .iter()
+        .map(|state_id| palette.iter().position(|p| p == state_id).unwrap_or(0) as u32)
+        .collect();
+    PackedSection {
+        bits_per_entry: bits,
+        palette: Palette::Indirect(palette),
+        data: pack(&indices, bits, spanning),
+    }
+}
+
+/// The number of longs holding `count` entries of `bits` bits.
+pub(crate) fn packed_len(bits: u32, count: usize, spanning: bool) -> usize {
+    let bits = bits as usize;
+    if spanning {
+        (count * bits).div_ceil(64)
+    } else {
+        count.div_ceil(64 / bits)
+    }
+}
+
+/// Reads `count` entries of `bits` bits from packed longs.
+pub(crate) fn unpack(
+    data: &[u64],
+    bits: u32,
+    count: usize,
+    spanning: bool,
+) -> Result<Vec<u32>, McDataError> {
+    if bits == 0 || bits > 32 {
+        return Err(McDataError::InvalidChunkSection {
+            reason: format!("unsupported bits per entry: {}", bits),
+        });
+    }
+    let expected = packed_len(bits, count, spanning);
+    if data.len() != expected {
+        return Err(McDataError::InvalidChunkSection {
+            reason: format!(
+                "expected {} longs for {} bits per entry, found {}",
+                expected,
+                bits,
+                data.len()
+            ),
+        });
+    }
+    let bits = bits as usize;
+    let mask = (1u64 << bits) - 1;
+    let per_long = 64 / bits;
+    Ok((0..count)
+        .map(|i| {
+            let (long, offset) = if spanning {
+                (i * bits / 64, i * bits % 64)
+            } else {
+                (i / per_long, i % per_long * bits)
+            };
+            let mut value = data[long] >> offset;
+            if offset + bits > 64 {
+                value |= data[long + 1] << (64 - offset);
+            }
+            (value & mask) as u32
+        })
+        .collect())
+}
+
+/// Writes entries of `bits` bits into packed longs. Values must fit in `bits` bits.
+pub(crate) fn pack(values: &[u32], bits: u32, spanning: bool) -> Vec<u64> {
+    let mut data = vec![0u64; packed_len(bits, values.len(), spanning)];
+    let bits = bits as usize;
+    let per_long = 64 / bits;
+    for (i, value) in values.iter().enumerate() {
+        let value = *value as u64;
+        let (long, offset) = if spanning {
+            (i * bits / 64, i * bits % 64)
+        } else {
+            (i / per_long, i % per_long * bits)
+        };
+        data[long] |= value << offset;
+        if offset + bits > 64 {
+            data[long + 1] |= value >> (64 - offset);
+        }
+    }
+    data
+}
@@ -0,0 +1,151 @@
+use crate::error::{Edition, McDataError};
+use crate::version::{self, compare_version_strings, Version};
+use std::cmp::Ordering;
+
+/// Curated correspondence between Bedrock and Java (PC) releases, oldest first.
+///
+/// Each entry pairs a Bedrock release with the Java release that shipped the same feature set
+/// at roughly the same time. A Bedrock version maps through the newest entry at or before it;
+/// a Java version maps through the newest entry whose Java release is at or before it.
+pub(crate) const BEDROCK_TO_JAVA: &[(&str, &str)] = &[
+    ("1.2.0", "1.12.2"), // Better Together
+    ("1.4.0", "1.13"),   // Update Aquatic (phase 1)
+    ("1.6.0", "1.13.2"), // Update Aquatic (phase 2)
+    ("1.11.0", "1.14"),  // Village & Pillage
+    ("1.12.0", "1.14.2"),
+    ("1.13.0", "1.14.4"),
+    ("1.14.0", "1.15"), // Buzzy Bees
+    ("1.14.60", "1.15.2"),
+    ("1.16.0", "1.16.1"), // Nether Update
+    ("1.16.20", "1.16.2"),
+    ("1.16.40", "1.16.3"),
+    ("1.16.100", "1.16.4"),
+    ("1.16.210", "1.16.5"),
+    ("1.17.0", "1.17"), // Caves & Cliffs: Part I
+    ("1.17.10", "1.17.1"),
+    ("1.18.0", "1.18"), // Caves & Cliffs: Part II
+    ("1.18.10", "1.18.1"),
+    ("1.18.30", "1.18.2"),
+    ("1.19.0", "1.19"), // The Wild Update
+    ("1.19.10", "1.19.1"),
+    ("1.19.20", "1.19.2"),
+    ("1.19.50", "1.19.3"),
+    ("1.19.70", "1.19.4"),
+    ("1.20.0", "1.20"), // Trails & Tales
+    ("1.20.10", "1.20.1"),
+    ("1.20.30", "1.20.2"),
+    ("1.20.50", "1.20.4"),
+    ("1.20.80", "1.20.6"),
+    ("1.21.0", "1.21"), // Tricky Trials
+    ("1.21.20", "1.21.1"),
+    ("1.21.40", "1.21.3"),
+    ("1.21.50", "1.21.4"),
+    ("1.21.70", "1.21.5"),
+];
+
+/// Returns the Java (PC) version closest to the given Bedrock version.
+///
+/// Accepts Bedrock version strings with or without the "bedrock_" prefix (e.g., "1.20.10").
+/// The match is taken from a curated table of corresponding updates and resolved to the newest
+/// known Java release at or before the corresponding version.
+///
+/// # Errors
+/// Returns `McDataError::NoEditionCounterpart` if the Bedrock version predates the table,
+/// or a resolution error if the input is not a known Bedrock version.
+pub fn closest_java_version(bedrock_version: &str) -> Result<Version, McDataError> {
+    let bedrock = resolve_in_edition(Edition::Bedrock, bedrock_version)?;
+    let java = BEDROCK_TO_JAVA
+        .iter()
+        .rev()
+        .find(|(b, _)| compare_version_strings(b, &bedrock.minecraft_version) != Ordering::Greater)
+        .map(|(_, j)| *j)
+        .ok_or_else(|| McDataError::NoEditionCounterpart {
+            edition: Edition::Bedrock,
+            version: bedrock.minecraft_version.clone(),
+            target: Edition::Pc,
+        })?;
+    log::debug!(
+        "Bedrock {} corresponds to Java {}",
+        bedrock.minecraft_version,
+        java
+    );
+    newest_release_at_or_before(Edition::Pc, java, &bedrock)
+}
+
+/// Returns the Bedrock version closest to the given Java (PC) version.
+///
+/// Accepts Java version strings with or without the "pc_" prefix (e.g., "1.20.1").
+/// The match is taken from the same curated table as `closest_java_version`, in reverse.
+///
+/// # Errors
+/// Returns `McDataError::NoEditionCounterpart` if the Java version predates the table,
+/// or a resolution error if the input is not a known Java version.
+pub fn closest_bedrock_version(java_version: &str) -> Result<Version, McDataError> {
+    let java = resolve_in_edition(Edition::Pc, java_version)?;
+    // Compare as versions, not strings, so snapshots and pre-releases (e.g., "23w31a" or
+    // "1.20.2-pre1") fall between the releases around them.
+    let bedrock = BEDROCK_TO_JAVA
+        .iter()
+        .rev()
+        .find(|(_, j)| matches!(exact_version(Edition::Pc, j), Ok(Some(entry)) if entry <= java))
+        .map(|(b, _)| *b)
+        .ok_or_else(|| McDataError::NoEditionCounterpart {
+            edition: Edition::Pc,
+            version: java.minecraft_version.clone(),
+            target: Edition::Bedrock,
+        })?;
+    log::debug!(
+        "Java {} corresponds to Bedrock {}",
+        java.minecraft_version,
+        bedrock
+    );
+    newest_release_at_or_before(Edition::Bedrock, bedrock, &java)
+}
+
+/// Resolves a version string that is known to belong to `edition`, adding the edition prefix if absent.
+fn resolve_in_edition(edition: Edition, version_str: &str) -> Result<Version, McDataError> {
+    let (_, unprefixed) = version::split_edition_prefix(version_str);
+    version::resolve_version(&format!("{}_{}", edition.path_prefix(), unprefixed))
+}
+
+/// Returns the version of `edition` named exactly `target` if known, otherwise the newest
+/// release whose version string is at or before `target`.
+fn newest_release_at_or_before(
+    edition: Edition,
+    target: &str,
+    source: &Version,
+) -> Result<Version, McDataError> {
+    if let Some(exact) = exact_version(edition, target)? {
+        return Ok(exact);
+    }
+    let version_data = version::get_version_data(edition)?;
+    version_data
+        .by_major_version
+        .values()
+        .flatten()
+        .filter(|v| v.release_type == "release")
+        .filter(|v| compare_version_strings(&v.minecraft_version, target) != Ordering::Greater)
+        .max_by(|a, b| {
+            compare_version_strings(&a.minecraft_version, &b.minecraft_version)
+                .then_with(|| a.cmp(b))
+        })
+        .cloned()
+        .ok_or_else(|| McDataError::NoEditionCounterpart {
+            edition: source.edition,
+            version: source.minecraft_version.clone(),
+            target: edition,
+        })
+}
+
+/// Returns the version of `edition` named exactly `name`, if known.
+fn exact_version(edition: Edition, name: &str) -> Result<Option<Version>, McDataError> {
+    let version_data = version::get_version_data(edition)?;
+    // `by_major_version` holds every version; `by_minecraft_version` maps major keys
+    // like "1.20" to the latest release of that major, which would hide the "1.20" release itself.
+    Ok(version_data
+        .by_major_version
+        .values()
+        .flatten()
+        .find(|v| v.minecraft_version == name)
+        .cloned())
+}
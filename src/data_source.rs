@@ -54,7 +54,7 @@ pub fn get_data_root() -> Result<&'static Path, McDataError> {
 
         // Determine the target cache directory structure.
         let base_cache_dir = dirs_next::cache_dir()
-            .ok_or_else(|| McDataError::CacheDirNotFound)?
+            .ok_or(McDataError::CacheDirNotFound)?
             .join(CACHE_SUBDIR); // e.g., ~/.cache/mcdata-rs
         let target_repo_dir = base_cache_dir.join(DATA_DIR_NAME); // e.g., ~/.cache/mcdata-rs/minecraft-data
         let target_data_dir = target_repo_dir.join("data"); // The final target: ~/.cache/mcdata-rs/minecraft-data/data
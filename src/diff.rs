@@ -0,0 +1,501 @@
+use crate::cached_data::IndexedData;
+use crate::error::McDataError;
+use crate::structs::{Biome, Block, Effect, Enchantment, Entity, Item, Particle, Sound};
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt::Write;
+
+/// Differences between the data of two versions, as returned by `diff`.
+///
+/// Serializes to JSON with `to_json` and renders to Markdown with `to_markdown`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct DiffReport {
+    /// The version compared from (e.g., "pc_1.18.2").
+    pub from: String,
+    /// The version compared to.
+    pub to: String,
+    pub blocks: CategoryDiff,
+    pub items: CategoryDiff,
+    pub entities: CategoryDiff,
+    pub biomes: CategoryDiff,
+    pub effects: CategoryDiff,
+    pub enchantments: CategoryDiff,
+    pub sounds: CategoryDiff,
+    pub particles: CategoryDiff,
+    /// Property changes of blocks present in both versions (matched by name or rename).
+    pub block_changes: Vec<BlockChange>,
+    pub block_loot: LootDiff,
+    pub entity_loot: LootDiff,
+    /// Features whose value differs between the two versions.
+    pub feature_changes: Vec<FeatureChange>,
+}
+
+/// Added, removed, renamed and re-numbered entries of one data category.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct CategoryDiff {
+    pub added: Vec<DiffEntry>,
+    pub removed: Vec<DiffEntry>,
+    pub renamed: Vec<Rename>,
+    pub id_changes: Vec<IdChange>,
+}
+
+/// An entry identified by name and numeric ID.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DiffEntry {
+    pub name: String,
+    pub id: u32,
+}
+
+/// An entry whose name changed between versions.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Rename {
+    pub from: String,
+    pub to: String,
+    pub from_id: u32,
+    pub to_id: u32,
+}
+
+/// An entry whose numeric ID changed between versions.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct IdChange {
+    pub name: String,
+    pub from: u32,
+    pub to: u32,
+}
+
+/// Changed properties of a block present in both versions.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BlockChange {
+    /// The block's name in the newer version.
+    pub name: String,
+    pub changes: Vec<PropertyChange>,
+}
+
+/// One changed property, with its old and new values as JSON.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PropertyChange {
+    pub property: String,
+    pub from: Value,
+    pub to: Value,
+}
+
+/// Loot tables added, removed or changed, by block or entity name.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct LootDiff {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub changed: Vec<String>,
+}
+
+/// A feature whose value differs between the two versions.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FeatureChange {
+    pub name: String,
+    pub from: Value,
+    pub to: Value,
+}
+
+impl CategoryDiff {
+    /// Whether no differences were found.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.renamed.is_empty()
+            && self.id_changes.is_empty()
+    }
+}
+
+impl LootDiff {
+    /// Whether no differences were found.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+// Common view of the named, numbered data types compared by `diff`.
+pub(crate) trait DiffItem {
+    fn id(&self) -> u32;
+    fn name(&self) -> &str;
+    // Display name used to pair renamed entries; `None` for types without one.
+    fn display_name(&self) -> Option<&str>;
+}
+
+macro_rules! impl_diff_item {
+    ($($ty:ty => $display:expr;)*) => {
+        $(
+            impl DiffItem for $ty {
+                fn id(&self) -> u32 {
+                    self.id
+                }
+                fn name(&self) -> &str {
+                    &self.name
+                }
+                fn display_name(&self) -> Option<&str> {
+                    let display: fn(&$ty) -> Option<&str> = $display;
+                    display(self)
+                }
+            }
+        )*
+    };
+}
+
+impl_diff_item! {
+    Block => |b| Some(&b.display_name);
+    Item => |i| Some(&i.display_name);
+    Entity => |e| Some(&e.display_name);
+    Biome => |b| Some(&b.display_name);
+    Effect => |e| Some(&e.display_name);
+    Enchantment => |e| Some(&e.display_name);
+    Sound => |_| None;
+    Particle => |_| None;
+}
+
+/// Compares the data of two versions.
+///
+/// Entries are matched by name. Of the remaining entries, a removed and an added entry are
+/// reported as a rename when they share a display name, or when they share an ID and the
+/// entries just below that ID also match (so IDs have not shifted around them).
+/// Blocks matched by name or rename are compared property by property.
+///
+/// # Errors
+/// Returns `McDataError` if the feature data of either version cannot be evaluated.
+pub fn diff(from: &IndexedData, to: &IndexedData) -> Result<DiffReport, McDataError> {
+    log::debug!(
+        "Diffing {} {} against {} {}",
+        from.version.edition,
+        from.version.minecraft_version,
+        to.version.edition,
+        to.version.minecraft_version
+    );
+    let blocks = diff_category(&from.blocks_array, &to.blocks_array);
+    let block_changes = diff_blocks(from, to, &blocks.renamed);
+
+    Ok(DiffReport {
+        from: version_label(from),
+        to: version_label(to),
+        items: diff_category(&from.items_array, &to.items_array),
+        entities: diff_category(&from.entities_array, &to.entities_array),
+        biomes: diff_category(&from.biomes_array, &to.biomes_array),
+        effects: diff_category(&from.effects_array, &to.effects_array),
+        enchantments: diff_category(&from.enchantments_array, &to.enchantments_array),
+        sounds: diff_category(&from.sounds_array, &to.sounds_array),
+        particles: diff_category(&from.particles_array, &to.particles_array),
+        blocks,
+        block_changes,
+        block_loot: diff_loot(&from.block_loot_by_name, &to.block_loot_by_name),
+        entity_loot: diff_loot(&from.entity_loot_by_name, &to.entity_loot_by_name),
+        feature_changes: diff_features(from, to)?,
+    })
+}
+
+fn version_label(data: &IndexedData) -> String {
+    format!(
+        "{}_{}",
+        data.version.edition.path_prefix(),
+        data.version.minecraft_version
+    )
+}
+
+pub(crate) fn diff_category<T: DiffItem>(from: &[T], to: &[T]) -> CategoryDiff {
+    let from_by_name: HashMap<&str, &T> = from.iter().map(|e| (e.name(), e)).collect();
+    let to_by_name: HashMap<&str, &T> = to.iter().map(|e| (e.name(), e)).collect();
+    let from_by_id: HashMap<u32, &T> = from.iter().map(|e| (e.id(), e)).collect();
+    let to_by_id: HashMap<u32, &T> = to.iter().map(|e| (e.id(), e)).collect();
+
+    let mut report = CategoryDiff::default();
+    let mut removed: Vec<&T> = Vec::new();
+    for entry in from {
+        match to_by_name.get(entry.name()) {
+            Some(other) if other.id() != entry.id() => report.id_changes.push(IdChange {
+                name: entry.name().to_string(),
+                from: entry.id(),
+                to: other.id(),
+            }),
+            Some(_) => {}
+            None => removed.push(entry),
+        }
+    }
+    let mut added: Vec<&T> = to
+        .iter()
+        .filter(|e| !from_by_name.contains_key(e.name()))
+        .collect();
+
+    // Pair removed and added entries that look like renames.
+    let stable_id = |id: u32| {
+        id.checked_sub(1)
+            .and_then(|below| Some((from_by_id.get(&below)?, to_by_id.get(&below)?)))
+            .is_some_and(|(a, b)| a.name() == b.name())
+    };
+    removed.retain(|old| {
+        let position = added.iter().position(|new| {
+            let same_display = matches!(
+                (old.display_name(), new.display_name()),
+                (Some(a), Some(b)) if !a.is_empty() && a.eq_ignore_ascii_case(b)
+            );
+            same_display || (old.id() == new.id() && stable_id(old.id()))
+        });
+        match position {
+            Some(index) => {
+                let new = added.remove(index);
+                report.renamed.push(Rename {
+                    from: old.name().to_string(),
+                    to: new.name().to_string(),
+                    from_id: old.id(),
+                    to_id: new.id(),
+                });
+                false
+            }
+            None => true,
+        }
+    });
+
+    let entry = |e: &&T| DiffEntry {
+        name: e.name().to_string(),
+        id: e.id(),
+    };
+    report.removed = removed.iter().map(entry).collect();
+    report.added = added.iter().map(entry).collect();
+    report
+}
+
+fn diff_blocks(from: &IndexedData, to: &IndexedData, renamed: &[Rename]) -> Vec<BlockChange> {
+    let renamed_to: HashMap<&str, &str> = renamed
+        .iter()
+        .map(|r| (r.from.as_str(), r.to.as_str()))
+        .collect();
+    let mut changes = Vec::new();
+    for old in from.blocks_array.iter() {
+        let new_name = renamed_to
+            .get(old.name.as_str())
+            .copied()
+            .unwrap_or(&old.name);
+        if let Some(new) = to.blocks_by_name.get(new_name) {
+            let properties = block_property_changes(old, new);
+            if !properties.is_empty() {
+                changes.push(BlockChange {
+                    name: new.name.clone(),
+                    changes: properties,
+                });
+            }
+        }
+    }
+    changes
+}
+
+fn block_property_changes(old: &Block, new: &Block) -> Vec<PropertyChange> {
+    let mut changes = Vec::new();
+    let mut compare = |property: &str, from: Value, to: Value| {
+        if from != to {
+            changes.push(PropertyChange {
+                property: property.to_string(),
+                from,
+                to,
+            });
+        }
+    };
+    compare("hardness", json!(old.hardness), json!(new.hardness));
+    compare("resistance", json!(old.resistance), json!(new.resistance));
+    compare("diggable", json!(old.diggable), json!(new.diggable));
+    compare(
+        "transparent",
+        json!(old.transparent),
+        json!(new.transparent),
+    );
+    compare("emitLight", json!(old.emit_light), json!(new.emit_light));
+    compare(
+        "filterLight",
+        json!(old.filter_light),
+        json!(new.filter_light),
+    );
+    compare(
+        "boundingBox",
+        json!(old.bounding_box),
+        json!(new.bounding_box),
+    );
+    compare("stackSize", json!(old.stack_size), json!(new.stack_size));
+    compare("material", json!(old.material), json!(new.material));
+    compare(
+        "stateRange",
+        json!([old.min_state_id, old.max_state_id]),
+        json!([new.min_state_id, new.max_state_id]),
+    );
+    compare(
+        "defaultState",
+        json!(old.default_state),
+        json!(new.default_state),
+    );
+    if old.states != new.states {
+        compare("states", states_json(old), states_json(new));
+    }
+    changes
+}
+
+// Block states as `{name: values}`, which reads better in reports than the raw definitions.
+fn states_json(block: &Block) -> Value {
+    let states: BTreeMap<&str, Value> = block
+        .states
+        .iter()
+        .map(|s| {
+            let values = if s.values.is_empty() {
+                json!(s.state_type)
+            } else {
+                json!(s.values)
+            };
+            (s.name.as_str(), values)
+        })
+        .collect();
+    json!(states)
+}
+
+pub(crate) fn diff_loot<T: PartialEq>(
+    from: &HashMap<String, T>,
+    to: &HashMap<String, T>,
+) -> LootDiff {
+    let names: BTreeSet<&String> = from.keys().chain(to.keys()).collect();
+    let mut report = LootDiff::default();
+    for name in names {
+        match (from.get(name), to.get(name)) {
+            (None, Some(_)) => report.added.push(name.clone()),
+            (Some(_), None) => report.removed.push(name.clone()),
+            (Some(a), Some(b)) if a != b => report.changed.push(name.clone()),
+            _ => {}
+        }
+    }
+    report
+}
+
+fn diff_features(from: &IndexedData, to: &IndexedData) -> Result<Vec<FeatureChange>, McDataError> {
+    let from_values: BTreeMap<String, Value> = from
+        .features()?
+        .into_iter()
+        .map(|f| (f.name, f.value))
+        .collect();
+    let mut to_values: BTreeMap<String, Value> = to
+        .features()?
+        .into_iter()
+        .map(|f| (f.name, f.value))
+        .collect();
+
+    // Features missing on one side (e.g., across editions) read as unsupported there.
+    let mut changes = Vec::new();
+    for (name, from_value) in from_values {
+        let to_value = to_values.remove(&name).unwrap_or(Value::Bool(false));
+        if from_value != to_value {
+            changes.push(FeatureChange {
+                name,
+                from: from_value,
+                to: to_value,
+            });
+        }
+    }
+    for (name, to_value) in to_values {
+        if to_value != Value::Bool(false) {
+            changes.push(FeatureChange {
+                name,
+                from: Value::Bool(false),
+                to: to_value,
+            });
+        }
+    }
+    changes.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(changes)
+}
+
+impl DiffReport {
+    /// Serializes the report to pretty-printed JSON.
+    pub fn to_json(&self) -> String {
+        // The report only holds strings, numbers and JSON values, so serialization cannot fail.
+        serde_json::to_string_pretty(self).expect("DiffReport serialization is infallible")
+    }
+
+    /// Renders the report as Markdown, with one section per non-empty category.
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "# Data changes from {} to {}", self.from, self.to);
+
+        for (title, category) in [
+            ("Blocks", &self.blocks),
+            ("Items", &self.items),
+            ("Entities", &self.entities),
+            ("Biomes", &self.biomes),
+            ("Effects", &self.effects),
+            ("Enchantments", &self.enchantments),
+            ("Sounds", &self.sounds),
+            ("Particles", &self.particles),
+        ] {
+            if category.is_empty() {
+                continue;
+            }
+            let _ = writeln!(out, "\n## {}\n", title);
+            for entry in &category.added {
+                let _ = writeln!(out, "- Added `{}` (ID {})", entry.name, entry.id);
+            }
+            for entry in &category.removed {
+                let _ = writeln!(out, "- Removed `{}` (ID {})", entry.name, entry.id);
+            }
+            for rename in &category.renamed {
+                let _ = writeln!(
+                    out,
+                    "- Renamed `{}` → `{}` (ID {} → {})",
+                    rename.from, rename.to, rename.from_id, rename.to_id
+                );
+            }
+            for change in &category.id_changes {
+                let _ = writeln!(
+                    out,
+                    "- `{}` ID {} → {}",
+                    change.name, change.from, change.to
+                );
+            }
+        }
+
+        if !self.block_changes.is_empty() {
+            let _ = writeln!(out, "\n## Block properties\n");
+            let _ = writeln!(out, "| Block | Property | From | To |");
+            let _ = writeln!(out, "| --- | --- | --- | --- |");
+            for block in &self.block_changes {
+                for change in &block.changes {
+                    let _ = writeln!(
+                        out,
+                        "| `{}` | {} | `{}` | `{}` |",
+                        block.name, change.property, change.from, change.to
+                    );
+                }
+            }
+        }
+
+        for (title, loot) in [
+            ("Block loot", &self.block_loot),
+            ("Entity loot", &self.entity_loot),
+        ] {
+            if loot.is_empty() {
+                continue;
+            }
+            let _ = writeln!(out, "\n## {}\n", title);
+            for (verb, names) in [
+                ("Added", &loot.added),
+                ("Removed", &loot.removed),
+                ("Changed", &loot.changed),
+            ] {
+                for name in names {
+                    let _ = writeln!(out, "- {} `{}`", verb, name);
+                }
+            }
+        }
+
+        if !self.feature_changes.is_empty() {
+            let _ = writeln!(out, "\n## Features\n");
+            let _ = writeln!(out, "| Feature | From | To |");
+            let _ = writeln!(out, "| --- | --- | --- |");
+            for change in &self.feature_changes {
+                let _ = writeln!(
+                    out,
+                    "| `{}` | `{}` | `{}` |",
+                    change.name, change.from, change.to
+                );
+            }
+        }
+        out
+    }
+}
@@ -0,0 +1,79 @@
+use crate::structs::{Block, Materials};
+use crate::tools;
+use std::time::Duration;
+
+// Length of one game tick.
+const TICK: Duration = Duration::from_millis(50);
+
+/// The circumstances of digging a block, as used by `IndexedData::dig_time`.
+///
+/// The default describes a survival player digging by hand, on the ground, out of water and
+/// without enchantments or effects.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DigOptions {
+    /// The ID of the held item, or `None` for an empty hand.
+    pub held_item: Option<u32>,
+    /// Creative mode breaks every block instantly.
+    pub creative: bool,
+    /// The player's head is in water (5× slower unless `aqua_affinity` is set).
+    pub in_water: bool,
+    /// Aqua Affinity on the helmet removes the underwater penalty.
+    pub aqua_affinity: bool,
+    /// The player is not standing on the ground (5× slower).
+    pub not_on_ground: bool,
+    /// Efficiency level of the held item (0 if not enchanted).
+    pub efficiency: u32,
+    /// Haste effect level (amplifier + 1; 0 if absent).
+    pub haste: u32,
+    /// Mining Fatigue effect level (amplifier + 1; 0 if absent).
+    pub mining_fatigue: u32,
+}
+
+/// Computes the time to break `block`, following vanilla's block breaking formula.
+///
+/// Returns `None` for unbreakable blocks and `Duration::ZERO` for instant breaks.
+pub(crate) fn dig_time(
+    block: &Block,
+    materials: Option<&Materials>,
+    options: &DigOptions,
+) -> Option<Duration> {
+    if options.creative {
+        return Some(Duration::ZERO);
+    }
+    let hardness = block.hardness.filter(|h| *h >= 0.0 && block.diggable)?;
+    if hardness == 0.0 {
+        return Some(Duration::ZERO);
+    }
+
+    let tool = tools::tool_effect(block, materials, options.held_item);
+    let mut speed = tool.speed_multiplier as f64;
+    // Efficiency only helps tools that are effective on the block.
+    if speed > 1.0 && options.efficiency > 0 {
+        speed += (options.efficiency * options.efficiency + 1) as f64;
+    }
+    if options.haste > 0 {
+        speed *= 1.0 + 0.2 * options.haste as f64;
+    }
+    if options.mining_fatigue > 0 {
+        speed *= match options.mining_fatigue {
+            1 => 0.3,
+            2 => 0.09,
+            3 => 0.0027,
+            _ => 8.1e-4,
+        };
+    }
+    if options.in_water && !options.aqua_affinity {
+        speed /= 5.0;
+    }
+    if options.not_on_ground {
+        speed /= 5.0;
+    }
+
+    let mut damage = speed / hardness as f64;
+    damage /= if tool.harvests { 30.0 } else { 100.0 };
+    if damage > 1.0 {
+        return Some(Duration::ZERO);
+    }
+    let ticks = (1.0 / damage).ceil() as u32;
+    Some(TICK * ticks)
+}
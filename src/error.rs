@@ -9,6 +9,13 @@ pub enum McDataError {
     #[error("Version string '{0}' is invalid or unsupported")]
     InvalidVersion(String),
 
+    #[error("Version '{version}' is not a known {edition} version{}", suggestion_hint(.suggestions))]
+    UnknownVersion {
+        version: String,
+        edition: Edition,         // The edition that was assumed or given by prefix
+        suggestions: Vec<String>, // Nearest known versions, best match first
+    },
+
     #[error("Version '{mc_version}' (major: {major_version}) not found for edition {edition:?}")]
     VersionNotFound {
         mc_version: String,
@@ -16,6 +23,68 @@ pub enum McDataError {
         edition: Edition,
     },
 
+    #[error("Cannot compare versions from different editions: {left:?} ({left_version}) and {right:?} ({right_version})")]
+    EditionMismatch {
+        left: Edition,
+        left_version: String,
+        right: Edition,
+        right_version: String,
+    },
+
+    #[error("No {target:?} counterpart is known for {edition:?} version '{version}'")]
+    NoEditionCounterpart {
+        edition: Edition,
+        version: String,
+        target: Edition,
+    },
+
+    // Errors related to feature checks.
+    #[error("Feature '{name}' is not defined in features.json for edition {edition:?}")]
+    UnknownFeature { name: String, edition: Edition },
+
+    #[error("Feature '{name}' has value {value}, which is not a valid {expected}")]
+    InvalidFeatureValue {
+        name: String,
+        expected: &'static str, // The typed value expected by the feature flag
+        value: serde_json::Value,
+    },
+
+    // Errors related to cross-version remapping.
+    #[error("No {kind} named '{name}' in {edition:?} version {version}")]
+    UnknownEntry {
+        kind: &'static str, // The data category searched (e.g., "block")
+        name: String,
+        edition: Edition,
+        version: String,
+    },
+
+    // Errors related to block states.
+    #[error("Unknown property `{property}` for `{block}`")]
+    UnknownBlockProperty { block: String, property: String },
+
+    #[error("Invalid value `{value}` for property `{property}` of `{block}` (expected one of: {})", .expected.join(", "))]
+    InvalidBlockPropertyValue {
+        block: String,
+        property: String,
+        value: String,
+        expected: Vec<String>, // The values the property accepts, in state ID order
+    },
+
+    #[error("Invalid block state `{input}`: {reason}")]
+    InvalidBlockStateString { input: String, reason: String },
+
+    // Errors related to pre-flattening data.
+    #[error("Legacy ID conversion is unavailable for {edition:?} version {version}: {reason}")]
+    LegacyConversionUnavailable {
+        edition: Edition,
+        version: String,
+        reason: String,
+    },
+
+    // Errors related to chunk data.
+    #[error("Invalid chunk section: {reason}")]
+    InvalidChunkSection { reason: String },
+
     // Errors related to finding and loading data files.
     #[error(
         "Data key '{data_key}' not found in dataPaths.json for version {mc_version} ({edition:?})"
@@ -63,3 +132,12 @@ pub enum McDataError {
     #[error("Cached operation failed previously: {0}")]
     CachedError(String), // Indicates a cached OnceCell holds a previous error result.
 }
+
+// Formats the "did you mean" suffix of `McDataError::UnknownVersion`.
+fn suggestion_hint(suggestions: &[String]) -> String {
+    if suggestions.is_empty() {
+        String::new()
+    } else {
+        format!("; did you mean {}?", suggestions.join(", "))
+    }
+}
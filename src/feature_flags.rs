@@ -0,0 +1,163 @@
+#[cfg(test)]
+use crate::structs::Feature;
+use serde_json::Value;
+
+// Strongly typed feature flags mirroring the entries of the PC `features.json`.
+// `features.json` is only fetched at runtime, so the flags are generated by `build.rs` from a
+// checked-in snapshot, `feature_flags.tsv`. A unit test compares the snapshot with upstream data
+// and rewrites it when `MCDATA_UPDATE_FEATURE_FLAGS` is set.
+
+/// A feature defined in `features.json`, with the Rust type of its value.
+///
+/// Each flag is a zero-sized type, so misspelled names fail to compile instead of
+/// silently reading as unsupported:
+///
+/// ```no_run
+/// use mcdata_rs::{mc_data, TheFlattening};
+///
+/// let data = mc_data("1.18.2")?;
+/// let flattened: bool = data.feature::<TheFlattening>()?;
+/// # Ok::<(), mcdata_rs::McDataError>(())
+/// ```
+///
+/// Flags whose values are not booleans return `Option<T>`, which is `None` for versions
+/// outside every range of the feature (where `features.json` implies `false`).
+/// Features whose values mix types are typed as `serde_json::Value`.
+///
+/// There is one flag per PC feature, named after it in UpperCamelCase (`theFlattening` is
+/// `TheFlattening`); `ALL_FEATURE_NAMES` lists them. Flags are generated from a snapshot of
+/// `features.json`, so features added upstream since are only reachable by name through
+/// `IndexedData::support_feature` or `IndexedData::feature_value`.
+pub trait FeatureFlag {
+    /// The feature's name in `features.json`.
+    const NAME: &'static str;
+    /// The type the feature's value converts to.
+    type Value: FromFeatureValue;
+}
+
+/// Conversion from a raw `features.json` value to a typed feature value.
+pub trait FromFeatureValue: Sized {
+    /// Human-readable name of the expected type, used in `McDataError::InvalidFeatureValue`.
+    const EXPECTED: &'static str;
+
+    /// Converts the raw value, returning `None` if it has the wrong type.
+    fn from_feature_value(value: &Value) -> Option<Self>;
+}
+
+impl FromFeatureValue for bool {
+    const EXPECTED: &'static str = "boolean";
+
+    fn from_feature_value(value: &Value) -> Option<Self> {
+        value.as_bool()
+    }
+}
+
+impl FromFeatureValue for i64 {
+    const EXPECTED: &'static str = "integer";
+
+    fn from_feature_value(value: &Value) -> Option<Self> {
+        value.as_i64()
+    }
+}
+
+impl FromFeatureValue for String {
+    const EXPECTED: &'static str = "string";
+
+    fn from_feature_value(value: &Value) -> Option<Self> {
+        value.as_str().map(str::to_string)
+    }
+}
+
+impl FromFeatureValue for Value {
+    const EXPECTED: &'static str = "JSON value";
+
+    fn from_feature_value(value: &Value) -> Option<Self> {
+        Some(value.clone())
+    }
+}
+
+// Unsupported features evaluate to `false`, which maps to `None` for non-boolean values.
+impl<T: FromFeatureValue> FromFeatureValue for Option<T> {
+    const EXPECTED: &'static str = T::EXPECTED;
+
+    fn from_feature_value(value: &Value) -> Option<Self> {
+        match value {
+            Value::Bool(false) => Some(None),
+            other => T::from_feature_value(other).map(Some),
+        }
+    }
+}
+
+// Declares a zero-sized type per feature and implements `FeatureFlag` for it.
+macro_rules! feature_flags {
+    ($($(#[$meta:meta])* $flag:ident => $name:literal: $value:ty;)*) => {
+        $(
+            $(#[$meta])*
+            #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+            pub struct $flag;
+
+            impl FeatureFlag for $flag {
+                const NAME: &'static str = $name;
+                type Value = $value;
+            }
+        )*
+
+        /// Names of all typed feature flags, in declaration order.
+        pub const ALL_FEATURE_NAMES: &[&str] = &[$($name),*];
+    };
+}
+
+include!(concat!(env!("OUT_DIR"), "/feature_flags.rs"));
+
+/// Renders the snapshot `build.rs` generates the flags from: one line per feature name, in
+/// first-definition order, with the type of its values and its description.
+#[cfg(test)]
+pub(crate) fn render_snapshot(features: &[Feature]) -> String {
+    let mut snapshot = String::from(SNAPSHOT_HEADER);
+    let mut seen = std::collections::HashSet::new();
+    for feature in features {
+        if !seen.insert(feature.name.as_str()) {
+            continue;
+        }
+        let definitions = features.iter().filter(|other| other.name == feature.name);
+        let values: Vec<&Value> = definitions
+            .flat_map(|definition| definition.values.iter().map(|value| &value.value))
+            .collect();
+        let description = feature.description.as_deref().unwrap_or_default();
+        snapshot.push_str(&format!(
+            "{}\t{}\t{}\n",
+            feature.name,
+            value_kind(&values),
+            description.split_whitespace().collect::<Vec<_>>().join(" ")
+        ));
+    }
+    snapshot
+}
+
+#[cfg(test)]
+const SNAPSHOT_HEADER: &str = "\
+# Feature flags generated from pc/common/features.json; see src/feature_flags.rs.
+# Regenerate with: MCDATA_UPDATE_FEATURE_FLAGS=1 cargo test feature_flags_match_upstream
+# name\tvalue type\tdescription
+";
+
+// The snapshot type of a feature's values. `false` only marks versions without support, so it
+// does not decide the type; features without `values` are implicitly boolean.
+#[cfg(test)]
+fn value_kind(values: &[&Value]) -> &'static str {
+    let kinds: std::collections::BTreeSet<&str> = values
+        .iter()
+        .filter(|value| !matches!(value, Value::Bool(false)))
+        .map(|value| match value {
+            Value::Bool(_) => "bool",
+            Value::Number(number) if number.is_i64() => "integer",
+            Value::String(_) => "string",
+            _ => "json",
+        })
+        .collect();
+    match kinds.len() {
+        0 => "bool",
+        1 => kinds.into_iter().next().unwrap_or("json"),
+        _ => "json",
+    }
+}
@@ -0,0 +1,169 @@
+use crate::error::{Edition, McDataError};
+use crate::features::{self, FeatureRange, RangeBound};
+use crate::structs::Feature;
+use crate::version::{self, Version};
+use std::collections::HashSet;
+use std::fmt;
+
+/// A problem found in a `features.json` definition by `lint_features`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeatureDiagnostic {
+    /// Name of the feature the problem was found in.
+    pub feature: String,
+    pub kind: FeatureDiagnosticKind,
+}
+
+/// The kinds of problems `lint_features` reports.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FeatureDiagnosticKind {
+    /// A range is neither a single `version` nor a two-element `versions` array.
+    MalformedRange { versions: Vec<String> },
+    /// A range bound does not resolve to a known version of the edition.
+    UnresolvableBound { bound: String, reason: String },
+    /// A range's minimum is newer than its maximum, so it matches no version.
+    InvertedRange { range: FeatureRange },
+    /// Two `values` entries of the same feature both match some versions;
+    /// lookups use the later entry for those versions.
+    OverlappingValues {
+        first: FeatureRange,
+        second: FeatureRange,
+    },
+    /// The feature has no `values`, `version` or `versions`, so it is never supported.
+    NoVersionInfo,
+    /// The feature name is defined more than once; lookups use the last definition.
+    DuplicateDefinition,
+}
+
+impl fmt::Display for FeatureDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "feature '{}': ", self.feature)?;
+        match &self.kind {
+            FeatureDiagnosticKind::MalformedRange { versions } => {
+                write!(f, "malformed version range {:?}", versions)
+            }
+            FeatureDiagnosticKind::UnresolvableBound { bound, reason } => {
+                write!(f, "cannot resolve range bound '{}': {}", bound, reason)
+            }
+            FeatureDiagnosticKind::InvertedRange { range } => {
+                write!(f, "inverted range [{}, {}]", range.min, range.max)
+            }
+            FeatureDiagnosticKind::OverlappingValues { first, second } => write!(
+                f,
+                "values ranges [{}, {}] and [{}, {}] overlap",
+                first.min, first.max, second.min, second.max
+            ),
+            FeatureDiagnosticKind::NoVersionInfo => write!(f, "no version information"),
+            FeatureDiagnosticKind::DuplicateDefinition => {
+                write!(f, "defined more than once; the last definition is used")
+            }
+        }
+    }
+}
+
+/// Checks the `features.json` definitions of an edition for malformed, unresolvable,
+/// inverted and overlapping version ranges.
+///
+/// Feature lookups skip the ranges reported here instead of failing, so this is the way
+/// to find out why a feature unexpectedly reads as unsupported.
+///
+/// # Errors
+/// Returns `McDataError` if `features.json` or the edition's version data cannot be loaded.
+pub fn lint_features(edition: Edition) -> Result<Vec<FeatureDiagnostic>, McDataError> {
+    // Load version data up front so load failures are reported as errors, not as unresolvable bounds.
+    version::get_version_data(edition)?;
+    let features = features::get_features(edition)?;
+    Ok(lint_feature_definitions(&features, |bound_str, bound| {
+        features::resolve_range_bound(edition, bound_str, bound)
+    }))
+}
+
+/// Lints feature definitions using `resolve` to turn range bounds into versions.
+pub(crate) fn lint_feature_definitions(
+    features: &[Feature],
+    resolve: impl Fn(&str, RangeBound) -> Result<Version, McDataError>,
+) -> Vec<FeatureDiagnostic> {
+    let mut diagnostics = Vec::new();
+    let mut seen = HashSet::new();
+
+    for feature in features {
+        let mut report = |kind| {
+            diagnostics.push(FeatureDiagnostic {
+                feature: feature.name.clone(),
+                kind,
+            })
+        };
+        if !seen.insert(feature.name.as_str()) {
+            report(FeatureDiagnosticKind::DuplicateDefinition);
+        }
+
+        // Collect the ranges the same way lookups prioritise them.
+        let ranges: Vec<(Option<&String>, &[String])> = if !feature.values.is_empty() {
+            feature
+                .values
+                .iter()
+                .map(|fv| (fv.version.as_ref(), fv.versions.as_slice()))
+                .collect()
+        } else if feature.version.is_some() {
+            vec![(feature.version.as_ref(), &[][..])]
+        } else if !feature.versions.is_empty() {
+            vec![(None, feature.versions.as_slice())]
+        } else {
+            report(FeatureDiagnosticKind::NoVersionInfo);
+            continue;
+        };
+
+        // Resolve each range, keeping the valid ones for the overlap check.
+        let mut resolved: Vec<(FeatureRange, Version, Version)> = Vec::new();
+        for (version, versions) in ranges {
+            let range = match (version, versions) {
+                (Some(v), _) => FeatureRange {
+                    min: v.clone(),
+                    max: v.clone(),
+                },
+                (None, [min, max]) => FeatureRange {
+                    min: min.clone(),
+                    max: max.clone(),
+                },
+                (None, other) => {
+                    report(FeatureDiagnosticKind::MalformedRange {
+                        versions: other.to_vec(),
+                    });
+                    continue;
+                }
+            };
+
+            let mut resolve_bound = |bound_str: &str, bound| match resolve(bound_str, bound) {
+                Ok(v) => Some(v),
+                Err(e) => {
+                    report(FeatureDiagnosticKind::UnresolvableBound {
+                        bound: bound_str.to_string(),
+                        reason: e.to_string(),
+                    });
+                    None
+                }
+            };
+            let min = resolve_bound(&range.min, RangeBound::Min);
+            let max = resolve_bound(&range.max, RangeBound::Max);
+            if let (Some(min), Some(max)) = (min, max) {
+                if min > max {
+                    report(FeatureDiagnosticKind::InvertedRange { range });
+                } else {
+                    resolved.push((range, min, max));
+                }
+            }
+        }
+
+        for (i, (first, first_min, first_max)) in resolved.iter().enumerate() {
+            for (second, second_min, second_max) in &resolved[i + 1..] {
+                if first_min <= second_max && second_min <= first_max {
+                    report(FeatureDiagnosticKind::OverlappingValues {
+                        first: first.clone(),
+                        second: second.clone(),
+                    });
+                }
+            }
+        }
+    }
+
+    diagnostics
+}
@@ -0,0 +1,200 @@
+use crate::error::{Edition, McDataError};
+use crate::loader::load_data_from_path;
+use crate::structs::{Feature, FeatureValue};
+use once_cell::sync::Lazy;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+
+// Registered overlay features per edition, in registration order.
+static OVERLAYS: Lazy<RwLock<HashMap<Edition, Vec<Feature>>>> = Lazy::new(Default::default);
+
+// Incremented whenever overlays change, invalidating merged lists and precomputed feature tables.
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+
+// A merged feature list tagged with the overlay generation it was built for.
+type MergedFeatures = (u64, Arc<Vec<Feature>>);
+
+// Merged upstream + overlay feature lists, keyed by edition.
+static MERGED_FEATURES: Lazy<RwLock<HashMap<Edition, MergedFeatures>>> =
+    Lazy::new(Default::default);
+
+/// A set of feature definitions to merge over the upstream `features.json` of one edition.
+///
+/// Definitions use the same schema as `features.json` (`structs::Feature`). Once registered,
+/// they are consulted by every feature lookup with the following precedence:
+/// 1. Overlay definitions, later registrations first.
+/// 2. Upstream `features.json`.
+///
+/// An overlay definition replaces the upstream definition of the same name entirely;
+/// new names extend the list.
+///
+/// ```no_run
+/// use mcdata_rs::{Edition, FeatureOverlay};
+///
+/// FeatureOverlay::new(Edition::Pc)
+///     .enabled("paperAsyncChunks", "1.13", "latest")
+///     .register()?;
+/// # Ok::<(), mcdata_rs::McDataError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct FeatureOverlay {
+    edition: Edition,
+    features: Vec<Feature>,
+}
+
+impl FeatureOverlay {
+    /// Creates an empty overlay for an edition.
+    pub fn new(edition: Edition) -> Self {
+        FeatureOverlay {
+            edition,
+            features: Vec::new(),
+        }
+    }
+
+    /// Creates an overlay from a JSON file with the same layout as `features.json`.
+    ///
+    /// # Errors
+    /// Returns `McDataError::IoError` or `McDataError::JsonParseError` if the file cannot be read or parsed.
+    pub fn from_json_file(edition: Edition, path: impl AsRef<Path>) -> Result<Self, McDataError> {
+        let features: Vec<Feature> = load_data_from_path(path.as_ref())?;
+        log::debug!(
+            "Loaded {} overlay features for {:?} from {}",
+            features.len(),
+            edition,
+            path.as_ref().display()
+        );
+        Ok(FeatureOverlay { edition, features })
+    }
+
+    /// Adds a full feature definition.
+    pub fn feature(mut self, feature: Feature) -> Self {
+        self.features.push(feature);
+        self
+    }
+
+    /// Adds a feature that is `true` for versions in `[min, max]` and `false` otherwise.
+    /// Bounds accept the same forms as `features.json` ("1.13", "1.8_major", "latest").
+    pub fn enabled(self, name: &str, min: &str, max: &str) -> Self {
+        self.feature(Feature {
+            name: name.to_string(),
+            versions: vec![min.to_string(), max.to_string()],
+            ..Default::default()
+        })
+    }
+
+    /// Adds a feature whose value depends on the version range, like a `values` array in
+    /// `features.json`. Later ranges take precedence where ranges overlap.
+    pub fn valued(
+        self,
+        name: &str,
+        values: impl IntoIterator<Item = (Value, String, String)>,
+    ) -> Self {
+        self.feature(Feature {
+            name: name.to_string(),
+            values: values
+                .into_iter()
+                .map(|(value, min, max)| FeatureValue {
+                    value,
+                    version: None,
+                    versions: vec![min, max],
+                })
+                .collect(),
+            ..Default::default()
+        })
+    }
+
+    /// Registers the overlay globally, taking precedence over upstream data and earlier overlays.
+    ///
+    /// Already loaded `IndexedData` instances pick up the change on their next feature lookup.
+    ///
+    /// # Errors
+    /// Returns `McDataError::Internal` if the overlay registry lock is poisoned.
+    pub fn register(self) -> Result<(), McDataError> {
+        let mut overlays = OVERLAYS
+            .write()
+            .map_err(|_| McDataError::Internal("Feature overlay lock poisoned".to_string()))?;
+        log::debug!(
+            "Registering {} overlay features for {:?}",
+            self.features.len(),
+            self.edition
+        );
+        overlays
+            .entry(self.edition)
+            .or_default()
+            .extend(self.features);
+        GENERATION.fetch_add(1, Ordering::AcqRel);
+        Ok(())
+    }
+}
+
+/// Removes all registered feature overlays for an edition.
+///
+/// # Errors
+/// Returns `McDataError::Internal` if the overlay registry lock is poisoned.
+pub fn clear_feature_overlays(edition: Edition) -> Result<(), McDataError> {
+    let mut overlays = OVERLAYS
+        .write()
+        .map_err(|_| McDataError::Internal("Feature overlay lock poisoned".to_string()))?;
+    if overlays.remove(&edition).is_some() {
+        GENERATION.fetch_add(1, Ordering::AcqRel);
+    }
+    Ok(())
+}
+
+/// The current overlay generation.
+pub(crate) fn generation() -> u64 {
+    GENERATION.load(Ordering::Acquire)
+}
+
+/// Returns the upstream features of an edition merged with its registered overlays.
+pub(crate) fn merged_features(
+    edition: Edition,
+    upstream: Arc<Vec<Feature>>,
+) -> Result<Arc<Vec<Feature>>, McDataError> {
+    let generation = generation();
+    {
+        let merged = MERGED_FEATURES
+            .read()
+            .map_err(|_| McDataError::Internal("Merged features lock poisoned".to_string()))?;
+        if let Some((cached_generation, features)) = merged.get(&edition) {
+            if *cached_generation == generation {
+                return Ok(features.clone());
+            }
+        }
+    } // Read lock is released here.
+
+    let overlays = OVERLAYS
+        .read()
+        .map_err(|_| McDataError::Internal("Feature overlay lock poisoned".to_string()))?;
+    let features = match overlays.get(&edition) {
+        Some(overlay) if !overlay.is_empty() => Arc::new(merge(&upstream, overlay)),
+        _ => upstream,
+    };
+    drop(overlays);
+
+    MERGED_FEATURES
+        .write()
+        .map_err(|_| McDataError::Internal("Merged features lock poisoned".to_string()))?
+        .insert(edition, (generation, features.clone()));
+    Ok(features)
+}
+
+/// Merges overlay definitions over base definitions. Each name keeps only its
+/// highest-precedence definition: the last overlay entry, otherwise the base entry.
+pub(crate) fn merge(base: &[Feature], overlay: &[Feature]) -> Vec<Feature> {
+    let mut merged: Vec<Feature> = base
+        .iter()
+        .filter(|feature| !overlay.iter().any(|o| o.name == feature.name))
+        .cloned()
+        .collect();
+    for (index, feature) in overlay.iter().enumerate() {
+        // Skip definitions overridden by a later overlay entry of the same name.
+        if !overlay[index + 1..].iter().any(|o| o.name == feature.name) {
+            merged.push(feature.clone());
+        }
+    }
+    merged
+}
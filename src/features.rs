@@ -1,5 +1,6 @@
 use crate::data_source;
 use crate::error::{Edition, McDataError};
+use crate::feature_overlay;
 use crate::loader::load_data_from_path;
 use crate::structs::Feature;
 use crate::version::{self, Version};
@@ -8,10 +9,84 @@ use serde_json::Value;
 use std::collections::HashMap;
 use std::sync::{Arc, RwLock};
 
+/// A feature from `features.json` evaluated for a specific version.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResolvedFeature {
+    /// The feature's name in `features.json`.
+    pub name: String,
+    /// The feature's value for the version; `Value::Bool(false)` if no range matched.
+    pub value: Value,
+    /// The version range that produced `value`, or `None` if no range matched.
+    pub range: Option<FeatureRange>,
+}
+
+/// An inclusive version range as written in `features.json`.
+///
+/// Bounds are kept verbatim, so they may be specific versions (e.g., "1.13"),
+/// "latest", or major versions with a "_major" suffix (e.g., "1.8_major").
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FeatureRange {
+    pub min: String,
+    pub max: String,
+}
+
+/// All features evaluated for one version, built once when `IndexedData` is loaded
+/// so that lookups need neither range resolution nor locks.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct FeatureTable {
+    features: Vec<ResolvedFeature>,
+    by_name: HashMap<String, usize>,
+    // Overlay generation the table was built for; registering overlays makes it stale.
+    generation: u64,
+}
+
+impl FeatureTable {
+    /// Evaluates every feature in `features.json` for the target version.
+    pub(crate) fn build(target_version: &Version) -> Result<Self, McDataError> {
+        // Read the generation first, so overlays registered during the build make the table stale.
+        let generation = feature_overlay::generation();
+        let features = get_all_feature_support(target_version)?;
+        let by_name = features
+            .iter()
+            .enumerate()
+            .map(|(index, feature)| (feature.name.clone(), index))
+            .collect();
+        log::debug!(
+            "Built feature table with {} entries for {}",
+            features.len(),
+            target_version.minecraft_version
+        );
+        Ok(FeatureTable {
+            features,
+            by_name,
+            generation,
+        })
+    }
+
+    /// Whether the table still reflects the registered overlays.
+    pub(crate) fn is_current(&self) -> bool {
+        self.generation == feature_overlay::generation()
+    }
+
+    /// Looks up a feature by name.
+    pub(crate) fn get(&self, feature_name: &str) -> Option<&ResolvedFeature> {
+        self.by_name
+            .get(feature_name)
+            .map(|&index| &self.features[index])
+    }
+
+    /// Returns all features in `features.json` order.
+    pub(crate) fn all(&self) -> &[ResolvedFeature] {
+        &self.features
+    }
+}
+
+// Result of loading features.json for a single edition.
+type FeaturesResult = Result<Arc<Vec<Feature>>, McDataError>;
+
 // Cache for loaded features.json data, keyed by edition.
 // Stores the Result to cache loading errors as well.
-static LOADED_FEATURES: OnceCell<HashMap<Edition, Result<Arc<Vec<Feature>>, McDataError>>> =
-    OnceCell::new();
+static LOADED_FEATURES: OnceCell<HashMap<Edition, FeaturesResult>> = OnceCell::new();
 
 /// Loads the features.json file for a specific edition.
 fn load_features_for_edition(edition: Edition) -> Result<Arc<Vec<Feature>>, McDataError> {
@@ -22,9 +97,16 @@ fn load_features_for_edition(edition: Edition) -> Result<Arc<Vec<Feature>>, McDa
     load_data_from_path(&path).map(Arc::new)
 }
 
-/// Retrieves the cached features data for the specified edition.
+/// Retrieves the features for the specified edition: upstream `features.json` merged with any
+/// registered overlays (see `feature_overlay`). The merged list is cached per overlay generation.
+pub(crate) fn get_features(edition: Edition) -> Result<Arc<Vec<Feature>>, McDataError> {
+    let upstream = get_upstream_features(edition)?;
+    feature_overlay::merged_features(edition, upstream)
+}
+
+/// Retrieves the cached upstream features data for the specified edition.
 /// Loads and caches the data for both editions on the first call.
-fn get_features(edition: Edition) -> Result<Arc<Vec<Feature>>, McDataError> {
+pub(crate) fn get_upstream_features(edition: Edition) -> Result<Arc<Vec<Feature>>, McDataError> {
     let cache = LOADED_FEATURES.get_or_init(|| {
         log::debug!("Initializing features cache map");
         let mut map = HashMap::new();
@@ -73,7 +155,10 @@ fn resolve_cached_version(edition: Edition, version_str: &str) -> Result<Version
     } // Read lock is released here.
 
     // If not found in cache, perform the actual version resolution.
-    let resolved_result = crate::version::resolve_version(version_str);
+    // Prefix the edition so Bedrock bounds are not resolved as PC versions.
+    let (_, unprefixed) = version::split_edition_prefix(version_str);
+    let resolved_result =
+        version::resolve_version(&format!("{}_{}", edition.path_prefix(), unprefixed));
 
     // If resolution was successful, cache the result.
     if let Ok(ref version) = resolved_result {
@@ -103,6 +188,69 @@ fn resolve_cached_version(edition: Edition, version_str: &str) -> Result<Version
     resolved_result // Return the original resolution result (Ok or Err).
 }
 
+/// Which end of a `features.json` version range a bound string belongs to.
+///
+/// "_major" bounds resolve to the oldest version of the major for `Min`, and to the newest for `Max`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum RangeBound {
+    Min,
+    Max,
+}
+
+/// Resolves one bound of a `features.json` version range to a `Version`.
+///
+/// Bounds can be specific version strings, "latest", or major versions with a "_major" suffix.
+pub(crate) fn resolve_range_bound(
+    edition: Edition,
+    bound_str: &str,
+    bound: RangeBound,
+) -> Result<Version, McDataError> {
+    if bound == RangeBound::Max && bound_str == "latest" {
+        // Handle "latest": Find the absolute newest version known for the edition.
+        log::trace!("Resolving max_ver 'latest'");
+        let version_data = version::get_version_data(edition)?;
+        return version_data
+            .by_minecraft_version // Use the map containing all versions.
+            .values()
+            .max() // Find the newest version.
+            .cloned()
+            .ok_or_else(|| {
+                McDataError::Internal(format!(
+                    "Could not determine latest version for {:?}",
+                    edition
+                ))
+            });
+    }
+
+    if let Some(base_major) = bound_str.strip_suffix("_major") {
+        // Handle `_major` suffix: Find the OLDEST (min) or NEWEST (max) version within that major release series.
+        log::trace!("Resolving {:?} bound {}_major", bound, base_major);
+        let version_data = version::get_version_data(edition)?;
+        let versions = version_data.by_major_version.get(base_major);
+        // Versions are sorted newest first.
+        let found = match bound {
+            RangeBound::Min => versions.and_then(|versions| versions.last()),
+            RangeBound::Max => versions.and_then(|versions| versions.first()),
+        };
+        return found.cloned().ok_or_else(|| {
+            McDataError::InvalidVersion(format!(
+                "Could not find {} version for major '{}_{}'",
+                if bound == RangeBound::Min {
+                    "oldest"
+                } else {
+                    "newest"
+                },
+                edition.path_prefix(),
+                base_major
+            ))
+        });
+    }
+
+    // Resolve a specific version string using the cache.
+    log::trace!("Resolving {:?} bound {}", bound, bound_str);
+    resolve_cached_version(edition, bound_str)
+}
+
 /// Checks if a target `Version` falls within a specified version range.
 ///
 /// The range can use specific version strings, "latest", or "_major" suffixes.
@@ -120,80 +268,46 @@ fn is_version_in_range(
         max_ver_str
     );
 
-    // Resolve the minimum version boundary.
-    let min_ver = if let Some(base_major) = min_ver_str.strip_suffix("_major") {
-        // Handle `_major` suffix: Find the OLDEST version within that major release series.
-        log::trace!("Resolving min_ver {}_major", base_major);
-        let version_data = version::get_version_data(edition)?;
-        version_data
-            .by_major_version
-            .get(base_major)
-            .and_then(|versions| versions.last()) // Get the last element (oldest in the sorted list).
-            .cloned()
-            .ok_or_else(|| {
-                McDataError::InvalidVersion(format!(
-                    "Could not find oldest version for major '{}_{}'",
-                    edition.path_prefix(),
-                    base_major
-                ))
-            })?
-    } else {
-        // Resolve a specific version string using the cache.
-        log::trace!("Resolving min_ver {}", min_ver_str);
-        resolve_cached_version(edition, min_ver_str)?
-    };
-
-    // Resolve the maximum version boundary.
-    let max_ver = if max_ver_str == "latest" {
-        // Handle "latest": Find the absolute newest version known for the edition.
-        log::trace!("Resolving max_ver 'latest'");
-        let version_data = version::get_version_data(edition)?;
-        version_data
-            .by_minecraft_version // Use the map containing all versions.
-            .values()
-            .max() // Find the version with the highest data_version (newest).
-            .cloned()
-            .ok_or_else(|| {
-                McDataError::Internal(format!(
-                    "Could not determine latest version for {:?}",
-                    edition
-                ))
-            })?
-    } else if let Some(base_major) = max_ver_str.strip_suffix("_major") {
-        // Handle `_major` suffix: Find the NEWEST version within that major release series.
-        log::trace!("Resolving max_ver {}_major", base_major);
-        let version_data = version::get_version_data(edition)?;
-        version_data
-            .by_major_version
-            .get(base_major)
-            .and_then(|versions| versions.first()) // Get the first element (newest in the sorted list).
-            .cloned()
-            .ok_or_else(|| {
-                McDataError::InvalidVersion(format!(
-                    "Could not find newest version for major '{}_{}'",
-                    edition.path_prefix(),
-                    base_major
-                ))
-            })?
-    } else {
-        // Resolve a specific version string using the cache.
-        log::trace!("Resolving max_ver {}", max_ver_str);
-        resolve_cached_version(edition, max_ver_str)?
-    };
+    let min_ver = resolve_range_bound(edition, min_ver_str, RangeBound::Min)?;
+    let max_ver = resolve_range_bound(edition, max_ver_str, RangeBound::Max)?;
 
     // Perform the comparison using the resolved Version structs (which implement Ord).
     let result = target_version >= &min_ver && target_version <= &max_ver;
     log::trace!(
         "Range check: {} >= {} && {} <= {} -> {}",
-        target_version.data_version,
-        min_ver.data_version,
-        target_version.data_version,
-        max_ver.data_version,
+        target_version.minecraft_version,
+        min_ver.minecraft_version,
+        target_version.minecraft_version,
+        max_ver.minecraft_version,
         result
     );
     Ok(result)
 }
 
+/// Like `is_version_in_range`, but treats a range whose bounds cannot be resolved as non-matching,
+/// so one malformed `features.json` entry does not make every lookup of the feature fail.
+/// Errors unrelated to the range itself (e.g., version data failing to load) are still returned.
+fn is_version_in_range_lenient(
+    target_version: &Version,
+    feature_name: &str,
+    min_ver_str: &str,
+    max_ver_str: &str,
+) -> Result<bool, McDataError> {
+    match is_version_in_range(target_version, min_ver_str, max_ver_str) {
+        Err(e @ McDataError::UnknownVersion { .. }) | Err(e @ McDataError::InvalidVersion(_)) => {
+            log::warn!(
+                "Ignoring unresolvable range [{}, {}] in feature '{}': {}",
+                min_ver_str,
+                max_ver_str,
+                feature_name,
+                e
+            );
+            Ok(false)
+        }
+        other => other,
+    }
+}
+
 /// Determines the support status and value of a feature for a given target version.
 ///
 /// It checks the `features.json` data, considering version ranges defined within it.
@@ -215,82 +329,272 @@ pub fn get_feature_support(
     // Find the feature entry by name. Iterating in reverse mimics node-minecraft-data's behavior
     // where later definitions override earlier ones.
     if let Some(feature) = features.iter().rev().find(|f| f.name == feature_name) {
-        log::trace!("Found feature entry: {:?}", feature);
+        return evaluate_feature(target_version, feature);
+    }
 
-        // Priority 1: Check the 'values' array if present.
-        if !feature.values.is_empty() {
-            log::trace!(
-                "Checking feature.values array ({} entries)",
-                feature.values.len()
-            );
-            // Iterate in reverse to match node-minecraft-data's priority (last matching range wins).
-            for fv in feature.values.iter().rev() {
-                let in_range = if let Some(v_str) = &fv.version {
-                    // Single version string range.
-                    is_version_in_range(target_version, v_str, v_str)?
-                } else if fv.versions.len() == 2 {
-                    // [min, max] version array range.
-                    is_version_in_range(target_version, &fv.versions[0], &fv.versions[1])?
-                } else {
-                    log::warn!(
-                        "Invalid version range definition in feature '{}' value: {:?}",
-                        feature_name,
-                        fv
-                    );
-                    false // Treat invalid range definitions as non-matching.
-                };
-                if in_range {
-                    log::debug!(
-                        "Feature '{}' supported via values array, value: {}",
-                        feature_name,
-                        fv.value
-                    );
-                    return Ok(fv.value.clone());
-                }
-            }
-            log::trace!("No matching range found in feature.values");
+    // Feature name was not found in the loaded features data.
+    log::trace!("Feature '{}' not found in features.json", feature_name);
+    log::debug!(
+        "Feature '{}' determined to be unsupported (defaulting to false)",
+        feature_name
+    );
+    Ok(Value::Bool(false))
+}
+
+/// Evaluates every feature defined in `features.json` for the target version.
+///
+/// Features are returned in file order. When a name is defined more than once, the last
+/// definition wins, matching `get_feature_support`.
+pub fn get_all_feature_support(
+    target_version: &Version,
+) -> Result<Vec<ResolvedFeature>, McDataError> {
+    let features = get_features(target_version.edition)?;
+    // Index of the last definition of each name.
+    let mut last_definition: HashMap<&str, usize> = HashMap::new();
+    for (index, feature) in features.iter().enumerate() {
+        last_definition.insert(feature.name.as_str(), index);
+    }
+    features
+        .iter()
+        .enumerate()
+        .filter(|(index, feature)| last_definition[feature.name.as_str()] == *index)
+        .map(|(_, feature)| resolve_feature(target_version, feature))
+        .collect()
+}
+
+/// Like `get_feature_support`, but fails for feature names missing from `features.json`.
+///
+/// # Errors
+/// Returns `McDataError::UnknownFeature` if no feature with this name exists for the target's edition,
+/// or another `McDataError` if feature data or version information cannot be loaded or resolved.
+pub fn get_feature_value(
+    target_version: &Version,
+    feature_name: &str,
+) -> Result<Value, McDataError> {
+    let features = get_features(target_version.edition)?;
+    let feature = find_feature(&features, target_version.edition, feature_name)?;
+    evaluate_feature(target_version, feature)
+}
+
+/// A run of consecutive versions over which a feature keeps the same value.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeatureSegment {
+    /// The oldest version of the run.
+    pub first: Version,
+    /// The newest version of the run.
+    pub last: Version,
+    /// The feature's value for every version in the run.
+    pub value: Value,
+}
+
+/// Returns the ordered list of version segments over which a feature keeps the same value.
+///
+/// Every known version of the edition (oldest first, including snapshots) is evaluated with the
+/// same rules as `get_feature_support`, and consecutive versions with equal values are merged.
+/// Segments where the feature is unsupported carry `Value::Bool(false)`.
+///
+/// # Errors
+/// Returns `McDataError::UnknownFeature` if the feature is not defined for the edition,
+/// or another `McDataError` if feature data or version information cannot be loaded or resolved.
+pub fn feature_timeline(
+    feature_name: &str,
+    edition: Edition,
+) -> Result<Vec<FeatureSegment>, McDataError> {
+    let features = get_features(edition)?;
+    let feature = find_feature(&features, edition, feature_name)?;
+    let version_data = version::get_version_data(edition)?;
+
+    let mut versions: Vec<&Version> = version_data.by_major_version.values().flatten().collect();
+    versions.sort();
+    let values = versions
+        .into_iter()
+        .map(|v| Ok((v.clone(), evaluate_feature(v, feature)?)))
+        .collect::<Result<Vec<_>, McDataError>>()?;
+    Ok(coalesce_segments(values))
+}
+
+/// Returns the oldest version for which a feature is supported (its value is neither `false` nor `null`).
+///
+/// # Errors
+/// Returns `McDataError::UnknownFeature` if the feature is not defined for the edition,
+/// or another `McDataError` if feature data or version information cannot be loaded or resolved.
+pub fn first_version_supporting(
+    feature_name: &str,
+    edition: Edition,
+) -> Result<Option<Version>, McDataError> {
+    Ok(feature_timeline(feature_name, edition)?
+        .into_iter()
+        .find(|segment| is_supported(&segment.value))
+        .map(|segment| segment.first))
+}
+
+/// Returns the newest version for which a feature is supported (its value is neither `false` nor `null`).
+///
+/// # Errors
+/// Returns `McDataError::UnknownFeature` if the feature is not defined for the edition,
+/// or another `McDataError` if feature data or version information cannot be loaded or resolved.
+pub fn last_version_supporting(
+    feature_name: &str,
+    edition: Edition,
+) -> Result<Option<Version>, McDataError> {
+    Ok(feature_timeline(feature_name, edition)?
+        .into_iter()
+        .rev()
+        .find(|segment| is_supported(&segment.value))
+        .map(|segment| segment.last))
+}
+
+// A feature counts as supported unless its value is `false` or `null`.
+fn is_supported(value: &Value) -> bool {
+    !matches!(value, Value::Bool(false) | Value::Null)
+}
+
+/// Merges per-version values (oldest first) into segments of equal consecutive values.
+pub(crate) fn coalesce_segments(values: Vec<(Version, Value)>) -> Vec<FeatureSegment> {
+    let mut segments: Vec<FeatureSegment> = Vec::new();
+    for (version, value) in values {
+        match segments.last_mut() {
+            Some(segment) if segment.value == value => segment.last = version,
+            _ => segments.push(FeatureSegment {
+                first: version.clone(),
+                last: version,
+                value,
+            }),
         }
-        // Priority 2: Check the single 'version' string if 'values' was empty or didn't match.
-        else if let Some(v_str) = &feature.version {
-            log::trace!("Checking feature.version string: {}", v_str);
-            if is_version_in_range(target_version, v_str, v_str)? {
+    }
+    segments
+}
+
+/// Finds the last definition of a feature by name, matching `get_feature_support`'s priority.
+fn find_feature<'a>(
+    features: &'a [Feature],
+    edition: Edition,
+    feature_name: &str,
+) -> Result<&'a Feature, McDataError> {
+    features
+        .iter()
+        .rev()
+        .find(|f| f.name == feature_name)
+        .ok_or_else(|| McDataError::UnknownFeature {
+            name: feature_name.to_string(),
+            edition,
+        })
+}
+
+/// Evaluates a single `features.json` entry for the target version, defaulting to `Value::Bool(false)`.
+fn evaluate_feature(target_version: &Version, feature: &Feature) -> Result<Value, McDataError> {
+    resolve_feature(target_version, feature).map(|resolved| resolved.value)
+}
+
+/// Evaluates a single `features.json` entry for the target version,
+/// recording the version range that produced the value.
+fn resolve_feature(
+    target_version: &Version,
+    feature: &Feature,
+) -> Result<ResolvedFeature, McDataError> {
+    let feature_name = feature.name.as_str();
+    log::trace!("Found feature entry: {:?}", feature);
+    let resolved = |value: Value, min: &str, max: &str| ResolvedFeature {
+        name: feature.name.clone(),
+        value,
+        range: Some(FeatureRange {
+            min: min.to_string(),
+            max: max.to_string(),
+        }),
+    };
+
+    // Priority 1: Check the 'values' array if present.
+    if !feature.values.is_empty() {
+        log::trace!(
+            "Checking feature.values array ({} entries)",
+            feature.values.len()
+        );
+        // Iterate in reverse to match node-minecraft-data's priority (last matching range wins).
+        for fv in feature.values.iter().rev() {
+            let in_range = if let Some(v_str) = &fv.version {
+                // Single version string range.
+                is_version_in_range_lenient(target_version, feature_name, v_str, v_str)?
+            } else if fv.versions.len() == 2 {
+                // [min, max] version array range.
+                is_version_in_range_lenient(
+                    target_version,
+                    feature_name,
+                    &fv.versions[0],
+                    &fv.versions[1],
+                )?
+            } else {
+                log::warn!(
+                    "Invalid version range definition in feature '{}' value: {:?}",
+                    feature_name,
+                    fv
+                );
+                false // Treat invalid range definitions as non-matching.
+            };
+            if in_range {
                 log::debug!(
-                    "Feature '{}' supported via version string (implicit true)",
-                    feature_name
+                    "Feature '{}' supported via values array, value: {}",
+                    feature_name,
+                    fv.value
                 );
-                return Ok(Value::Bool(true)); // Implicitly true if range matches.
+                return Ok(match &fv.version {
+                    Some(v_str) => resolved(fv.value.clone(), v_str, v_str),
+                    None => resolved(fv.value.clone(), &fv.versions[0], &fv.versions[1]),
+                });
             }
         }
-        // Priority 3: Check the 'versions' array [min, max] if others were absent or didn't match.
-        else if feature.versions.len() == 2 {
-            log::trace!(
-                "Checking feature.versions array: [{}, {}]",
-                feature.versions[0],
-                feature.versions[1]
+        log::trace!("No matching range found in feature.values");
+    }
+    // Priority 2: Check the single 'version' string if 'values' was empty or didn't match.
+    else if let Some(v_str) = &feature.version {
+        log::trace!("Checking feature.version string: {}", v_str);
+        if is_version_in_range_lenient(target_version, feature_name, v_str, v_str)? {
+            log::debug!(
+                "Feature '{}' supported via version string (implicit true)",
+                feature_name
             );
-            if is_version_in_range(target_version, &feature.versions[0], &feature.versions[1])? {
-                log::debug!(
-                    "Feature '{}' supported via versions array (implicit true)",
-                    feature_name
-                );
-                return Ok(Value::Bool(true)); // Implicitly true if range matches.
-            }
-        } else {
-            // Feature entry exists but has no valid version definition.
-            log::trace!(
-                "Feature '{}' found but has no version/versions/values definition, assuming false",
+            return Ok(resolved(Value::Bool(true), v_str, v_str)); // Implicitly true if range matches.
+        }
+    }
+    // Priority 3: Check the 'versions' array [min, max] if others were absent or didn't match.
+    else if feature.versions.len() == 2 {
+        log::trace!(
+            "Checking feature.versions array: [{}, {}]",
+            feature.versions[0],
+            feature.versions[1]
+        );
+        if is_version_in_range_lenient(
+            target_version,
+            feature_name,
+            &feature.versions[0],
+            &feature.versions[1],
+        )? {
+            log::debug!(
+                "Feature '{}' supported via versions array (implicit true)",
                 feature_name
             );
+            // Implicitly true if range matches.
+            return Ok(resolved(
+                Value::Bool(true),
+                &feature.versions[0],
+                &feature.versions[1],
+            ));
         }
     } else {
-        // Feature name was not found in the loaded features data.
-        log::trace!("Feature '{}' not found in features.json", feature_name);
+        // Feature entry exists but has no valid version definition.
+        log::trace!(
+            "Feature '{}' found but has no version/versions/values definition, assuming false",
+            feature_name
+        );
     }
 
-    // Default to false if the feature wasn't found or no applicable version range matched.
+    // Default to false if no applicable version range matched.
     log::debug!(
         "Feature '{}' determined to be unsupported (defaulting to false)",
         feature_name
     );
-    Ok(Value::Bool(false))
+    Ok(ResolvedFeature {
+        name: feature.name.clone(),
+        value: Value::Bool(false),
+        range: None,
+    })
 }
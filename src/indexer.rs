@@ -2,7 +2,6 @@ use crate::structs::{
     Attribute, Biome, Block, BlockCollisionShapes, BlockLoot, BlockShapeRef, Effect, Enchantment,
     Entity, EntityLoot, Food, Instrument, Item, MapIcon, Particle, Sound, Window,
 };
-use log;
 use std::collections::HashMap;
 
 // Helper macro to create a HashMap index from a slice of data structs.
@@ -24,6 +23,15 @@ macro_rules! index_by_field {
     };
 }
 
+// Index types returned by functions producing several maps at once.
+type EntityIndexes = (
+    HashMap<u32, Entity>,
+    HashMap<String, Entity>,
+    HashMap<u32, Entity>,
+    HashMap<u32, Entity>,
+);
+type BlockShapeIndexes = (HashMap<u32, Vec<[f64; 6]>>, HashMap<String, Vec<[f64; 6]>>);
+
 // Functions to create indexed HashMaps for various data types.
 
 /// Indexes blocks by ID, name, and state ID.
@@ -93,14 +101,7 @@ pub fn index_effects(effects: &[Effect]) -> (HashMap<u32, Effect>, HashMap<Strin
 }
 
 /// Indexes entities by ID and name, and also creates filtered indexes for mobs and objects.
-pub fn index_entities(
-    entities: &[Entity],
-) -> (
-    HashMap<u32, Entity>,
-    HashMap<String, Entity>,
-    HashMap<u32, Entity>,
-    HashMap<u32, Entity>,
-) {
+pub fn index_entities(entities: &[Entity]) -> EntityIndexes {
     let by_id: HashMap<u32, Entity> = index_by_field!(entities, id, u32);
     let by_name: HashMap<String, Entity> = index_by_field!(entities, name, String);
 
@@ -203,7 +204,7 @@ pub fn index_block_shapes(
     blocks_by_state_id: &HashMap<u32, Block>,
     blocks_by_name: &HashMap<String, Block>,
     collision_data: &BlockCollisionShapes,
-) -> (HashMap<u32, Vec<[f64; 6]>>, HashMap<String, Vec<[f64; 6]>>) {
+) -> BlockShapeIndexes {
     log::debug!("Indexing block shapes...");
     let mut shapes_by_state_id = HashMap::new();
     let mut shapes_by_name = HashMap::new();
@@ -278,10 +279,10 @@ pub fn index_block_shapes(
         } else {
             // Log a warning if a shape was expected but not found for the default state.
             // Avoid warning for blocks explicitly defined as shapeless (shape index 0).
-            let is_explicitly_shapeless =
-                collision_data.blocks.get(name).map_or(false, |shape_ref| {
-                    matches!(shape_ref, BlockShapeRef::Single(0))
-                });
+            let is_explicitly_shapeless = collision_data
+                .blocks
+                .get(name)
+                .is_some_and(|shape_ref| matches!(shape_ref, BlockShapeRef::Single(0)));
             if name != "air" && !is_explicitly_shapeless {
                 log::warn!(
                     "Default state shape not found for block '{}' (defaultState: {})",
@@ -0,0 +1,206 @@
+use crate::error::Edition;
+
+// Built-in version metadata that upstream `protocolVersions.json` does not always provide.
+// These tables are keyed by Minecraft version string and never change once a version ships,
+// so values derived from them stay stable across minecraft-data updates.
+
+/// Mojang data versions of PC releases, used when `protocolVersions.json` omits `dataVersion`.
+/// Data versions were introduced in snapshot 15w32a (1.9 development), so older versions have none.
+const PC_DATA_VERSIONS: &[(&str, i32)] = &[
+    ("1.9", 169),
+    ("1.9.1", 175),
+    ("1.9.2", 176),
+    ("1.9.3", 183),
+    ("1.9.4", 184),
+    ("1.10", 510),
+    ("1.10.1", 511),
+    ("1.10.2", 512),
+    ("1.11", 819),
+    ("1.11.1", 921),
+    ("1.11.2", 922),
+    ("1.12", 1139),
+    ("1.12.1", 1241),
+    ("1.12.2", 1343),
+    ("1.13", 1519),
+    ("1.13.1", 1628),
+    ("1.13.2", 1631),
+    ("1.14", 1952),
+    ("1.14.1", 1957),
+    ("1.14.2", 1963),
+    ("1.14.3", 1968),
+    ("1.14.4", 1976),
+    ("1.15", 2225),
+    ("1.15.1", 2227),
+    ("1.15.2", 2230),
+    ("1.16", 2566),
+    ("1.16.1", 2567),
+    ("1.16.2", 2578),
+    ("1.16.3", 2580),
+    ("1.16.4", 2584),
+    ("1.16.5", 2586),
+    ("1.17", 2724),
+    ("1.17.1", 2730),
+    ("1.18", 2860),
+    ("1.18.1", 2865),
+    ("1.18.2", 2975),
+    ("1.19", 3105),
+    ("1.19.1", 3117),
+    ("1.19.2", 3120),
+    ("1.19.3", 3218),
+    ("1.19.4", 3337),
+    ("1.20", 3463),
+    ("1.20.1", 3465),
+    ("1.20.2", 3578),
+    ("1.20.3", 3698),
+    ("1.20.4", 3700),
+    ("1.20.5", 3837),
+    ("1.20.6", 3839),
+    ("1.21", 3953),
+    ("1.21.1", 3955),
+    ("1.21.2", 4080),
+    ("1.21.3", 4082),
+    ("1.21.4", 4189),
+    ("1.21.5", 4325),
+];
+
+/// Release dates (as `yyyymmdd`) of PC releases that predate data versions.
+/// Used to give pre-1.9 versions a chronological ordinal, since their protocol numbers
+/// restart at the Netty rewrite (1.7) and cannot be compared directly.
+const PC_LEGACY_RELEASE_DATES: &[(&str, u32)] = &[
+    ("1.0", 20111118),
+    ("1.0.0", 20111118),
+    ("1.1", 20120112),
+    ("1.2.1", 20120301),
+    ("1.2.2", 20120301),
+    ("1.2.3", 20120302),
+    ("1.2.4", 20120322),
+    ("1.2.5", 20120404),
+    ("1.3.1", 20120801),
+    ("1.3.2", 20120816),
+    ("1.4.2", 20121025),
+    ("1.4.4", 20121114),
+    ("1.4.5", 20121120),
+    ("1.4.6", 20121220),
+    ("1.4.7", 20121228),
+    ("1.5", 20130313),
+    ("1.5.1", 20130321),
+    ("1.5.2", 20130502),
+    ("1.6", 20130625),
+    ("1.6.1", 20130701),
+    ("1.6.2", 20130708),
+    ("1.6.4", 20130919),
+    ("1.7.1", 20131023),
+    ("1.7.2", 20131025),
+    ("1.7.3", 20131206),
+    ("1.7.4", 20131210),
+    ("1.7.5", 20140226),
+    ("1.7.6", 20140409),
+    ("1.7.7", 20140409),
+    ("1.7.8", 20140411),
+    ("1.7.9", 20140414),
+    ("1.7.10", 20140626),
+    ("1.8", 20140902),
+    ("1.8.1", 20141124),
+    ("1.8.2", 20150219),
+    ("1.8.3", 20150220),
+    ("1.8.4", 20150417),
+    ("1.8.5", 20150522),
+    ("1.8.6", 20150525),
+    ("1.8.7", 20150605),
+    ("1.8.8", 20150728),
+    ("1.8.9", 20151209),
+];
+
+// Ordinals are `yyyymmdd * 100 + sub`. Releases use `RELEASE_SUB`; pre-releases and release
+// candidates use their number (sorting just before the release), and snapshots sort after
+// releases from the same day using their letter.
+const RELEASE_SUB: u32 = 50;
+
+/// Returns the Mojang data version for a version lacking one in upstream data, if known.
+pub fn known_data_version(edition: Edition, minecraft_version: &str) -> Option<i32> {
+    match edition {
+        Edition::Pc => PC_DATA_VERSIONS
+            .iter()
+            .find(|(name, _)| *name == minecraft_version)
+            .map(|(_, data_version)| *data_version),
+        // Bedrock has no equivalent of data versions.
+        Edition::Bedrock => None,
+    }
+}
+
+/// Returns a stable chronological ordinal for a PC version that predates data versions.
+///
+/// Recognises releases from the built-in table, their pre-releases/release candidates
+/// (e.g., "1.8-pre2"), and weekly snapshots (e.g., "14w02a"), whose ordinal is derived from
+/// the snapshot's year and week. Returns `None` for anything else.
+pub fn legacy_ordinal(edition: Edition, minecraft_version: &str) -> Option<u32> {
+    if edition != Edition::Pc {
+        return None;
+    }
+    let release_date = |name: &str| {
+        PC_LEGACY_RELEASE_DATES
+            .iter()
+            .find(|(release, _)| *release == name)
+            .map(|(_, date)| *date)
+    };
+
+    if let Some(date) = release_date(minecraft_version) {
+        return Some(date * 100 + RELEASE_SUB);
+    }
+
+    // Pre-releases and release candidates, e.g. "1.8-pre3" or "1.7.10-pre4".
+    for separator in ["-pre", "-rc"] {
+        if let Some((base, number)) = minecraft_version.split_once(separator) {
+            let number: u32 = number.trim().parse().ok()?;
+            let date = release_date(base)?;
+            return Some(date * 100 + number.min(RELEASE_SUB - 1));
+        }
+    }
+
+    // Weekly snapshots, e.g. "14w02a".
+    let (year, rest) = minecraft_version.split_once('w')?;
+    let year: i32 = year.parse().ok()?;
+    let week_digits: String = rest.chars().take_while(|c| c.is_ascii_digit()).collect();
+    let week: u32 = week_digits.parse().ok()?;
+    let letter = rest[week_digits.len()..].chars().next()?;
+    if year > 99 || !(1..=53).contains(&week) || !letter.is_ascii_lowercase() {
+        return None;
+    }
+    let date = snapshot_date(2000 + year, week);
+    Some(date * 100 + RELEASE_SUB + 1 + (letter as u32 - 'a' as u32))
+}
+
+/// Approximates a snapshot's release date (`yyyymmdd`) as the Thursday of its ISO week.
+fn snapshot_date(year: i32, week: u32) -> u32 {
+    // January 4th is always in ISO week 1.
+    let jan4 = days_from_civil(year, 1, 4);
+    let jan4_weekday = (jan4 + 3).rem_euclid(7); // 0 = Monday
+    let thursday = jan4 - jan4_weekday + 3 + 7 * (week as i64 - 1);
+    let (y, m, d) = civil_from_days(thursday);
+    (y as u32) * 10000 + m * 100 + d
+}
+
+// Days since 1970-01-01 for a proleptic Gregorian date (Howard Hinnant's algorithm).
+fn days_from_civil(year: i32, month: u32, day: u32) -> i64 {
+    let y = if month <= 2 { year - 1 } else { year } as i64;
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let m = month as i64;
+    let doy = (153 * (if m > 2 { m - 3 } else { m + 9 }) + 2) / 5 + day as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146097 + doe - 719468
+}
+
+// Inverse of `days_from_civil`.
+fn civil_from_days(days: i64) -> (i32, u32, u32) {
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = (yoe + era * 400 + if month <= 2 { 1 } else { 0 }) as i32;
+    (year, month, day)
+}
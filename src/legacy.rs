@@ -0,0 +1,188 @@
+use crate::aliases::AliasKind;
+use crate::block_state::{self, BlockState};
+use crate::cached_data::IndexedData;
+use crate::error::McDataError;
+use crate::feature_flags::TheFlattening;
+use crate::structs::Item;
+use std::collections::{BTreeMap, HashMap};
+
+/// A pre-flattening ID and metadata pair, e.g. `(35, 14)` for red wool.
+pub type LegacyId = (u32, u16);
+
+/// Converts pre-flattening (1.12 and older) block and item IDs to the blocks and items of a
+/// 1.13+ version and back, using `legacy.json`.
+///
+/// Modern names in `legacy.json` are those of 1.13; names renamed since (e.g., `grass_path` to
+/// `dirt_path`) are followed through the alias tables (see `AliasTable`). Block properties
+/// whose names or values changed since keep the block's default value instead, e.g. walls'
+/// `north=false`, which became `north=none` in 1.16.
+///
+/// Like vanilla's world upgrader, unknown metadata falls back to metadata 0 of the same ID.
+/// Converting back picks the lowest legacy ID and metadata mapping to a block state or item;
+/// states with no legacy counterpart (e.g., waterlogged blocks) do not convert back.
+///
+/// ```no_run
+/// use mcdata_rs::{mc_data, LegacyConverter};
+///
+/// let data = mc_data("1.20.4")?;
+/// let legacy = LegacyConverter::new(&data)?;
+/// let red_wool = legacy.block(35, 14).unwrap();
+/// assert_eq!(red_wool.name(), "red_wool");
+/// assert_eq!(legacy.legacy_block(red_wool.state_id()), Some((35, 14)));
+/// # Ok::<(), mcdata_rs::McDataError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct LegacyConverter<'a> {
+    data: &'a IndexedData,
+    blocks: BTreeMap<LegacyId, u32>,
+    items: BTreeMap<LegacyId, u32>,
+    legacy_blocks: HashMap<u32, LegacyId>,
+    legacy_items: HashMap<u32, LegacyId>,
+}
+
+impl<'a> LegacyConverter<'a> {
+    /// Builds the conversion tables for a target version.
+    ///
+    /// Entries whose blocks or items do not exist in the target version (e.g., removed since
+    /// 1.13) are skipped and logged at debug level.
+    ///
+    /// # Errors
+    /// Returns `McDataError::LegacyConversionUnavailable` if the edition has no `legacy.json`
+    /// or the target version predates the flattening, `McDataError::InvalidBlockStateString`
+    /// if a block in `legacy.json` is malformed, and another `McDataError` if feature or alias
+    /// data cannot be evaluated.
+    pub fn new(data: &'a IndexedData) -> Result<Self, McDataError> {
+        let unavailable = |reason: &str| McDataError::LegacyConversionUnavailable {
+            edition: data.version.edition,
+            version: data.version.minecraft_version.clone(),
+            reason: reason.to_string(),
+        };
+        let legacy = data
+            .legacy
+            .as_ref()
+            .as_ref()
+            .ok_or_else(|| unavailable("legacy.json is not available"))?;
+        if !data.feature::<TheFlattening>()? {
+            return Err(unavailable("the version predates the flattening"));
+        }
+
+        let blocks = index_entries(&legacy.blocks, |modern| {
+            let (name, properties) = block_state::parse_block_state_string(modern)?;
+            let Some(resolved) = data.canonical_name(AliasKind::Block, name)? else {
+                log::debug!("Skipping legacy block {}: unknown name {}", modern, name);
+                return Ok(None);
+            };
+            let Some(default) = data
+                .blocks_by_name
+                .get(&resolved.name)
+                .and_then(|block| data.block_state(block.default_state))
+            else {
+                log::debug!(
+                    "Skipping legacy block {}: no block {}",
+                    modern,
+                    resolved.name
+                );
+                return Ok(None);
+            };
+            Ok(Some(apply_properties(default, &properties).state_id()))
+        })?;
+        let items = index_entries(&legacy.items, |modern| {
+            let name = modern.strip_prefix("minecraft:").unwrap_or(modern);
+            let Some(resolved) = data.canonical_name(AliasKind::Item, name)? else {
+                log::debug!("Skipping legacy item {}: unknown name {}", modern, name);
+                return Ok(None);
+            };
+            Ok(data.items_by_name.get(&resolved.name).map(|item| item.id))
+        })?;
+
+        Ok(LegacyConverter {
+            data,
+            legacy_blocks: reverse(&blocks),
+            legacy_items: reverse(&items),
+            blocks,
+            items,
+        })
+    }
+
+    /// The state ID of a legacy block in the target version.
+    pub fn block_state_id(&self, id: u32, metadata: u16) -> Option<u32> {
+        lookup(&self.blocks, id, metadata)
+    }
+
+    /// The block state of a legacy block in the target version.
+    pub fn block(&self, id: u32, metadata: u16) -> Option<BlockState> {
+        self.data.block_state(self.block_state_id(id, metadata)?)
+    }
+
+    /// The item of a legacy item (metadata being its variant or damage) in the target version.
+    pub fn item(&self, id: u32, metadata: u16) -> Option<&'a Item> {
+        self.data
+            .items_by_id
+            .get(&lookup(&self.items, id, metadata)?)
+    }
+
+    /// The legacy ID and metadata of a block state of the target version, if it has one.
+    pub fn legacy_block(&self, state_id: u32) -> Option<LegacyId> {
+        self.legacy_blocks.get(&state_id).copied()
+    }
+
+    /// The legacy ID and metadata of an item of the target version, if it has one.
+    pub fn legacy_item(&self, item_id: u32) -> Option<LegacyId> {
+        self.legacy_items.get(&item_id).copied()
+    }
+}
+
+/// Parses a `legacy.json` key: `"id:metadata"`, or a bare `"id"` for metadata 0.
+pub(crate) fn parse_legacy_key(key: &str) -> Option<LegacyId> {
+    let (id, metadata) = key.split_once(':').unwrap_or((key, "0"));
+    Some((id.trim().parse().ok()?, metadata.trim().parse().ok()?))
+}
+
+/// Sets each property on `state` that its block still accepts, logging the ones it does not.
+pub(crate) fn apply_properties(state: BlockState, properties: &[(&str, &str)]) -> BlockState {
+    properties.iter().fold(state, |state, (property, value)| {
+        match state.with_property(property, value) {
+            Ok(changed) => changed,
+            Err(e) => {
+                log::debug!("Keeping default {} of {}: {}", property, state.name(), e);
+                state
+            }
+        }
+    })
+}
+
+/// Maps every well-formed `legacy.json` entry through `resolve`, dropping entries it maps to
+/// `None` and stopping at the first error.
+pub(crate) fn index_entries(
+    entries: &HashMap<String, String>,
+    mut resolve: impl FnMut(&str) -> Result<Option<u32>, McDataError>,
+) -> Result<BTreeMap<LegacyId, u32>, McDataError> {
+    let mut table = BTreeMap::new();
+    for (key, modern) in entries {
+        let Some(legacy_id) = parse_legacy_key(key) else {
+            log::warn!("Skipping malformed legacy.json key {}", key);
+            continue;
+        };
+        if let Some(modern_id) = resolve(modern)? {
+            table.insert(legacy_id, modern_id);
+        }
+    }
+    Ok(table)
+}
+
+/// Inverts a conversion table, keeping the lowest legacy ID for each modern ID.
+pub(crate) fn reverse(table: &BTreeMap<LegacyId, u32>) -> HashMap<u32, LegacyId> {
+    let mut reversed = HashMap::new();
+    for (legacy_id, modern) in table {
+        reversed.entry(*modern).or_insert(*legacy_id);
+    }
+    reversed
+}
+
+/// Looks up a legacy ID, falling back to metadata 0 of the same ID.
+pub(crate) fn lookup(table: &BTreeMap<LegacyId, u32>, id: u32, metadata: u16) -> Option<u32> {
+    table
+        .get(&(id, metadata))
+        .or_else(|| table.get(&(id, 0)))
+        .copied()
+}
@@ -1,24 +1,69 @@
-use log;
 use once_cell::sync::Lazy;
 use std::collections::HashMap;
 use std::sync::{Arc, RwLock};
 
 // Module definitions
+mod aliases;
+mod block_state;
 mod cached_data;
+mod chunk;
+mod cross_edition;
 mod data_source;
+mod diff;
+mod dig;
 mod error;
+mod feature_flags;
+mod feature_lint;
+mod feature_overlay;
 mod features;
 mod indexer;
+mod known_versions;
+mod legacy;
+mod light;
 mod loader;
 mod paths;
+#[cfg(feature = "physics")]
+mod physics;
+mod query;
+mod remap;
+mod shapes;
 mod structs;
+mod tints;
+mod tools;
 mod version;
+mod world;
 
 // Public API exports
+pub use aliases::{clear_aliases, Alias, AliasKind, AliasTable, CanonicalName};
+pub use block_state::BlockState;
 pub use cached_data::IndexedData;
+pub use chunk::{ChunkSection, PackedSection, Palette, SECTION_SIZE, SECTION_VOLUME};
+pub use cross_edition::{closest_bedrock_version, closest_java_version};
+pub use diff::{
+    diff, BlockChange, CategoryDiff, DiffEntry, DiffReport, FeatureChange, IdChange, LootDiff,
+    PropertyChange, Rename,
+};
+pub use dig::DigOptions;
 pub use error::{Edition, McDataError};
+// Flag types are generated from the `features.json` snapshot, so they are re-exported wholesale.
+pub use feature_flags::*;
+pub use feature_lint::{lint_features, FeatureDiagnostic, FeatureDiagnosticKind};
+pub use feature_overlay::{clear_feature_overlays, FeatureOverlay};
+pub use features::{
+    feature_timeline, first_version_supporting, last_version_supporting, FeatureRange,
+    FeatureSegment, ResolvedFeature,
+};
+pub use legacy::{LegacyConverter, LegacyId};
+pub use light::{LightMap, Lighting, MAX_LIGHT};
+#[cfg(feature = "physics")]
+pub use physics::{Controls, EntityState, Physics, PhysicsConstants};
+pub use query::VersionRange;
+pub use remap::{IdMap, IdMapping, RemapCategory, Remapper};
+pub use shapes::{Aabb, Face, RayHit, VoxelShape};
 pub use structs::*;
+pub use tools::ToolEffect;
 pub use version::Version; // Re-export all data structs
+pub use world::World;
 
 // Global cache for loaded and indexed data, keyed by canonical version string (e.g., "pc_1.18.2").
 // Uses a RwLock to allow concurrent reads while ensuring safe writes.
@@ -27,6 +72,8 @@ static DATA_CACHE: Lazy<RwLock<HashMap<String, Arc<IndexedData>>>> = Lazy::new(D
 /// The main entry point to get Minecraft data for a specific version.
 ///
 /// Accepts version strings like "1.18.2", "pc_1.16.5", "bedrock_1.17.10", "1.19".
+/// Edition prefixes are case-insensitive, and "java_"/"je_" and "be_"/"pe_"/"mcpe_" are
+/// accepted as aliases for "pc_" and "bedrock_".
 /// Handles caching of loaded data automatically.
 /// On first use for a specific version (or if data is missing from the local cache),
 /// it may download the required data files from the PrismarineJS/minecraft-data repository
@@ -35,7 +82,8 @@ static DATA_CACHE: Lazy<RwLock<HashMap<String, Arc<IndexedData>>>> = Lazy::new(D
 /// # Errors
 ///
 /// Returns `McDataError` if:
-/// *   The version string is invalid or cannot be resolved to a known Minecraft version.
+/// *   The version string cannot be resolved to a known Minecraft version
+///     (`McDataError::UnknownVersion`, which carries the closest known versions as suggestions).
 /// *   Network errors occur during the initial data download.
 /// *   Filesystem errors occur while accessing or writing to the cache directory.
 /// *   Required data files are missing or corrupt (e.g., JSON parsing errors).
@@ -44,23 +92,33 @@ pub fn mc_data(version_str: &str) -> Result<Arc<IndexedData>, McDataError> {
     // 1. Resolve the input version string to a canonical `Version` struct.
     // This step might trigger initial download/loading of version metadata if not already cached.
     let version = version::resolve_version(version_str)?;
-    let cache_key = format!(
-        "{}_{}",
-        version.edition.path_prefix(),
-        version.minecraft_version
-    );
+    let cache_key = cache_key(&version);
     log::debug!("Requesting data for resolved version key: {}", cache_key);
 
     // 2. Check the cache for existing data using a read lock.
-    {
+    let stale = {
         let cache = DATA_CACHE
             .read()
             .map_err(|_| McDataError::Internal("Data cache read lock poisoned".to_string()))?;
-        if let Some(data) = cache.get(&cache_key) {
-            log::info!("Cache hit for version: {}", cache_key);
-            return Ok(data.clone()); // Return the cached Arc.
+        match cache.get(&cache_key) {
+            Some(data) if data.has_current_feature_table() => {
+                log::info!("Cache hit for version: {}", cache_key);
+                return Ok(data.clone()); // Return the cached Arc.
+            }
+            stale => stale.cloned(),
         }
-    } // Read lock is released here.
+    }; // Read lock is released here.
+
+    // Feature overlays changed since the cached data was built: refresh its feature table.
+    if let Some(stale) = stale {
+        log::info!("Refreshing feature table for version: {}", cache_key);
+        let refreshed = Arc::new(stale.with_refreshed_feature_table());
+        DATA_CACHE
+            .write()
+            .map_err(|_| McDataError::Internal("Data cache write lock poisoned".to_string()))?
+            .insert(cache_key, refreshed.clone());
+        return Ok(refreshed);
+    }
 
     // 3. Cache miss: Load and index the data for this version.
     // This involves reading files, parsing JSON, and building index HashMaps.
@@ -99,6 +157,23 @@ pub fn mc_data(version_str: &str) -> Result<Arc<IndexedData>, McDataError> {
     Ok(loaded_data)
 }
 
+// The `DATA_CACHE` key of a version (e.g., "pc_1.18.2").
+fn cache_key(version: &Version) -> String {
+    format!(
+        "{}_{}",
+        version.edition.path_prefix(),
+        version.minecraft_version
+    )
+}
+
+// Returns the cached data of a version without loading it.
+pub(crate) fn cached_entry(version: &Version) -> Result<Option<Arc<IndexedData>>, McDataError> {
+    let cache = DATA_CACHE
+        .read()
+        .map_err(|_| McDataError::Internal("Data cache read lock poisoned".to_string()))?;
+    Ok(cache.get(&cache_key(version)).cloned())
+}
+
 /// Returns a list of supported Minecraft versions for the given edition,
 /// sorted oldest to newest based on available data in `protocolVersions.json`.
 ///
@@ -140,6 +215,33 @@ mod tests {
         }
     }
 
+    // A blocks.json entry for offline tests: a single-state block whose state ID is its ID,
+    // with `fields` (e.g., "states", "hardness") added or overriding the defaults.
+    fn test_block(id: u32, name: &str, fields: serde_json::Value) -> Block {
+        let mut value = serde_json::json!({
+            "id": id,
+            "name": name,
+            "displayName": name,
+            "minStateId": id,
+            "maxStateId": id,
+            "defaultState": id,
+        });
+        if let serde_json::Value::Object(fields) = fields {
+            value.as_object_mut().unwrap().extend(fields);
+        }
+        serde_json::from_value(value).unwrap()
+    }
+
+    // Indexes test blocks by every state ID in their ranges, like `blocks_by_state_id`.
+    fn test_blocks_by_state_id(blocks: impl IntoIterator<Item = Block>) -> HashMap<u32, Block> {
+        blocks
+            .into_iter()
+            .flat_map(|block| {
+                (block.min_state_id..=block.max_state_id).map(move |id| (id, block.clone()))
+            })
+            .collect()
+    }
+
     #[test]
     fn load_pc_1_18_2() {
         setup();
@@ -269,26 +371,1477 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_legacy_version_ordering() {
+        // Pre-1.9 versions are ordered chronologically, regardless of protocol numbering.
+        let ordinal = |v: &str| known_versions::legacy_ordinal(Edition::Pc, v).unwrap();
+        assert!(ordinal("1.6.4") < ordinal("1.7.2")); // Protocol 78 vs 4 (Netty rewrite).
+        assert!(ordinal("1.7.4") < ordinal("14w02a"));
+        assert!(ordinal("14w02a") < ordinal("1.7.10")); // 1.8 snapshots interleave with 1.7.x.
+        assert!(ordinal("14w02a") < ordinal("14w02b"));
+        assert!(ordinal("14w34d") < ordinal("1.8-pre1"));
+        assert!(ordinal("1.8-pre1") < ordinal("1.8-pre3"));
+        assert!(ordinal("1.8-pre3") < ordinal("1.8"));
+        assert!(ordinal("1.8") < ordinal("1.8.9"));
+        assert!(known_versions::legacy_ordinal(Edition::Bedrock, "1.8.0").is_none());
+        assert!(known_versions::legacy_ordinal(Edition::Pc, "1.18.2").is_none());
+
+        // Data versions come from the built-in table when upstream omits them.
+        assert_eq!(
+            known_versions::known_data_version(Edition::Pc, "1.18.2"),
+            Some(2975)
+        );
+        assert_eq!(
+            known_versions::known_data_version(Edition::Pc, "1.8.9"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_feature_table_matches_live_evaluation() {
+        setup();
+        for version in ["1.8.8", "1.15.2", "1.18.2"] {
+            let data = mc_data(version).unwrap();
+            assert!(
+                data.feature_table.is_some(),
+                "No feature table for {}",
+                version
+            );
+            for feature in data.features().unwrap() {
+                let live = features::get_feature_support(&data.version, &feature.name).unwrap();
+                assert_eq!(
+                    data.support_feature(&feature.name).unwrap(),
+                    live,
+                    "{} in {}",
+                    feature.name,
+                    version
+                );
+            }
+            assert_eq!(
+                data.support_feature("someRandomFeatureName").unwrap(),
+                serde_json::Value::Bool(false)
+            );
+        }
+    }
+
+    #[test]
+    fn test_feature_lint() {
+        use feature_lint::{lint_feature_definitions, FeatureDiagnosticKind as Kind};
+        // A toy edition: "1.N" has data version N; anything else is unknown.
+        let resolve = |bound: &str, _| {
+            let minor: i32 = bound
+                .strip_prefix("1.")
+                .and_then(|n| n.parse().ok())
+                .ok_or_else(|| McDataError::InvalidVersion(bound.to_string()))?;
+            Ok(Version {
+                minecraft_version: bound.to_string(),
+                major_version: bound.to_string(),
+                version: minor,
+                data_version: Some(minor),
+                legacy_ordinal: None,
+                edition: Edition::Pc,
+                release_type: "release".to_string(),
+            })
+        };
+        let features: Vec<Feature> = serde_json::from_str(
+            r#"[
+                {"name": "fine", "values": [
+                    {"value": 1, "versions": ["1.8", "1.12"]},
+                    {"value": 2, "versions": ["1.13", "1.20"]}]},
+                {"name": "overlap", "values": [
+                    {"value": 1, "versions": ["1.8", "1.14"]},
+                    {"value": 2, "version": "1.13"}]},
+                {"name": "inverted", "versions": ["1.20", "1.13"]},
+                {"name": "unknown", "versions": ["1.13", "1.21.9"]},
+                {"name": "malformed", "values": [{"value": true, "versions": ["1.13"]}]},
+                {"name": "empty"},
+                {"name": "fine", "version": "1.13"}
+            ]"#,
+        )
+        .unwrap();
+
+        let kinds: Vec<(String, Kind)> = lint_feature_definitions(&features, resolve)
+            .into_iter()
+            .map(|d| (d.feature, d.kind))
+            .collect();
+        let names: Vec<&str> = kinds.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "overlap",
+                "inverted",
+                "unknown",
+                "malformed",
+                "empty",
+                "fine"
+            ]
+        );
+        assert!(matches!(kinds[0].1, Kind::OverlappingValues { .. }));
+        assert!(matches!(kinds[1].1, Kind::InvertedRange { .. }));
+        assert!(matches!(&kinds[2].1, Kind::UnresolvableBound { bound, .. } if bound == "1.21.9"));
+        assert!(matches!(kinds[3].1, Kind::MalformedRange { .. }));
+        assert_eq!(kinds[4].1, Kind::NoVersionInfo);
+        assert_eq!(kinds[5].1, Kind::DuplicateDefinition);
+    }
+
+    #[test]
+    fn test_feature_overlay_merge() {
+        let feature = |name: &str, version: &str| Feature {
+            name: name.to_string(),
+            version: Some(version.to_string()),
+            ..Default::default()
+        };
+        let upstream = vec![
+            feature("a", "1.8"),
+            feature("b", "1.9"),
+            feature("c", "1.10"),
+        ];
+        let overlay = vec![
+            feature("b", "1.12"),
+            feature("d", "1.13"),
+            feature("b", "1.14"),
+        ];
+
+        let merged = feature_overlay::merge(&upstream, &overlay);
+        let summary: Vec<(&str, &str)> = merged
+            .iter()
+            .map(|f| (f.name.as_str(), f.version.as_deref().unwrap()))
+            .collect();
+        // Overlays replace upstream definitions by name, and later overlay entries win.
+        assert_eq!(
+            summary,
+            vec![("a", "1.8"), ("c", "1.10"), ("d", "1.13"), ("b", "1.14")]
+        );
+    }
+
+    #[test]
+    fn test_diff_categories() {
+        let sounds = |entries: &[(u32, &str)]| -> Vec<Sound> {
+            entries
+                .iter()
+                .map(|&(id, name)| Sound {
+                    id,
+                    name: name.to_string(),
+                })
+                .collect()
+        };
+        let from = sounds(&[(0, "a"), (1, "b"), (2, "old"), (3, "gone"), (4, "d")]);
+        let to = sounds(&[(0, "a"), (1, "b"), (2, "new"), (5, "d"), (6, "fresh")]);
+
+        let report = diff::diff_category(&from, &to);
+        // "old" -> "new" keeps its ID while its neighbour "b" is unchanged, so it reads as a rename.
+        assert_eq!(
+            report.renamed,
+            vec![Rename {
+                from: "old".to_string(),
+                to: "new".to_string(),
+                from_id: 2,
+                to_id: 2,
+            }]
+        );
+        assert_eq!(report.removed.len(), 1);
+        assert_eq!(report.removed[0].name, "gone");
+        assert_eq!(report.added.len(), 1);
+        assert_eq!(report.added[0].name, "fresh");
+        assert_eq!(
+            report.id_changes,
+            vec![IdChange {
+                name: "d".to_string(),
+                from: 4,
+                to: 5,
+            }]
+        );
+
+        let loot_from: HashMap<String, u32> = [("x".to_string(), 1), ("y".to_string(), 2)].into();
+        let loot_to: HashMap<String, u32> = [("y".to_string(), 3), ("z".to_string(), 4)].into();
+        let loot = diff::diff_loot(&loot_from, &loot_to);
+        assert_eq!(loot.added, vec!["z"]);
+        assert_eq!(loot.removed, vec!["x"]);
+        assert_eq!(loot.changed, vec!["y"]);
+
+        let report = DiffReport {
+            from: "pc_1.0".to_string(),
+            to: "pc_1.1".to_string(),
+            sounds: report,
+            block_loot: loot,
+            ..Default::default()
+        };
+        let markdown = report.to_markdown();
+        assert!(markdown.starts_with("# Data changes from pc_1.0 to pc_1.1"));
+        assert!(markdown.contains("## Sounds"));
+        assert!(markdown.contains("- Renamed `old` → `new` (ID 2 → 2)"));
+        assert!(markdown.contains("- Changed `y`"));
+        assert!(!markdown.contains("## Blocks"));
+        let json: serde_json::Value = serde_json::from_str(&report.to_json()).unwrap();
+        assert_eq!(json["sounds"]["added"][0]["name"], "fresh");
+    }
+
+    #[test]
+    fn test_block_state_remapping() {
+        let old = test_block(
+            1,
+            "lever",
+            serde_json::json!({
+                "minStateId": 10,
+                "maxStateId": 13,
+                "defaultState": 13,
+                "states": [
+                    {"name": "facing", "type": "enum", "num_values": 2, "values": ["north", "south"]},
+                    {"name": "powered", "type": "bool", "num_values": 2},
+                ],
+            }),
+        );
+        // The newer block gains a trailing "waterlogged" property, defaulting to false.
+        let new = test_block(
+            1,
+            "lever",
+            serde_json::json!({
+                "minStateId": 100,
+                "maxStateId": 107,
+                "defaultState": 107,
+                "states": [
+                    {"name": "facing", "type": "enum", "num_values": 2, "values": ["north", "south"]},
+                    {"name": "powered", "type": "bool", "num_values": 2},
+                    {"name": "waterlogged", "type": "bool", "num_values": 2},
+                ],
+            }),
+        );
+
+        let properties = remap::state_properties(&old);
+        // The last property varies fastest, and boolean index 0 is true.
+        assert_eq!(
+            properties[1],
+            vec![
+                ("facing".to_string(), "north".to_string()),
+                ("powered".to_string(), "false".to_string()),
+            ]
+        );
+        assert_eq!(properties[2][0].1, "south");
+
+        let mut states = HashMap::new();
+        remap::map_block(&old, &new, remap::MatchKind::Name, &mut states);
+        assert_eq!(states.len(), 4);
+        // facing=south, powered=false -> facing=south, powered=false, waterlogged=false
+        assert_eq!(states[&13], IdMapping::Approximate(107));
+        assert_eq!(states[&10], IdMapping::Approximate(101));
+
+        states.clear();
+        remap::map_block(&new, &old, remap::MatchKind::Name, &mut states);
+        assert_eq!(states[&106], IdMapping::Approximate(13));
+        states.clear();
+        remap::map_block(&old, &old, remap::MatchKind::Fallback, &mut states);
+        assert_eq!(states[&11], IdMapping::Fallback(11));
+    }
+
+    #[test]
+    fn test_pre_flattening_remapping() {
+        // Pre-1.13 blocks.json entries have no state ranges; indexing gives them `id << 4`.
+        let legacy = |id: u32, name: &str| {
+            test_block(
+                id,
+                name,
+                serde_json::json!({"minStateId": 0, "maxStateId": 0, "defaultState": 0}),
+            )
+        };
+        let (old_by_id, _, _) = indexer::index_blocks(&[
+            legacy(0, "air"),
+            legacy(1, "stone"),
+            legacy(35, "wool"),
+            legacy(95, "stained_glass"),
+        ]);
+        let (new_by_id, _, _) =
+            indexer::index_blocks(&[legacy(0, "air"), legacy(1, "stone"), legacy(35, "wool")]);
+
+        let map = remap::map_block_states(&old_by_id, &new_by_id);
+        // Every metadata value of every block is mapped, not just state 0.
+        assert_eq!(map.forward(1 << 4 | 1), Some(IdMapping::Exact(1 << 4 | 1)));
+        assert_eq!(
+            map.forward(35 << 4 | 14),
+            Some(IdMapping::Exact(35 << 4 | 14))
+        );
+        assert_eq!(
+            map.backward(35 << 4 | 14),
+            Some(IdMapping::Exact(35 << 4 | 14))
+        );
+        assert_eq!(map.forward(0), Some(IdMapping::Exact(0)));
+        assert_eq!(
+            map.missing_forward(),
+            (95 << 4..=95 << 4 | 15).collect::<Vec<_>>()
+        );
+        assert!(map.missing_backward().is_empty());
+    }
+
+    #[test]
+    fn test_alias_resolution() {
+        let version = |name: &str, data_version: i32| Version {
+            minecraft_version: name.to_string(),
+            major_version: name.to_string(),
+            version: 0,
+            data_version: Some(data_version),
+            legacy_ordinal: None,
+            edition: Edition::Pc,
+            release_type: "release".to_string(),
+        };
+        let alias = |old: &str, new: &str, renamed_in: &Version| Alias {
+            kind: AliasKind::Block,
+            old_name: old.to_string(),
+            new_name: new.to_string(),
+            renamed_in: renamed_in.clone(),
+        };
+        let (v1, v2, v3) = (version("1", 100), version("2", 200), version("3", 300));
+        let aliases = vec![alias("a", "b", &v2), alias("b", "c", &v3)];
+        let resolve = |at: &Version, name: &str, current: &[&str]| {
+            aliases::resolve_name(&aliases, at, AliasKind::Block, name, None, |n| {
+                current.contains(&n)
+            })
+        };
+
+        // Current names resolve to themselves.
+        let resolved = resolve(&v3, "c", &["c"]).unwrap();
+        assert_eq!((resolved.name.as_str(), resolved.renamed_in), ("c", None));
+        // Old names follow renames forward through chains.
+        let resolved = resolve(&v3, "a", &["c"]).unwrap();
+        assert_eq!(resolved.name, "c");
+        assert_eq!(resolved.renamed_in, Some(v3.clone()));
+        let resolved = resolve(&v2, "a", &["b"]).unwrap();
+        assert_eq!(resolved.name, "b");
+        assert_eq!(resolved.renamed_in, Some(v2.clone()));
+        // New names map back for versions predating the rename.
+        assert_eq!(resolve(&v1, "c", &["a"]).unwrap().name, "a");
+        // Renames never apply to the wrong side of their version, or to other kinds.
+        assert!(resolve(&v1, "a", &["b"]).is_none());
+        assert!(
+            aliases::resolve_name(&aliases, &v3, AliasKind::Item, "a", None, |n| n == "c")
+                .is_none()
+        );
+
+        // A reused name, like `grass`: "x" became "x_block" in 2, "tall" took its place, and
+        // "x" became "short" in 3.
+        let aliases = vec![
+            alias("x", "x_block", &v2),
+            alias("tall", "x", &v2),
+            alias("x", "short", &v3),
+        ];
+        let resolve_from = |from: &Version, at: &Version, name: &str, current: &[&str]| {
+            aliases::resolve_name(&aliases, at, AliasKind::Block, name, Some(from), |n| {
+                current.contains(&n)
+            })
+        };
+        let (new, old) = (&["x_block", "short"][..], &["x", "tall"][..]);
+        // Forward, the name means what it did at the source.
+        let resolved = resolve_from(&v1, &v3, "x", new).unwrap();
+        assert_eq!(resolved.name, "x_block");
+        assert_eq!(resolved.renamed_in, Some(v2.clone()));
+        let resolved = resolve_from(&v1, &v3, "tall", new).unwrap();
+        assert_eq!(resolved.name, "short");
+        assert_eq!(resolved.renamed_in, Some(v3.clone()));
+        assert_eq!(resolve_from(&v2, &v3, "x", new).unwrap().name, "short");
+        // Backward as well.
+        assert_eq!(resolve_from(&v3, &v1, "short", old).unwrap().name, "tall");
+        assert_eq!(resolve_from(&v3, &v1, "x_block", old).unwrap().name, "x");
+        assert_eq!(resolve_from(&v2, &v1, "x", old).unwrap().name, "tall");
+        // Names unchanged between the versions resolve to themselves.
+        let resolved = resolve_from(&v1, &v1, "x", old).unwrap();
+        assert_eq!((resolved.name.as_str(), resolved.renamed_in), ("x", None));
+        assert!(resolve_from(&v2, &v3, "x_block", &["short"]).is_none());
+        // Without a source, a name takes its latest meaning.
+        let resolve = |at: &Version, name: &str, current: &[&str]| {
+            aliases::resolve_name(&aliases, at, AliasKind::Block, name, None, |n| {
+                current.contains(&n)
+            })
+        };
+        assert_eq!(resolve(&v3, "x", new).unwrap().name, "short");
+        assert_eq!(resolve(&v3, "tall", new).unwrap().name, "short");
+        assert_eq!(resolve(&v1, "short", old).unwrap().name, "tall");
+        assert_eq!(resolve(&v1, "x_block", old).unwrap().name, "x");
+    }
+
+    #[test]
+    fn test_version_range_comparators() {
+        use query::{Comparator, Op};
+
+        let (edition, parsed) = query::parse_comparators(">=1.8, <1.21 ,=1.19.2,1.20").unwrap();
+        assert_eq!(edition, None);
+        assert_eq!(
+            parsed,
+            vec![
+                (Op::Ge, "1.8"),
+                (Op::Lt, "1.21"),
+                (Op::Eq, "1.19.2"),
+                (Op::Eq, "1.20"),
+            ]
+        );
+        let (edition, parsed) = query::parse_comparators("*").unwrap();
+        assert_eq!((edition, parsed.len()), (None, 0));
+        let (edition, _) = query::parse_comparators(">=be_1.20.0, <=bedrock_1.21.0").unwrap();
+        assert_eq!(edition, Some(Edition::Bedrock));
+
+        for malformed in ["", ">=1.8,", ">=", "1.8 1.9", ">=pc_1.8, <bedrock_1.21.0"] {
+            assert!(
+                matches!(
+                    query::parse_comparators(malformed),
+                    Err(McDataError::InvalidVersion(_))
+                ),
+                "{:?} should be rejected",
+                malformed
+            );
+        }
+
+        let version = |data_version: i32| Version {
+            minecraft_version: data_version.to_string(),
+            major_version: data_version.to_string(),
+            version: 0,
+            data_version: Some(data_version),
+            legacy_ordinal: None,
+            edition: Edition::Pc,
+            release_type: "release".to_string(),
+        };
+        let at_least = Comparator {
+            op: Op::Ge,
+            version: version(200),
+        };
+        let below = Comparator {
+            op: Op::Lt,
+            version: version(300),
+        };
+        let matches = |v: i32| at_least.matches(&version(v)) && below.matches(&version(v));
+        assert!(!matches(199));
+        assert!(matches(200));
+        assert!(matches(299));
+        assert!(!matches(300));
+    }
+
+    #[test]
+    fn test_block_state_round_trip() {
+        // oak_stairs in 1.16.5: 4 facings x 2 halves x 5 shapes x 2 waterlogged.
+        let stairs: Block = serde_json::from_value(serde_json::json!({
+            "id": 150,
+            "name": "oak_stairs",
+            "displayName": "Oak Stairs",
+            "minStateId": 3955,
+            "maxStateId": 4034,
+            "defaultState": 3966,
+            "states": [
+                {"name": "facing", "type": "enum", "num_values": 4,
+                 "values": ["north", "south", "west", "east"]},
+                {"name": "half", "type": "enum", "num_values": 2, "values": ["top", "bottom"]},
+                {"name": "shape", "type": "enum", "num_values": 5,
+                 "values": ["straight", "inner_left", "inner_right", "outer_left", "outer_right"]},
+                {"name": "waterlogged", "type": "bool", "num_values": 2},
+            ],
+        }))
+        .unwrap();
+
+        let default = BlockState::decode(&stairs, 3966).unwrap();
+        assert!(default.is_default());
+        assert_eq!(
+            default.properties().collect::<Vec<_>>(),
+            vec![
+                ("facing", "north"),
+                ("half", "bottom"),
+                ("shape", "straight"),
+                ("waterlogged", "false"),
+            ]
+        );
+        assert_eq!(default.property("half"), Some("bottom"));
+        assert_eq!(
+            default.to_string(),
+            "minecraft:oak_stairs[facing=north,half=bottom,shape=straight,waterlogged=false]"
+        );
+        assert_eq!(default.property("axis"), None);
+
+        let east_top = default
+            .with_property("facing", "east")
+            .unwrap()
+            .with_property("half", "top")
+            .unwrap();
+        assert_eq!(east_top.state_id(), 3955 + 3 * 20 + 1);
+        assert_eq!(east_top.name(), "oak_stairs");
+
+        let layout = block_state::StateLayout::of(&stairs);
+        for state_id in stairs.min_state_id..=stairs.max_state_id {
+            let indices = layout.decode(state_id).unwrap();
+            assert_eq!(layout.encode(&indices), state_id);
+        }
+        assert!(layout.decode(stairs.max_state_id + 1).is_none());
+        assert!(layout.decode(stairs.min_state_id - 1).is_none());
+
+        match default.with_property("facing", "up") {
+            Err(McDataError::InvalidBlockPropertyValue {
+                property, expected, ..
+            }) => {
+                assert_eq!(property, "facing");
+                assert_eq!(expected.len(), 4);
+            }
+            other => panic!("Expected InvalidBlockPropertyValue, got {:?}", other),
+        }
+        assert_eq!(
+            default.with_property("axis", "x").unwrap_err().to_string(),
+            "Unknown property `axis` for `oak_stairs`"
+        );
+    }
+
+    #[test]
+    fn test_block_state_strings() {
+        let parse = block_state::parse_block_state_string;
+        assert_eq!(
+            parse("minecraft:oak_stairs[facing=north,half=top]").unwrap(),
+            ("oak_stairs", vec![("facing", "north"), ("half", "top")])
+        );
+        assert_eq!(
+            parse(" oak_stairs [ facing = north ] ").unwrap(),
+            ("oak_stairs", vec![("facing", "north")])
+        );
+        assert_eq!(parse("stone").unwrap(), ("stone", vec![]));
+        assert_eq!(parse("minecraft:stone[]").unwrap(), ("stone", vec![]));
+
+        let reason = |input: &str| match parse(input) {
+            Err(McDataError::InvalidBlockStateString { reason, .. }) => reason,
+            other => panic!("{:?} should be rejected, got {:?}", input, other),
+        };
+        assert_eq!(reason(""), "missing block name");
+        assert_eq!(reason("[facing=north]"), "missing block name");
+        assert_eq!(reason("oak_stairs[facing=north"), "missing closing `]`");
+        assert_eq!(
+            reason("oak_stairs[facing]"),
+            "expected `property=value`, found `facing`"
+        );
+        assert_eq!(
+            reason("oak_stairs[facing=north,]"),
+            "expected `property=value`, found ``"
+        );
+        assert_eq!(
+            reason("oak_stairs[facing=north,facing=south]"),
+            "property `facing` is given more than once"
+        );
+        assert_eq!(reason("oak stairs"), "unexpected ` ` in block name");
+        assert_eq!(
+            parse("oak_stairs[facing=north]]").unwrap_err().to_string(),
+            "Invalid block state `oak_stairs[facing=north]]`: unexpected bracket in property list"
+        );
+    }
+
+    #[test]
+    fn test_dig_time() {
+        use std::time::Duration;
+
+        let block = |name: &str, hardness: f32, material: &str, harvest: serde_json::Value| {
+            test_block(
+                1,
+                name,
+                serde_json::json!({
+                    "hardness": hardness,
+                    "diggable": hardness >= 0.0,
+                    "material": material,
+                    "harvestTools": harvest,
+                }),
+            )
+        };
+        let (wooden_pickaxe, diamond_pickaxe) = (702, 717);
+        let stone = block(
+            "stone",
+            1.5,
+            "mineable/pickaxe",
+            serde_json::json!({"702": true, "717": true}),
+        );
+        let dirt = block("dirt", 0.5, "mineable/shovel", serde_json::json!({}));
+        let bedrock = block("bedrock", -1.0, "default", serde_json::json!({}));
+        let materials: Materials = serde_json::from_value(serde_json::json!({
+            "mineable/pickaxe": {"702": 2.0, "717": 8.0},
+            "mineable/shovel": {"703": 2.0},
+        }))
+        .unwrap();
+        let millis = |block: &Block, options: DigOptions| {
+            dig::dig_time(block, Some(&materials), &options).map(|d| d.as_millis())
+        };
+        let holding = |item: u32| DigOptions {
+            held_item: Some(item),
+            ..Default::default()
+        };
+
+        // Known vanilla timings.
+        assert_eq!(millis(&stone, DigOptions::default()), Some(7500));
+        assert_eq!(millis(&stone, holding(wooden_pickaxe)), Some(1150));
+        assert_eq!(millis(&stone, holding(diamond_pickaxe)), Some(300));
+        assert_eq!(millis(&dirt, DigOptions::default()), Some(750));
+        // Tools that are not effective on the material dig at hand speed.
+        assert_eq!(millis(&dirt, holding(diamond_pickaxe)), Some(750));
+
+        let diamond = holding(diamond_pickaxe);
+        let efficiency_5 = DigOptions {
+            efficiency: 5,
+            ..diamond.clone()
+        };
+        assert_eq!(millis(&stone, efficiency_5), Some(100));
+        // Efficiency does nothing without an effective tool.
+        let bare_efficiency = DigOptions {
+            efficiency: 5,
+            ..Default::default()
+        };
+        assert_eq!(millis(&stone, bare_efficiency), Some(7500));
+        let haste_2 = DigOptions {
+            haste: 2,
+            ..diamond.clone()
+        };
+        assert_eq!(millis(&stone, haste_2), Some(250));
+        let fatigue_1 = DigOptions {
+            mining_fatigue: 1,
+            ..Default::default()
+        };
+        assert_eq!(millis(&stone, fatigue_1), Some(25000));
+
+        let underwater = DigOptions {
+            in_water: true,
+            ..Default::default()
+        };
+        assert_eq!(millis(&stone, underwater.clone()), Some(37500));
+        let aqua_affinity = DigOptions {
+            aqua_affinity: true,
+            ..underwater.clone()
+        };
+        assert_eq!(millis(&stone, aqua_affinity), Some(7500));
+        let swimming = DigOptions {
+            not_on_ground: true,
+            ..underwater
+        };
+        assert_eq!(millis(&stone, swimming), Some(187500));
+
+        assert_eq!(millis(&bedrock, diamond), None);
+        let creative = DigOptions {
+            creative: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            dig::dig_time(&bedrock, Some(&materials), &creative),
+            Some(Duration::ZERO)
+        );
+    }
+
+    #[test]
+    fn test_materials_and_tools() {
+        let materials: Materials = serde_json::from_value(serde_json::json!({
+            "mineable/pickaxe": {"702": 2, "707": 4.0, "717": 8, "not_an_id": 3, "712": "fast"},
+            "default": {},
+        }))
+        .unwrap();
+        // Malformed entries are skipped rather than failing the whole file.
+        assert_eq!(materials.get("mineable/pickaxe").unwrap().len(), 3);
+        assert_eq!(materials.multiplier("mineable/pickaxe", 707), Some(4.0));
+        assert_eq!(materials.multiplier("mineable/pickaxe", 1), None);
+        assert_eq!(
+            materials.effective_tools("mineable/pickaxe"),
+            vec![(717, 8.0), (707, 4.0), (702, 2.0)]
+        );
+        assert!(materials.effective_tools("default").is_empty());
+        assert!(materials.effective_tools("mineable/axe").is_empty());
+
+        // Iron ore needs a stone pickaxe (707) or better to drop.
+        let iron_ore: Block = serde_json::from_value(serde_json::json!({
+            "id": 1,
+            "name": "iron_ore",
+            "displayName": "Iron Ore",
+            "hardness": 3.0,
+            "diggable": true,
+            "material": "mineable/pickaxe",
+            "harvestTools": {"707": true, "717": true},
+        }))
+        .unwrap();
+        let effect = tools::tool_effect(&iron_ore, Some(&materials), Some(702));
+        assert_eq!(effect.speed_multiplier, 2.0);
+        assert!(!effect.harvests);
+        assert!(tools::can_harvest(&iron_ore, Some(707)));
+        assert!(!tools::can_harvest(&iron_ore, None));
+        assert_eq!(
+            tools::tool_effect(&iron_ore, Some(&materials), None).speed_multiplier,
+            1.0
+        );
+
+        let best = |candidates: &[u32]| tools::best_tool(&iron_ore, Some(&materials), candidates);
+        assert_eq!(best(&[702, 717, 707]), Some(717));
+        // A tool that makes the block drop beats a faster one that does not.
+        assert_eq!(best(&[1, 702, 707]), Some(707));
+        // Without harvesting tools, the fastest candidate wins, then the earliest.
+        assert_eq!(best(&[1, 702]), Some(702));
+        assert_eq!(best(&[5, 1]), Some(5));
+        assert_eq!(best(&[]), None);
+    }
+
+    #[test]
+    fn test_collision_shapes() {
+        let slab = VoxelShape::from_arrays(&[[0.0, 0.0, 0.0, 1.0, 0.5, 1.0]]);
+        let top_slab = slab.offset(0.0, 0.5, 0.0);
+        assert!(!slab.is_full_cube());
+        assert!(slab.union(&top_slab).is_full_cube());
+        assert!(slab.intersection(&top_slab).is_empty());
+        assert!(slab.is_face_solid(Face::Down));
+        assert!(!slab.is_face_solid(Face::Up));
+        assert!(!slab.is_face_solid(Face::North));
+        assert!(VoxelShape::empty().bounds().is_none());
+
+        // Stairs: bottom half plus a back quarter; full on the bottom and back, not on top.
+        let stairs = VoxelShape::from_arrays(&[
+            [0.0, 0.0, 0.0, 1.0, 0.5, 1.0],
+            [0.0, 0.5, 0.5, 1.0, 1.0, 1.0],
+        ]);
+        assert!(stairs.is_face_solid(Face::South));
+        assert!(!stairs.is_face_solid(Face::Up));
+        assert!(!stairs.is_full_cube());
+        // Overlapping boxes still form a full cube.
+        assert!(VoxelShape::from_arrays(&[
+            [0.0, 0.0, 0.0, 1.0, 0.75, 1.0],
+            [0.0, 0.25, 0.0, 1.0, 1.0, 1.0],
+        ])
+        .is_full_cube());
+
+        // A player standing on the slab at (3, 64, 7) can only fall 0.5 blocks.
+        let placed = slab.at_block(3, 64, 7);
+        assert_eq!(placed.bounds().unwrap().max_y, 64.5);
+        let player = Aabb::new(3.2, 65.0, 7.2, 3.8, 66.8, 7.8);
+        assert_eq!(placed.calculate_y_offset(&player, -2.0), -0.5);
+        assert_eq!(placed.calculate_y_offset(&player, 1.0), 1.0);
+        // Moving sideways above the slab is not blocked.
+        assert_eq!(placed.calculate_x_offset(&player, 1.0), 1.0);
+        let beside = player.offset(-1.0, -0.8, 0.0);
+        assert!((placed.calculate_x_offset(&beside, 1.0) - 0.2).abs() < 1e-9);
+
+        let unit = Aabb::full_cube();
+        assert_eq!(
+            unit.intersection(&unit.offset(0.5, 0.5, 0.5)),
+            Some(Aabb::new(0.5, 0.5, 0.5, 1.0, 1.0, 1.0))
+        );
+        assert_eq!(unit.intersection(&unit.offset(1.0, 0.0, 0.0)), None);
+        assert_eq!(
+            unit.expand_towards(-1.0, 2.0, 0.0),
+            Aabb::new(-1.0, 0.0, 0.0, 1.0, 3.0, 1.0)
+        );
+
+        // Looking down onto the slab hits its top face.
+        let hit = placed
+            .raycast([3.5, 66.0, 7.5], [0.0, -1.0, 0.0], 5.0)
+            .unwrap();
+        assert_eq!(hit.face, Face::Up);
+        assert_eq!(hit.point, [3.5, 64.5, 7.5]);
+        assert_eq!(hit.distance, 1.5);
+        let hit = stairs
+            .raycast([0.5, 0.75, -1.0], [0.0, 0.0, 1.0], 5.0)
+            .unwrap();
+        assert_eq!(hit.face, Face::North);
+        assert_eq!(hit.point, [0.5, 0.75, 0.5]);
+        assert!(placed
+            .raycast([3.5, 66.0, 7.5], [0.0, -1.0, 0.0], 1.0)
+            .is_none());
+        assert!(placed
+            .raycast([3.5, 66.0, 7.5], [0.0, 1.0, 0.0], 5.0)
+            .is_none());
+    }
+
+    #[cfg(feature = "physics")]
+    #[test]
+    fn test_physics_simulation() {
+        use serde_json::json;
+        let blocks = test_blocks_by_state_id([
+            test_block(0, "air", json!({})),
+            test_block(1, "stone", json!({})),
+            test_block(2, "oak_slab", json!({})),
+            test_block(3, "ladder", json!({})),
+            test_block(
+                4,
+                "water",
+                json!({"minStateId": 10, "maxStateId": 25, "defaultState": 10,
+                       "states": [{"name": "level", "type": "int", "num_values": 16}]}),
+            ),
+        ]);
+        let shapes: HashMap<u32, Vec<[f64; 6]>> = HashMap::from([
+            (0, vec![]),
+            (1, vec![[0.0, 0.0, 0.0, 1.0, 1.0, 1.0]]),
+            (2, vec![[0.0, 0.0, 0.0, 1.0, 0.5, 1.0]]),
+            (3, vec![[0.0, 0.0, 0.8125, 1.0, 1.0, 1.0]]),
+        ]);
+        let physics = Physics::from_parts(
+            &blocks,
+            &shapes,
+            PhysicsConstants::from_features(|name| name == "independentLiquidGravity"),
+        );
+        let ticks = |world: &dyn Fn(i32, i32, i32) -> Option<u32>, state: &mut EntityState, n| {
+            for _ in 0..n {
+                physics.simulate(&world, state);
+            }
+        };
+
+        // Falling onto a stone floor.
+        let floor = |_x: i32, y: i32, _z: i32| Some(if y < 64 { 1 } else { 0 });
+        let mut player = EntityState::player([0.5, 70.0, 0.5]);
+        ticks(&floor, &mut player, 40);
+        assert!(player.on_ground);
+        assert_eq!(player.position[1], 64.0);
+        // Standing still, gravity is applied after the ground stops the fall, as in vanilla.
+        assert!((player.velocity[1] + 0.0784).abs() < 1e-9);
+
+        // Walking south (+Z) steps up onto a row of slabs.
+        let slabs = |_x: i32, y: i32, z: i32| {
+            Some(match (y, z) {
+                (..=63, _) => 1,
+                (64, 3..) => 2,
+                _ => 0,
+            })
+        };
+        let mut player = EntityState::player([0.5, 64.0, 0.5]);
+        player.on_ground = true;
+        player.controls.forward = true;
+        ticks(&slabs, &mut player, 20);
+        assert_eq!(player.position[1], 64.5);
+        assert!(player.position[2] > 4.0);
+        assert_eq!(player.position[0], 0.5);
+
+        // Sneaking stops at the edge of the floor instead of falling off.
+        let ledge = |_x: i32, y: i32, z: i32| Some(if y < 64 && z <= 2 { 1 } else { 0 });
+        let mut player = EntityState::player([0.5, 64.0, 0.5]);
+        player.on_ground = true;
+        player.controls.forward = true;
+        player.controls.sneak = true;
+        ticks(&ledge, &mut player, 100);
+        assert!(player.on_ground);
+        assert_eq!(player.position[1], 64.0);
+        assert!(player.position[2] > 3.0 && player.position[2] < 3.3);
+        // Without sneaking, the player walks off and falls.
+        player.controls.sneak = false;
+        ticks(&ledge, &mut player, 20);
+        assert!(player.position[1] < 64.0);
+
+        // Walking into a ladder climbs it.
+        let ladder = |x: i32, y: i32, z: i32| {
+            Some(match (x, y, z) {
+                (_, ..=63, _) => 1,
+                (0, 64..=80, 0) => 3,
+                _ => 0,
+            })
+        };
+        let mut player = EntityState::player([0.5, 64.0, 0.4]);
+        player.on_ground = true;
+        player.controls.forward = true;
+        ticks(&ladder, &mut player, 20);
+        assert!(player.on_ladder);
+        // Climbing rises 0.1176 blocks per tick once against the ladder.
+        assert!(player.position[1] > 66.0);
+        assert!((player.velocity[1] - 0.1176).abs() < 1e-9);
+
+        // Water slows sinking to its own gravity.
+        let pool = |_x: i32, y: i32, _z: i32| Some(if y < 60 { 1 } else { 10 });
+        let mut player = EntityState::player([0.5, 62.0, 0.5]);
+        physics.simulate(&pool, &mut player);
+        assert!(player.in_water && !player.in_lava);
+        assert_eq!(player.velocity[1], -0.02);
+
+        let legacy = PhysicsConstants::from_features(|_| false);
+        assert_eq!((legacy.water_gravity, legacy.lava_gravity), (0.005, 0.02));
+        let proportional =
+            PhysicsConstants::from_features(|name| name == "proportionalLiquidGravity");
+        assert_eq!(proportional.water_gravity, 0.005);
+        assert!(!proportional.climb_using_jump);
+    }
+
+    #[test]
+    fn test_chunk_section_packing() {
+        // The long layout changed in snapshots: 20w17a (2529) and 21w37a (2834).
+        let layout = |edition: Edition, data_version: Option<i32>| {
+            chunk::PaletteLayout::of(&Version {
+                minecraft_version: "test".to_string(),
+                major_version: "test".to_string(),
+                version: 0,
+                data_version,
+                legacy_ordinal: None,
+                edition,
+                release_type: "snapshot".to_string(),
+            })
+            .map(|layout| (layout.spanning, layout.single_valued))
+        };
+        // 1.8 and older have no data version and no paletted sections.
+        assert!(matches!(
+            layout(Edition::Pc, None),
+            Err(McDataError::InvalidChunkSection { .. })
+        ));
+        assert_eq!(layout(Edition::Pc, Some(2504)).unwrap(), (true, false));
+        assert_eq!(layout(Edition::Pc, Some(2529)).unwrap(), (false, false));
+        assert_eq!(layout(Edition::Pc, Some(2833)).unwrap(), (false, false));
+        assert_eq!(layout(Edition::Pc, Some(2834)).unwrap(), (false, true));
+        assert!(matches!(
+            layout(Edition::Bedrock, None),
+            Err(McDataError::InvalidChunkSection { .. })
+        ));
+
+        // Reference longs for 5 bits per entry, from the protocol documentation.
+        let padded = [0x0020863148418841];
+        let expected = vec![1, 2, 2, 3, 4, 4, 5, 6, 6, 4, 8, 0];
+        assert_eq!(chunk::unpack(&padded, 5, 12, false).unwrap(), expected);
+        assert_eq!(chunk::pack(&expected, 5, false), padded);
+        let spanning = [0x7020863148418841, 0x8B1018A7260F68C8];
+        let values = chunk::unpack(&spanning, 5, 25, true).unwrap();
+        assert_eq!(
+            values,
+            vec![1, 2, 2, 3, 4, 4, 5, 6, 6, 4, 8, 0, 7, 4, 3, 13, 15, 16, 9, 14, 10, 12, 0, 2, 11]
+        );
+        assert_eq!(
+            chunk::unpack(&chunk::pack(&values, 5, true), 5, 25, true).unwrap(),
+            values
+        );
+        assert!(matches!(
+            chunk::unpack(&spanning, 5, 12, false),
+            Err(McDataError::InvalidChunkSection { .. })
+        ));
+
+        assert_eq!(chunk::ceil_log2(1), 0);
+        assert_eq!(chunk::ceil_log2(16), 4);
+        assert_eq!(chunk::ceil_log2(17), 5);
+        assert_eq!(chunk::ceil_log2(17112), 15);
+
+        // Air with a stone floor and one dirt block: a 4-bit indirect palette.
+        let mut section = ChunkSection::filled(0);
+        for x in 0..SECTION_SIZE {
+            for z in 0..SECTION_SIZE {
+                section.set(x, 0, z, 1);
+            }
+        }
+        assert_eq!(section.set(3, 5, 7, 10), Some(0));
+        assert_eq!(section.set(16, 0, 0, 10), None);
+        assert_eq!(section.get(3, 5, 7), Some(10));
+        assert_eq!(section.state_ids()[(5 * 16 + 7) * 16 + 3], 10);
+        let known = |state_id: u32| state_id < 20_000;
+        for spanning in [true, false] {
+            let packed = chunk::encode(&section, 15, spanning, true);
+            assert_eq!(packed.bits_per_entry, 4);
+            assert_eq!(packed.palette, Palette::Indirect(vec![1, 0, 10]));
+            assert_eq!(packed.data.len(), 256);
+            assert_eq!(chunk::decode(&packed, spanning, known).unwrap(), section);
+        }
+
+        // More than 256 states fall back to the global palette.
+        let varied =
+            ChunkSection::from_state_ids((0..SECTION_VOLUME as u32).map(|i| i % 300).collect())
+                .unwrap();
+        let packed = chunk::encode(&varied, 15, false, true);
+        assert_eq!(
+            (packed.bits_per_entry, &packed.palette),
+            (15, &Palette::Direct)
+        );
+        assert_eq!(packed.data.len(), 1024);
+        assert_eq!(chunk::decode(&packed, false, known).unwrap(), varied);
+
+        // One state is sent without data from 1.18, and as a 4-bit palette before.
+        let air = ChunkSection::filled(0);
+        let packed = chunk::encode(&air, 15, false, true);
+        assert_eq!((packed.bits_per_entry, packed.data.len()), (0, 0));
+        assert_eq!(chunk::decode(&packed, false, known).unwrap(), air);
+        assert_eq!(chunk::encode(&air, 14, true, false).bits_per_entry, 4);
+
+        let bad_index = PackedSection {
+            bits_per_entry: 4,
+            palette: Palette::Indirect(vec![0]),
+            data: chunk::pack(&[1; SECTION_VOLUME], 4, false),
+        };
+        assert!(matches!(
+            chunk::decode(&bad_index, false, known),
+            Err(McDataError::InvalidChunkSection { .. })
+        ));
+        let unknown = PackedSection {
+            bits_per_entry: 0,
+            palette: Palette::Single(25_000),
+            data: Vec::new(),
+        };
+        assert!(chunk::decode(&unknown, false, known).is_err());
+        assert!(ChunkSection::from_state_ids(vec![0; 10]).is_err());
+    }
+
+    #[test]
+    fn test_light_propagation() {
+        use serde_json::json;
+        let blocks = test_blocks_by_state_id([
+            test_block(0, "air", json!({})),
+            test_block(1, "stone", json!({"filterLight": 15})),
+            test_block(2, "glowstone", json!({"emitLight": 15, "filterLight": 15})),
+            test_block(
+                3,
+                "furnace",
+                json!({"emitLight": 13, "filterLight": 15, "maxStateId": 4,
+                       "states": [{"name": "lit", "type": "bool", "num_values": 2}]}),
+            ),
+            test_block(5, "oak_slab", json!({})),
+            test_block(6, "oak_leaves", json!({"filterLight": 1})),
+            test_block(7, "glass", json!({})),
+            test_block(8, "oak_trapdoor", json!({})),
+            test_block(9, "white_carpet", json!({})),
+            test_block(
+                10,
+                "snow",
+                json!({"maxStateId": 17, "states": [{"name": "layers", "type": "int",
+                       "num_values": 8, "values": ["1", "2", "3", "4", "5", "6", "7", "8"]}]}),
+            ),
+        ]);
+        let full = vec![[0.0, 0.0, 0.0, 1.0, 1.0, 1.0]];
+        let shapes: HashMap<u32, Vec<[f64; 6]>> = HashMap::from([
+            (0, vec![]),
+            (1, full.clone()),
+            (2, full.clone()),
+            (5, vec![[0.0, 0.0, 0.0, 1.0, 0.5, 1.0]]),
+            (6, full.clone()),
+            (7, full),
+            (8, vec![[0.0, 0.0, 0.0, 1.0, 0.1875, 1.0]]),
+            (9, vec![[0.0, 0.0, 0.0, 1.0, 0.0625, 1.0]]),
+            (10, vec![]),
+        ]);
+        let lighting = Lighting::from_parts(&blocks, &shapes);
+        assert_eq!((lighting.emission(3), lighting.emission(4)), (13, 0));
+        assert_eq!(
+            (
+                lighting.opacity(1),
+                lighting.opacity(6),
+                lighting.opacity(99)
+            ),
+            (15, 1, 0)
+        );
+
+        // A glowstone on a stone floor, with a bottom slab on top of it.
+        let lamp = |_x: i32, y: i32, _z: i32| Some(if y < 64 { 1 } else { 0 });
+        let lamp = |x: i32, y: i32, z: i32| match (x, y, z) {
+            (0, 64, 0) => Some(2),
+            (0, 65, 0) => Some(5),
+            _ => lamp(x, y, z),
+        };
+        let light = lighting.compute(&lamp, [-4, 63, -4], [4, 70, 4]);
+        assert_eq!((light.min(), light.max()), ([-4, 63, -4], [4, 70, 4]));
+        assert_eq!(light.block_light(0, 64, 0), Some(15));
+        assert_eq!(light.block_light(2, 65, 1), Some(11));
+        assert_eq!(light.block_light(0, 63, 1), Some(0));
+        assert_eq!(light.block_light(5, 64, 0), None);
+        // The slab's full bottom face keeps light out from below; it is lit from the side.
+        assert_eq!(light.block_light(0, 65, 0), Some(12));
+        assert_eq!(light.sky_light(3, 64, 3), Some(15));
+        assert_eq!(light.sky_light(0, 63, 3), Some(0));
+
+        // Sky light under a roof at y = 70.
+        let under_roof = |roof: u32| {
+            let world = move |_x: i32, y: i32, _z: i32| {
+                Some(match y {
+                    ..=63 => 1,
+                    70 => roof,
+                    _ => 0,
+                })
+            };
+            lighting.compute(&world, [0, 64, 0], [0, 71, 0])
+        };
+        // Glass lets full sky light down; leaves dim it, and below them it fades per block.
+        assert_eq!(under_roof(7).sky_light(0, 64, 0), Some(15));
+        let leaves = under_roof(6);
+        assert_eq!(leaves.sky_light(0, 70, 0), Some(14));
+        assert_eq!(leaves.sky_light(0, 64, 0), Some(8));
+        // Bottom slabs pass no light downwards at all.
+        let slabs = under_roof(5);
+        assert_eq!(slabs.sky_light(0, 70, 0), Some(15));
+        assert_eq!(slabs.sky_light(0, 69, 0), Some(0));
+        // Closed trapdoors and carpets cover a whole face but let light through, as in vanilla.
+        assert_eq!(under_roof(8).sky_light(0, 69, 0), Some(15));
+        assert_eq!(under_roof(9).sky_light(0, 64, 0), Some(15));
+        // A single snow layer has no collision box, yet stops light like its visible shape.
+        assert_eq!(under_roof(10).sky_light(0, 69, 0), Some(0));
+    }
+
+    #[test]
+    fn test_tint_colors() {
+        use serde_json::json;
+        use tints::TintSource;
+        assert_eq!(
+            tints::colormap_color(1.0, 1.0, &tints::GRASS_COLORMAP),
+            0x47CD33
+        );
+        assert_eq!(
+            tints::colormap_color(1.5, 0.0, &tints::GRASS_COLORMAP),
+            0xBFB755
+        );
+        assert_eq!(
+            tints::colormap_color(-0.5, 0.8, &tints::FOLIAGE_COLORMAP),
+            0x60A17B
+        );
+        // Plains: temperature 0.8, rainfall 0.4.
+        assert_eq!(
+            tints::colormap_color(0.8, 0.4, &tints::GRASS_COLORMAP),
+            0x8CBD57
+        );
+        assert_eq!(tints::redstone_formula(0), 0x4C0000);
+        assert_eq!(tints::redstone_formula(15), 0xFF3200);
+
+        let grass: structs::TintData = serde_json::from_value(json!({
+            "default": 0x123456,
+            "data": [{"keys": ["minecraft:swamp", "mangrove_swamp"], "color": 0x6A7039}],
+        }))
+        .unwrap();
+        let redstone: structs::TintData =
+            serde_json::from_value(json!({"data": [{"keys": [3], "color": 0x7A0000}]})).unwrap();
+        assert_eq!(tints::listed_color(&grass, "swamp"), Some(0x6A7039));
+        assert_eq!(tints::listed_color(&redstone, "3"), Some(0x7A0000));
+        assert_eq!(tints::listed_color(&redstone, "4"), None);
+
+        let plains: Biome = serde_json::from_value(json!({
+            "id": 1, "name": "plains", "temperature": 0.8, "rainfall": 0.4,
+        }))
+        .unwrap();
+        let color =
+            |name, biome| tints::biome_color(Some(&grass), name, biome, &tints::GRASS_COLORMAP);
+        assert_eq!(color("mangrove_swamp", Some(&plains)), Some(0x6A7039));
+        assert_eq!(color("plains", Some(&plains)), Some(0x8CBD57));
+        assert_eq!(color("unknown", None), Some(0x123456));
+        assert_eq!(
+            tints::biome_color(None, "unknown", None, &tints::GRASS_COLORMAP),
+            None
+        );
+
+        let source = |name: &str, states: serde_json::Value, state_id: u32| {
+            let block = test_block(0, name, json!({"maxStateId": 15, "states": states}));
+            let constant: structs::TintData = serde_json::from_value(
+                json!({"data": [{"keys": ["birch_leaves"], "color": 0x80A755}]}),
+            )
+            .unwrap();
+            let state = BlockState::decode(&block, state_id).unwrap();
+            tints::tint_source(&state, Some(&constant))
+        };
+        let int =
+            |name: &str, values: u32| json!([{"name": name, "type": "int", "num_values": values}]);
+        assert_eq!(source("grass_block", json!([]), 0), Some(TintSource::Grass));
+        assert_eq!(source("vine", json!([]), 0), Some(TintSource::Foliage));
+        assert_eq!(
+            source("birch_leaves", json!([]), 0),
+            Some(TintSource::Fixed(0x80A755))
+        );
+        assert_eq!(
+            source("redstone_wire", int("power", 16), 9),
+            Some(TintSource::Redstone(9))
+        );
+        assert_eq!(
+            source("melon_stem", int("age", 8), 7),
+            Some(TintSource::Fixed(0xE0C71C))
+        );
+        assert_eq!(source("stone", json!([]), 0), None);
+    }
+
+    #[test]
+    fn test_legacy_tables() {
+        assert_eq!(legacy::parse_legacy_key("35:14"), Some((35, 14)));
+        assert_eq!(legacy::parse_legacy_key("256"), Some((256, 0)));
+        assert_eq!(legacy::parse_legacy_key("stone:1"), None);
+        assert_eq!(legacy::parse_legacy_key("1:-1"), None);
+
+        let entries: HashMap<String, String> = [
+            ("1:0", "minecraft:stone"),
+            ("1:1", "minecraft:granite"),
+            ("17:0", "minecraft:oak_log[axis=y]"),
+            ("17:12", "minecraft:oak_wood[axis=y]"),
+            ("17:4", "minecraft:oak_log[axis=x]"),
+            ("43:0", "minecraft:smooth_stone"),
+            ("bad", "minecraft:air"),
+            ("95:0", "minecraft:removed_block"),
+        ]
+        .into_iter()
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect();
+        // Stand-in state IDs; smooth_stone stands for a block both 1:0 and 43:0 map to.
+        let table = legacy::index_entries(&entries, |modern| {
+            let (name, properties) = block_state::parse_block_state_string(modern)?;
+            Ok(match (name, properties.first().map(|(_, value)| *value)) {
+                ("stone" | "smooth_stone", _) => Some(1),
+                ("granite", _) => Some(2),
+                ("oak_log", Some("y")) => Some(74),
+                ("oak_log", Some("x")) => Some(73),
+                ("oak_wood", _) => Some(110),
+                _ => None,
+            })
+        })
+        .unwrap();
+        assert_eq!(table.len(), 6);
+        assert_eq!(legacy::lookup(&table, 17, 4), Some(73));
+        // Unknown metadata falls back to metadata 0; unknown IDs do not resolve.
+        assert_eq!(legacy::lookup(&table, 1, 9), Some(1));
+        assert_eq!(legacy::lookup(&table, 95, 0), None);
+
+        let reversed = legacy::reverse(&table);
+        assert_eq!(reversed[&74], (17, 0));
+        assert_eq!(reversed[&110], (17, 12));
+        // Several legacy IDs mapping to one state convert back to the lowest.
+        assert_eq!(reversed[&1], (1, 0));
+        // Errors are not mistaken for unknown names.
+        let malformed: HashMap<String, String> =
+            HashMap::from([("1:0".to_string(), "minecraft:stone[axis".to_string())]);
+        assert!(matches!(
+            legacy::index_entries(&malformed, |modern| {
+                block_state::parse_block_state_string(modern)?;
+                Ok(Some(1))
+            }),
+            Err(McDataError::InvalidBlockStateString { .. })
+        ));
+
+        // Walls' 1.13 `north=false` no longer parses in 1.16+, so `north` keeps its default.
+        let wall = test_block(
+            139,
+            "cobblestone_wall",
+            serde_json::json!({
+                "minStateId": 0,
+                "maxStateId": 5,
+                "defaultState": 0,
+                "states": [
+                    {"name": "north", "type": "enum", "num_values": 3, "values": ["none", "low", "tall"]},
+                    {"name": "up", "type": "bool", "num_values": 2},
+                ],
+            }),
+        );
+        let default = BlockState::decode(&wall, 0).unwrap();
+        let state = legacy::apply_properties(default, &[("north", "false"), ("up", "false")]);
+        assert_eq!(state.property("north"), Some("none"));
+        assert_eq!(state.property("up"), Some("false"));
+        assert_eq!(state.state_id(), 1);
+    }
+
+    #[test]
+    fn test_feature_timeline_coalescing() {
+        use serde_json::json;
+        let version = |mc: &str, data_version: i32| Version {
+            minecraft_version: mc.to_string(),
+            major_version: mc.to_string(),
+            version: 0,
+            data_version: Some(data_version),
+            legacy_ordinal: None,
+            edition: Edition::Pc,
+            release_type: "release".to_string(),
+        };
+        let segments = features::coalesce_segments(vec![
+            (version("1.15", 2225), json!(false)),
+            (version("1.16", 2566), json!(false)),
+            (version("1.17", 2724), json!(7)),
+            (version("1.18", 2860), json!(8)),
+            (version("1.19", 3105), json!(8)),
+        ]);
+        let summary: Vec<_> = segments
+            .iter()
+            .map(|s| {
+                (
+                    s.first.minecraft_version.as_str(),
+                    s.last.minecraft_version.as_str(),
+                    s.value.clone(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("1.15", "1.16", json!(false)),
+                ("1.17", "1.17", json!(7)),
+                ("1.18", "1.19", json!(8)),
+            ]
+        );
+        assert!(features::coalesce_segments(Vec::new()).is_empty());
+    }
+
+    #[test]
+    fn test_edition_correspondence_table_is_ordered() {
+        use std::cmp::Ordering;
+        // Both columns must be non-decreasing for the nearest-entry lookups to be meaningful.
+        for pair in cross_edition::BEDROCK_TO_JAVA.windows(2) {
+            let (b0, j0) = pair[0];
+            let (b1, j1) = pair[1];
+            assert_eq!(
+                version::compare_version_strings(b0, b1),
+                Ordering::Less,
+                "Bedrock {} should precede {}",
+                b0,
+                b1
+            );
+            assert_ne!(
+                version::compare_version_strings(j0, j1),
+                Ordering::Greater,
+                "Java {} should not follow {}",
+                j0,
+                j1
+            );
+        }
+    }
+
     #[test]
     fn test_invalid_version() {
         setup();
         let result = mc_data("invalid_version_string_1.2.3");
         assert!(result.is_err());
         match result.err().unwrap() {
-            McDataError::InvalidVersion(s) => assert!(s.contains("invalid_version")),
-            e => panic!("Expected InvalidVersion error, got {:?}", e),
+            McDataError::UnknownVersion { version, .. } => {
+                assert!(version.contains("invalid_version"))
+            }
+            e => panic!("Expected UnknownVersion error, got {:?}", e),
+        }
+    }
+
+    #[test]
+    fn test_edition_prefix_aliases() {
+        use version::split_edition_prefix;
+        assert_eq!(
+            split_edition_prefix("pc_1.18.2"),
+            (Some(Edition::Pc), "1.18.2")
+        );
+        assert_eq!(
+            split_edition_prefix("java_1.18.2"),
+            (Some(Edition::Pc), "1.18.2")
+        );
+        assert_eq!(
+            split_edition_prefix("JE_1.18.2"),
+            (Some(Edition::Pc), "1.18.2")
+        );
+        assert_eq!(
+            split_edition_prefix("be_1.20.10"),
+            (Some(Edition::Bedrock), "1.20.10")
+        );
+        assert_eq!(
+            split_edition_prefix("Bedrock_1.20.10"),
+            (Some(Edition::Bedrock), "1.20.10")
+        );
+        assert_eq!(split_edition_prefix("1.18.2"), (None, "1.18.2"));
+    }
+
+    #[test]
+    fn test_version_suggestions() {
+        let known = [
+            "1.17.1", "1.18", "1.18.1", "1.18.2", "1.19", "1.8.8", "21w07a",
+        ];
+        let suggest = |input| version::suggest_versions(input, &known, 3);
+
+        assert_eq!(suggest("1.18.3"), vec!["1.18.2", "1.18.1", "1.18"]);
+        assert_eq!(suggest("1,18,2")[0], "1.18.2");
+        assert_eq!(suggest("21w07b"), vec!["21w07a"]);
+        assert!(suggest("definitely not a version").is_empty());
+
+        let error = McDataError::UnknownVersion {
+            version: "1.18.3".to_string(),
+            edition: Edition::Pc,
+            suggestions: suggest("1.18.3"),
+        };
+        assert_eq!(
+            error.to_string(),
+            "Version '1.18.3' is not a known pc version; did you mean 1.18.2, 1.18.1, 1.18?"
+        );
+    }
+
+    #[test]
+    fn test_feature_flag_values() {
+        use feature_flags::{FeatureFlag, FromFeatureValue, MetadataIxOfItem, TheFlattening};
+        use serde_json::json;
+
+        assert_eq!(TheFlattening::NAME, "theFlattening");
+        assert!(feature_flags::ALL_FEATURE_NAMES.contains(&"tallWorld"));
+
+        assert_eq!(bool::from_feature_value(&json!(true)), Some(true));
+        assert_eq!(bool::from_feature_value(&json!(8)), None);
+        // Unsupported valued features read as `false`, which maps to `None`.
+        type Ix = <MetadataIxOfItem as FeatureFlag>::Value;
+        assert_eq!(Ix::from_feature_value(&json!(8)), Some(Some(8)));
+        assert_eq!(Ix::from_feature_value(&json!(false)), Some(None));
+        assert_eq!(Ix::from_feature_value(&json!("8")), None);
+        assert_eq!(
+            Option::<String>::from_feature_value(&json!("ench")),
+            Some(Some("ench".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_feature_flags_match_upstream() {
+        setup();
+        let upstream =
+            features::get_upstream_features(Edition::Pc).expect("Failed to load features");
+        let expected = feature_flags::render_snapshot(&upstream);
+        if std::env::var_os("MCDATA_UPDATE_FEATURE_FLAGS").is_some() {
+            let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("src/feature_flags.tsv");
+            std::fs::write(&path, &expected).expect("Failed to write the feature flag snapshot");
+            return;
         }
+        assert_eq!(
+            include_str!("feature_flags.tsv"),
+            expected,
+            "src/feature_flags.tsv is out of date with features.json; regenerate it with \
+             MCDATA_UPDATE_FEATURE_FLAGS=1 cargo test feature_flags_match_upstream"
+        );
+    }
+
+    #[test]
+    fn test_feature_flag_snapshot_rendering() {
+        let features: Vec<Feature> = serde_json::from_value(serde_json::json!([
+            {"name": "theFlattening", "description": "flattened\tIDs", "versions": ["1.13", "latest"]},
+            {"name": "metadataIxOfItem", "values": [
+                {"value": 7, "versions": ["1.14", "1.16.5"]},
+                {"value": 8, "versions": ["1.17", "latest"]}
+            ]},
+            {"name": "nbtNameForEnchant", "values": [{"value": "ench", "versions": ["1.8", "1.12.2"]}]},
+            {"name": "mixed", "values": [
+                {"value": false, "versions": ["1.8", "1.8.9"]},
+                {"value": true, "versions": ["1.9", "1.9.4"]},
+                {"value": 3, "versions": ["1.10", "latest"]}
+            ]},
+            {"name": "theFlattening", "versions": ["1.13", "1.20"]},
+        ]))
+        .unwrap();
+        let snapshot = feature_flags::render_snapshot(&features);
+        let lines: Vec<&str> = snapshot
+            .lines()
+            .filter(|line| !line.starts_with('#'))
+            .collect();
+        assert_eq!(
+            lines,
+            vec![
+                "theFlattening\tbool\tflattened IDs",
+                "metadataIxOfItem\tinteger\t",
+                "nbtNameForEnchant\tstring\t",
+                "mixed\tjson\t",
+            ]
+        );
+        // The checked-in snapshot uses the same header.
+        assert!(include_str!("feature_flags.tsv")
+            .starts_with(&snapshot[..snapshot.find("# name").unwrap()]));
+    }
+
+    #[test]
+    fn test_bedrock_struct_shapes() {
+        // Bedrock blocks omit Java-only fields and list numeric state values as numbers.
+        let block: Block = serde_json::from_str(
+            r#"{"id":1,"name":"stone","displayName":"Stone","hardness":1.5,
+                "states":[{"name":"facing_direction","type":"int","num_values":2,"values":[0,1]}]}"#,
+        )
+        .unwrap();
+        assert_eq!(block.stack_size, 64);
+        assert_eq!(block.bounding_box, "block");
+        assert_eq!(block.states[0].num_values, Some(2));
+        assert_eq!(block.states[0].values, vec!["0", "1"]);
+
+        let states: Vec<BedrockBlockState> = serde_json::from_str(
+            r#"[{"name":"air","states":{},"version":17959425},
+                {"name":"acacia_button","states":{"button_pressed_bit":{"type":"byte","value":0}}}]"#,
+        )
+        .unwrap();
+        assert_eq!(states[1].states["button_pressed_bit"].value_type, "byte");
+
+        let mapping: BlockMapping = serde_json::from_str(
+            r#"{"pc":{"name":"granite","states":{}},"pe":{"name":"stone","states":{"stone_type":"granite"}}}"#,
+        )
+        .unwrap();
+        assert_eq!(mapping.pe.states["stone_type"], "granite");
+
+        let steve: SteveSkin = serde_json::from_str(
+            r#"{"SkinId":"Standard_Custom","SkinImageWidth":64,"PremiumSkin":false}"#,
+        )
+        .unwrap();
+        assert_eq!(steve.skin_image_width, Some(64));
+        assert!(steve.extra.contains_key("PremiumSkin"));
     }
 
-    // Placeholder for Bedrock tests when data/support is confirmed.
-    // #[test]
-    // fn load_bedrock_version() {
-    //     setup();
-    //     let version = "bedrock_1.18.30"; // Use a known valid Bedrock version
-    //     let data = mc_data(version).expect("Failed to load Bedrock data");
-    //     assert_eq!(data.version.edition, Edition::Bedrock);
-    //     assert!(data.version.minecraft_version.contains("1.18.30"));
-    //     assert!(!data.blocks_array.is_empty());
-    //     assert!(!data.items_array.is_empty());
-    // }
+    #[test]
+    fn load_bedrock_version() {
+        setup();
+        let version = "bedrock_1.18.30";
+        let data = mc_data(version).expect("Failed to load Bedrock data");
+        assert_eq!(data.version.edition, Edition::Bedrock);
+        assert!(data.version.minecraft_version.contains("1.18.30"));
+        assert!(!data.blocks_array.is_empty());
+        assert!(!data.items_array.is_empty());
+        assert!(data.blocks_by_name.contains_key("stone"));
+    }
 }
@@ -0,0 +1,368 @@
+// Flood-fill light propagation following vanilla's light engine (1.14+): block light spreads
+// from emitting blocks and sky light falls from above, each losing at least one level per block.
+
+use crate::block_state::BlockState;
+use crate::cached_data::IndexedData;
+use crate::shapes::{Aabb, Face, VoxelShape};
+use crate::structs::Block;
+use crate::world::World;
+use std::collections::{HashMap, VecDeque};
+
+/// The highest light level.
+pub const MAX_LIGHT: u8 = 15;
+
+// How far light reaches: blocks this far outside a region can still light it.
+const LIGHT_RANGE: i32 = MAX_LIGHT as i32;
+
+/// Block-light and sky-light levels of a region, as computed by `Lighting::compute`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LightMap {
+    min: [i32; 3],
+    size: [usize; 3],
+    block: Vec<u8>,
+    sky: Vec<u8>,
+}
+
+impl LightMap {
+    /// The lowest corner of the region.
+    pub fn min(&self) -> [i32; 3] {
+        self.min
+    }
+
+    /// The highest corner of the region (inclusive).
+    pub fn max(&self) -> [i32; 3] {
+        [0, 1, 2].map(|axis| self.min[axis] + self.size[axis] as i32 - 1)
+    }
+
+    /// The block-light level at a position, or `None` if it is outside the region.
+    pub fn block_light(&self, x: i32, y: i32, z: i32) -> Option<u8> {
+        self.index(x, y, z).map(|index| self.block[index])
+    }
+
+    /// The sky-light level at a position, or `None` if it is outside the region.
+    ///
+    /// This is the level at full daylight; vanilla subtracts the sky darkening (up to 11 at
+    /// night) before comparing it with block light.
+    pub fn sky_light(&self, x: i32, y: i32, z: i32) -> Option<u8> {
+        self.index(x, y, z).map(|index| self.sky[index])
+    }
+
+    fn index(&self, x: i32, y: i32, z: i32) -> Option<usize> {
+        Grid::new(self.min, self.size).index([x, y, z])
+    }
+}
+
+/// Computes light levels from caller-supplied blocks, using each state's light emission and
+/// opacity (`Block.emit_light` and `filter_light`).
+///
+/// Some light behaviour depends on the state rather than the block:
+/// - blocks with a `lit` property emit nothing while unlit (e.g., furnaces, redstone lamps),
+///   and `light` blocks emit their `level`;
+/// - waterlogged blocks dim light like water, by at least one level;
+/// - the blocks vanilla gives shape-based occlusion (slabs, stairs, farmland, snow layers, dirt
+///   paths, daylight detectors, lecterns and a few others) stop light passing through the
+///   faces their shape covers. Other see-through blocks, such as carpets and trapdoors, do not.
+///
+/// ```no_run
+/// use mcdata_rs::{mc_data, Lighting};
+///
+/// let data = mc_data("1.20.4")?;
+/// let lighting = Lighting::new(&data);
+/// let stone = data.blocks_by_name["stone"].default_state;
+/// let torch = data.blocks_by_name["torch"].default_state;
+/// // A stone floor with a torch on it, open to the sky.
+/// let world = |x: i32, y: i32, z: i32| {
+///     Some(match (x, y, z) {
+///         (0, 64, 0) => torch,
+///         (_, y, _) if y < 64 => stone,
+///         _ => 0,
+///     })
+/// };
+///
+/// let light = lighting.compute(&world, [-8, 60, -8], [8, 80, 8]);
+/// assert_eq!(light.block_light(0, 64, 0), Some(14));
+/// assert_eq!(light.block_light(3, 64, 0), Some(11));
+/// assert_eq!(light.sky_light(3, 64, 0), Some(15));
+/// assert_eq!(light.sky_light(3, 63, 0), Some(0));
+/// # Ok::<(), mcdata_rs::McDataError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct Lighting<'a> {
+    blocks: &'a HashMap<u32, Block>,
+    shapes: &'a HashMap<u32, Vec<[f64; 6]>>,
+}
+
+impl<'a> Lighting<'a> {
+    /// Creates a light calculator for the data's version.
+    pub fn new(data: &'a IndexedData) -> Self {
+        Lighting::from_parts(&data.blocks_by_state_id, &data.block_shapes_by_state_id)
+    }
+
+    pub(crate) fn from_parts(
+        blocks: &'a HashMap<u32, Block>,
+        shapes: &'a HashMap<u32, Vec<[f64; 6]>>,
+    ) -> Self {
+        Lighting { blocks, shapes }
+    }
+
+    /// The light level a block state emits. Unknown states emit nothing.
+    pub fn emission(&self, state_id: u32) -> u8 {
+        self.properties(state_id).emission
+    }
+
+    /// The levels light loses passing into a block state, from 0 (e.g., air, glass) to 15
+    /// (opaque). Unknown states are treated as air.
+    ///
+    /// Light always loses at least one level per block, except sky light at full strength
+    /// falling straight down through blocks of opacity 0.
+    pub fn opacity(&self, state_id: u32) -> u8 {
+        self.properties(state_id).opacity
+    }
+
+    /// Computes block and sky light for the region between `min` and `max` (inclusive).
+    ///
+    /// Blocks up to 15 blocks outside the region are read too, since their light reaches into
+    /// it. Sky light enters unobstructed at the top of that margin, so for exact sky light the
+    /// region should reach above the highest block of the world. Unloaded blocks are treated
+    /// as air.
+    pub fn compute(&self, world: &impl World, min: [i32; 3], max: [i32; 3]) -> LightMap {
+        let (min, max) = (
+            [0, 1, 2].map(|a| min[a].min(max[a])),
+            [0, 1, 2].map(|a| min[a].max(max[a])),
+        );
+        let outer_min = min.map(|v| v - LIGHT_RANGE);
+        let outer_max = max.map(|v| v + LIGHT_RANGE);
+        let outer = Grid::new(
+            outer_min,
+            [0, 1, 2].map(|a| (outer_max[a] - outer_min[a] + 1) as usize),
+        );
+
+        // Light properties of every block, worked out once per distinct state.
+        let mut by_state: HashMap<u32, usize> = HashMap::new();
+        let mut properties: Vec<LightProperties> = Vec::new();
+        let cells: Vec<usize> = outer
+            .positions()
+            .map(|[x, y, z]| {
+                let state_id = world.state_id(x, y, z).unwrap_or(0);
+                *by_state.entry(state_id).or_insert_with(|| {
+                    properties.push(self.properties(state_id));
+                    properties.len() - 1
+                })
+            })
+            .collect();
+        let cell = |index: usize| &properties[cells[index]];
+
+        let mut block = vec![0u8; cells.len()];
+        let mut queue = VecDeque::new();
+        for (index, light) in cells.iter().map(|c| &properties[*c]).enumerate() {
+            if light.emission > 0 {
+                block[index] = light.emission;
+                queue.push_back(index);
+            }
+        }
+        propagate(&outer, &mut block, queue, false, cell);
+
+        // Sky light comes down into the top layer from an open sky above it.
+        let mut sky = vec![0u8; cells.len()];
+        let mut queue = VecDeque::new();
+        for x in outer_min[0]..=outer_max[0] {
+            for z in outer_min[2]..=outer_max[2] {
+                let Some(index) = outer.index([x, outer_max[1], z]) else {
+                    continue;
+                };
+                let level = light_into(None, cell(index), Face::Down, MAX_LIGHT, true);
+                if level > 0 {
+                    sky[index] = level;
+                    queue.push_back(index);
+                }
+            }
+        }
+        propagate(&outer, &mut sky, queue, true, cell);
+
+        let size = [0, 1, 2].map(|a| (max[a] - min[a] + 1) as usize);
+        let inner = Grid::new(min, size);
+        let copy = |levels: &[u8]| -> Vec<u8> {
+            inner
+                .positions()
+                .map(|position| outer.index(position).map_or(0, |index| levels[index]))
+                .collect()
+        };
+        LightMap {
+            min,
+            size,
+            block: copy(&block),
+            sky: copy(&sky),
+        }
+    }
+
+    fn properties(&self, state_id: u32) -> LightProperties {
+        let Some(block) = self.blocks.get(&state_id) else {
+            return LightProperties::default();
+        };
+        let state = BlockState::decode(block, state_id);
+        let property = |name: &str| state.as_ref().and_then(|state| state.property(name));
+
+        let emission = match (block.name.as_str(), property("lit"), property("level")) {
+            (_, Some("false"), _) => 0,
+            ("light", _, Some(level)) => level.parse().unwrap_or(0),
+            _ => block.emit_light,
+        };
+        let mut opacity = block.filter_light.min(MAX_LIGHT);
+        if property("waterlogged") == Some("true") {
+            opacity = opacity.max(1);
+        }
+        // Opaque blocks stop light anyway; full and empty shapes have no partial faces.
+        let occlusion = if opacity < MAX_LIGHT && uses_shape_for_light_occlusion(&block.name) {
+            match property("layers").and_then(|layers| layers.parse::<f64>().ok()) {
+                // Snow's collision box is a layer lower than the snow itself.
+                Some(layers) => Some(VoxelShape::from_boxes([Aabb::new(
+                    0.0,
+                    0.0,
+                    0.0,
+                    1.0,
+                    layers / 8.0,
+                    1.0,
+                )])),
+                None => self
+                    .shapes
+                    .get(&state_id)
+                    .map(|boxes| VoxelShape::from_arrays(boxes)),
+            }
+            .filter(|shape| !shape.is_empty() && !shape.is_full_cube())
+        } else {
+            None
+        };
+
+        LightProperties {
+            emission: emission.min(MAX_LIGHT),
+            opacity,
+            occlusion,
+        }
+    }
+}
+
+// Blocks whose shape stops light through the faces it covers (vanilla's
+// `useShapeForLightOcclusion`), under both modern and pre-1.13 names. Other non-opaque blocks,
+// such as carpets, trapdoors and hoppers, let light through whatever their shape.
+fn uses_shape_for_light_occlusion(name: &str) -> bool {
+    name.ends_with("_slab")
+        || name.ends_with("_stairs")
+        || matches!(
+            name,
+            "farmland"
+                | "snow"
+                | "snow_layer"
+                | "dirt_path"
+                | "grass_path"
+                | "daylight_detector"
+                | "daylight_detector_inverted"
+                | "enchanting_table"
+                | "lectern"
+                | "stonecutter"
+                | "sculk_sensor"
+                | "calibrated_sculk_sensor"
+                | "sculk_shrieker"
+                | "end_portal_frame"
+        )
+}
+
+#[derive(Debug, Clone, Default)]
+struct LightProperties {
+    emission: u8,
+    opacity: u8,
+    occlusion: Option<VoxelShape>,
+}
+
+// Spreads light from the queued cells until no neighbour can be raised any further.
+fn propagate<'p>(
+    grid: &Grid,
+    levels: &mut [u8],
+    mut queue: VecDeque<usize>,
+    sky: bool,
+    cell: impl Fn(usize) -> &'p LightProperties,
+) {
+    while let Some(index) = queue.pop_front() {
+        let level = levels[index];
+        let position = grid.position(index);
+        for face in Face::ALL {
+            let normal = face.normal();
+            let neighbour = [0, 1, 2].map(|a| position[a] + normal[a]);
+            let Some(next) = grid.index(neighbour) else {
+                continue;
+            };
+            let raised = light_into(Some(cell(index)), cell(next), face, level, sky);
+            if raised > levels[next] {
+                levels[next] = raised;
+                queue.push_back(next);
+            }
+        }
+    }
+}
+
+// The level light of `level` has after moving from a block (`None` for the open sky) through
+// its `face` into the neighbouring block.
+fn light_into(
+    from: Option<&LightProperties>,
+    to: &LightProperties,
+    face: Face,
+    level: u8,
+    sky: bool,
+) -> u8 {
+    let from_shape = from.and_then(|from| from.occlusion.as_ref());
+    let occluded = match (from_shape, to.occlusion.as_ref()) {
+        (None, None) => false,
+        (from_shape, to_shape) => {
+            let empty = VoxelShape::empty();
+            from_shape
+                .unwrap_or(&empty)
+                .face_occludes(to_shape.unwrap_or(&empty), face)
+        }
+    };
+    if occluded {
+        0
+    } else if sky && level == MAX_LIGHT && face == Face::Down && to.opacity == 0 {
+        MAX_LIGHT
+    } else {
+        level.saturating_sub(to.opacity.max(1))
+    }
+}
+
+// A box of block positions stored in x-fastest order.
+#[derive(Debug, Clone, Copy)]
+struct Grid {
+    min: [i32; 3],
+    size: [usize; 3],
+}
+
+impl Grid {
+    fn new(min: [i32; 3], size: [usize; 3]) -> Self {
+        Grid { min, size }
+    }
+
+    fn index(&self, position: [i32; 3]) -> Option<usize> {
+        let mut local = [0usize; 3];
+        for axis in 0..3 {
+            let offset = position[axis].checked_sub(self.min[axis])?;
+            if offset < 0 || offset as usize >= self.size[axis] {
+                return None;
+            }
+            local[axis] = offset as usize;
+        }
+        Some((local[1] * self.size[2] + local[2]) * self.size[0] + local[0])
+    }
+
+    fn position(&self, index: usize) -> [i32; 3] {
+        let x = index % self.size[0];
+        let z = index / self.size[0] % self.size[2];
+        let y = index / (self.size[0] * self.size[2]);
+        [
+            self.min[0] + x as i32,
+            self.min[1] + y as i32,
+            self.min[2] + z as i32,
+        ]
+    }
+
+    fn positions(&self) -> impl Iterator<Item = [i32; 3]> + '_ {
+        (0..self.size.iter().product::<usize>()).map(|index| self.position(index))
+    }
+}
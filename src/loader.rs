@@ -18,12 +18,23 @@ pub fn load_data_from_path<T: DeserializeOwned>(path: &Path) -> Result<T, McData
 
 /// Loads data by resolving the path using dataPaths.json first.
 ///
-/// Uses the major version string (e.g., "1.18") to look up the specific path suffix.
+/// Uses the dataPaths version key (see `paths::data_paths_key`) to look up the specific path suffix.
 pub fn load_data<T: DeserializeOwned>(
     edition: crate::version::Edition,
-    version: &str, // Major version string (e.g., "1.18")
+    version: &str, // dataPaths version key (e.g., "1.18.2")
     data_key: &str,
 ) -> Result<T, McDataError> {
     let path = crate::paths::get_full_data_path(edition, version, data_key)?;
     load_data_from_path(&path)
 }
+
+/// Loads a non-JSON data file (e.g., Bedrock's proto.yml) as raw text,
+/// resolving the path using dataPaths.json like `load_data`.
+pub fn load_text_data(
+    edition: crate::version::Edition,
+    version: &str,
+    data_key: &str,
+) -> Result<String, McDataError> {
+    let path = crate::paths::get_full_data_path(edition, version, data_key)?;
+    fs::read_to_string(&path).map_err(|e| McDataError::IoError { path, source: e })
+}
@@ -2,6 +2,7 @@ use crate::data_source;
 use crate::error::{Edition, McDataError};
 use crate::loader::load_data_from_path;
 use crate::structs::DataPaths;
+use crate::version::{self, Version};
 use once_cell::sync::OnceCell;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -52,6 +53,60 @@ fn get_data_paths() -> Result<Arc<DataPaths>, McDataError> {
     }
 }
 
+/// Selects the `dataPaths.json` version key holding the data for a resolved `Version`.
+///
+/// `dataPaths.json` is keyed by full version for most entries (e.g., "1.18.2", "1.16.201"),
+/// and only by major version for some older ones (e.g., "1.8"). The lookup order is:
+/// 1. The exact Minecraft version.
+/// 2. The major version.
+/// 3. The newest listed version of the same edition that is older than the requested one,
+///    mirroring how minecraft-data reuses the previous version's data for versions without their own.
+pub fn data_paths_key(version: &Version) -> Result<String, McDataError> {
+    let data_paths = get_data_paths()?;
+    let edition_paths = match version.edition {
+        Edition::Pc => &data_paths.pc,
+        Edition::Bedrock => &data_paths.bedrock,
+    };
+
+    for candidate in [&version.minecraft_version, &version.major_version] {
+        if edition_paths.contains_key(candidate.as_str()) {
+            return Ok(candidate.clone());
+        }
+    }
+
+    // Fall back to the newest listed version older than the requested one.
+    let version_data = version::get_version_data(version.edition)?;
+    let all_versions: Vec<&Version> = version_data.by_major_version.values().flatten().collect();
+    let fallback = edition_paths
+        .keys()
+        .filter_map(|key| {
+            all_versions
+                .iter()
+                .find(|v| &v.minecraft_version == key)
+                .map(|v| (key, *v))
+        })
+        .filter(|(_, v)| *v <= version)
+        .max_by(|(_, a), (_, b)| a.cmp(b))
+        .map(|(key, _)| key.clone());
+
+    match fallback {
+        Some(key) => {
+            log::debug!(
+                "No dataPaths entry for {} {}, using data from {}",
+                version.edition,
+                version.minecraft_version,
+                key
+            );
+            Ok(key)
+        }
+        None => Err(McDataError::DataPathNotFound {
+            mc_version: version.minecraft_version.clone(),
+            edition: version.edition,
+            data_key: "*".to_string(),
+        }),
+    }
+}
+
 /// Gets the relative path suffix (like "pc/1.18") for a given data key and version.
 ///
 /// This path is retrieved from the loaded dataPaths.json.
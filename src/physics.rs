@@ -0,0 +1,717 @@
+// Tick-by-tick movement simulation following vanilla's `EntityLivingBase.travel` / `Entity.move`
+// (as ported by prismarine-physics), driven by the indexed collision shapes.
+
+use crate::block_state::BlockState;
+use crate::cached_data::IndexedData;
+use crate::error::{Edition, McDataError};
+use crate::features::{self, RangeBound};
+use crate::shapes::{Aabb, VoxelShape};
+use crate::structs::{Block, Entity};
+use crate::world::World;
+use std::collections::HashMap;
+
+const PLAYER_WIDTH: f64 = 0.6;
+const PLAYER_HEIGHT: f64 = 1.8;
+
+// Movement changes between Java versions, as (name, oldest, newest) in the bound syntax of
+// `features.json`. The names follow prismarine-physics' own feature list; minecraft-data's
+// `features.json` does not define them, so the ranges ship with the crate.
+const MOVEMENT_CHANGES: &[(&str, &str, &str)] = &[
+    ("independentLiquidGravity", "1.8_major", "1.12_major"),
+    ("proportionalLiquidGravity", "1.13_major", "latest"),
+    ("velocityBlocksOnCollision", "1.8_major", "1.14_major"),
+    ("velocityBlocksOnTop", "1.15_major", "latest"),
+    ("climbUsingJump", "1.14_major", "latest"),
+    ("climbableTrapdoor", "1.9_major", "latest"),
+];
+
+/// Movement constants of a version, in blocks and ticks.
+///
+/// `PhysicsConstants::for_version` fills in vanilla values, using built-in version ranges for the
+/// ones that changed between versions. Fields are public so they can be tuned (e.g., for other
+/// entity types or server-side attribute modifiers).
+#[derive(Debug, Clone, PartialEq)]
+pub struct PhysicsConstants {
+    /// Downward acceleration per tick out of liquids.
+    pub gravity: f64,
+    /// Vertical velocity multiplier per tick out of liquids.
+    pub air_drag: f64,
+    /// Downward acceleration per tick in water.
+    pub water_gravity: f64,
+    /// Downward acceleration per tick in lava.
+    pub lava_gravity: f64,
+    /// Velocity multiplier per tick in water.
+    pub water_inertia: f64,
+    /// Velocity multiplier per tick in lava.
+    pub lava_inertia: f64,
+    /// Horizontal acceleration from input while in a liquid.
+    pub liquid_acceleration: f64,
+    /// Horizontal velocity multiplier per tick while airborne.
+    pub airborne_inertia: f64,
+    /// Horizontal acceleration from input while airborne.
+    pub airborne_acceleration: f64,
+    /// Slipperiness of blocks other than ice and slime.
+    pub default_slipperiness: f64,
+    /// The movement speed attribute of a player.
+    pub walk_speed: f64,
+    /// Movement speed multiplier while sprinting.
+    pub sprint_multiplier: f64,
+    /// Input multiplier while sneaking.
+    pub sneak_speed: f64,
+    /// Upward velocity of a jump.
+    pub jump_velocity: f64,
+    /// Horizontal boost of a sprint jump.
+    pub sprint_jump_boost: f64,
+    /// Upward acceleration per tick while holding jump in a liquid.
+    pub liquid_jump_acceleration: f64,
+    /// Ticks between automatic jumps while holding jump.
+    pub jump_cooldown: u32,
+    /// Highest ledge walked onto without jumping.
+    pub step_height: f64,
+    /// Velocities smaller than this are zeroed at the start of a tick.
+    pub negligible_velocity: f64,
+    /// Highest horizontal and falling speed on ladders and vines.
+    pub ladder_max_speed: f64,
+    /// Upward speed when climbing ladders and vines.
+    pub ladder_climb_speed: f64,
+    /// Upward velocity when swimming against a ledge at the liquid surface.
+    pub out_of_liquid_impulse: f64,
+    /// Horizontal velocity multiplier on soul sand.
+    pub soul_sand_speed: f64,
+    /// Horizontal velocity multiplier on honey blocks.
+    pub honey_block_speed: f64,
+    /// Jump velocity multiplier on honey blocks.
+    pub honey_block_jump_speed: f64,
+    /// Soul sand slows entities inside its (lowered) collision box (before 1.15).
+    pub velocity_blocks_on_collision: bool,
+    /// Soul sand and honey slow entities standing on them (1.15+).
+    pub velocity_blocks_on_top: bool,
+    /// Holding jump climbs ladders and vines (1.14+).
+    pub climb_using_jump: bool,
+    /// Open trapdoors above a ladder with the same facing are climbable (1.9+).
+    pub climbable_trapdoor: bool,
+}
+
+impl PhysicsConstants {
+    /// The vanilla constants of a version.
+    ///
+    /// # Errors
+    /// Returns `McDataError` if the version data of the edition cannot be loaded.
+    pub fn for_version(data: &IndexedData) -> Result<Self, McDataError> {
+        let mut supported = HashMap::new();
+        // The table covers Java Edition only; other editions get the oldest behaviour.
+        if data.version.edition == Edition::Pc {
+            for (name, min, max) in MOVEMENT_CHANGES {
+                let min = features::resolve_range_bound(Edition::Pc, min, RangeBound::Min)?;
+                let max = features::resolve_range_bound(Edition::Pc, max, RangeBound::Max)?;
+                supported.insert(*name, data.version >= min && data.version <= max);
+            }
+        }
+        Ok(Self::from_features(|name| {
+            supported.get(name).copied().unwrap_or(false)
+        }))
+    }
+
+    /// Builds the constants given which features a version supports.
+    pub(crate) fn from_features(supports: impl Fn(&str) -> bool) -> Self {
+        let gravity = 0.08;
+        let (water_gravity, lava_gravity) = if supports("independentLiquidGravity") {
+            (0.02, 0.02)
+        } else if supports("proportionalLiquidGravity") {
+            (gravity / 16.0, gravity / 4.0)
+        } else {
+            (0.005, 0.02)
+        };
+        PhysicsConstants {
+            gravity,
+            air_drag: 0.98,
+            water_gravity,
+            lava_gravity,
+            water_inertia: 0.8,
+            lava_inertia: 0.5,
+            liquid_acceleration: 0.02,
+            airborne_inertia: 0.91,
+            airborne_acceleration: 0.02,
+            default_slipperiness: 0.6,
+            walk_speed: 0.1,
+            sprint_multiplier: 1.3,
+            sneak_speed: 0.3,
+            jump_velocity: 0.42,
+            sprint_jump_boost: 0.2,
+            liquid_jump_acceleration: 0.04,
+            jump_cooldown: 10,
+            step_height: 0.6,
+            negligible_velocity: 0.003,
+            ladder_max_speed: 0.15,
+            ladder_climb_speed: 0.2,
+            out_of_liquid_impulse: 0.3,
+            soul_sand_speed: 0.4,
+            honey_block_speed: 0.4,
+            honey_block_jump_speed: 0.4,
+            velocity_blocks_on_collision: supports("velocityBlocksOnCollision"),
+            velocity_blocks_on_top: supports("velocityBlocksOnTop"),
+            climb_using_jump: supports("climbUsingJump"),
+            climbable_trapdoor: supports("climbableTrapdoor"),
+        }
+    }
+}
+
+/// The movement input of an entity for one tick.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Controls {
+    pub forward: bool,
+    pub back: bool,
+    pub left: bool,
+    pub right: bool,
+    pub jump: bool,
+    pub sprint: bool,
+    pub sneak: bool,
+}
+
+/// The movement state of an entity, updated in place by `Physics::simulate`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EntityState {
+    /// Position of the bottom center of the bounding box.
+    pub position: [f64; 3],
+    /// Velocity in blocks per tick.
+    pub velocity: [f64; 3],
+    /// Yaw in degrees, as in vanilla: 0 faces south (+Z) and 90 faces west (-X).
+    pub yaw: f64,
+    /// Width of the bounding box.
+    pub width: f64,
+    /// Height of the bounding box.
+    pub height: f64,
+    /// The movement input for the next tick.
+    pub controls: Controls,
+    pub on_ground: bool,
+    pub in_water: bool,
+    pub in_lava: bool,
+    pub on_ladder: bool,
+    /// Set after touching a cobweb; slows the next tick's movement.
+    pub in_web: bool,
+    pub collided_horizontally: bool,
+    pub collided_vertically: bool,
+    /// Ticks left before holding jump jumps again.
+    pub jump_ticks: u32,
+}
+
+impl EntityState {
+    /// A player standing still at a position.
+    pub fn player(position: [f64; 3]) -> Self {
+        EntityState {
+            position,
+            velocity: [0.0; 3],
+            yaw: 0.0,
+            width: PLAYER_WIDTH,
+            height: PLAYER_HEIGHT,
+            controls: Controls::default(),
+            on_ground: false,
+            in_water: false,
+            in_lava: false,
+            on_ladder: false,
+            in_web: false,
+            collided_horizontally: false,
+            collided_vertically: false,
+            jump_ticks: 0,
+        }
+    }
+
+    /// An entity standing still at a position, sized by `Entity.width` and `height` (player
+    /// dimensions if the data has none).
+    pub fn for_entity(entity: &Entity, position: [f64; 3]) -> Self {
+        EntityState {
+            width: entity.width.map_or(PLAYER_WIDTH, f64::from),
+            height: entity.height.map_or(PLAYER_HEIGHT, f64::from),
+            ..EntityState::player(position)
+        }
+    }
+
+    /// The bounding box at the current position.
+    pub fn bounding_box(&self) -> Aabb {
+        bounding_box_at(self.position, self.width, self.height)
+    }
+}
+
+fn bounding_box_at(position: [f64; 3], width: f64, height: f64) -> Aabb {
+    let half = width / 2.0;
+    let [x, y, z] = position;
+    Aabb::new(x - half, y, z - half, x + half, y + height, z + half)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Fluid {
+    Water,
+    Lava,
+}
+
+/// Simulates entity movement one tick at a time against a caller-supplied `World`.
+///
+/// Covers walking, sprinting and sneaking (which stops at edges), jumping, gravity and drag,
+/// block slipperiness, stepping up ledges, ladders and vines, cobwebs, soul sand, honey,
+/// slime bounces, and swimming in water and lava. Liquid currents, bubble columns, potion
+/// effects and elytra flight are not simulated.
+///
+/// ```no_run
+/// use mcdata_rs::{mc_data, EntityState, Physics};
+///
+/// let data = mc_data("1.18.2")?;
+/// let physics = Physics::new(&data)?;
+/// let stone = data.blocks_by_name["stone"].default_state;
+/// let world = |_x: i32, y: i32, _z: i32| Some(if y < 64 { stone } else { 0 });
+///
+/// let mut player = EntityState::player([0.5, 70.0, 0.5]);
+/// while !player.on_ground {
+///     physics.simulate(&world, &mut player);
+/// }
+/// assert_eq!(player.position[1], 64.0);
+/// # Ok::<(), mcdata_rs::McDataError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct Physics<'a> {
+    blocks: &'a HashMap<u32, Block>,
+    shapes: &'a HashMap<u32, Vec<[f64; 6]>>,
+    constants: PhysicsConstants,
+}
+
+impl<'a> Physics<'a> {
+    /// Creates a simulator with the vanilla constants of the data's version.
+    ///
+    /// # Errors
+    /// Returns `McDataError` if feature data cannot be loaded or evaluated.
+    pub fn new(data: &'a IndexedData) -> Result<Self, McDataError> {
+        Ok(Physics::from_parts(
+            &data.blocks_by_state_id,
+            &data.block_shapes_by_state_id,
+            PhysicsConstants::for_version(data)?,
+        ))
+    }
+
+    pub(crate) fn from_parts(
+        blocks: &'a HashMap<u32, Block>,
+        shapes: &'a HashMap<u32, Vec<[f64; 6]>>,
+        constants: PhysicsConstants,
+    ) -> Self {
+        Physics {
+            blocks,
+            shapes,
+            constants,
+        }
+    }
+
+    /// Replaces the movement constants.
+    pub fn with_constants(mut self, constants: PhysicsConstants) -> Self {
+        self.constants = constants;
+        self
+    }
+
+    /// The movement constants in use.
+    pub fn constants(&self) -> &PhysicsConstants {
+        &self.constants
+    }
+
+    /// Advances an entity by one tick, applying its controls.
+    pub fn simulate(&self, world: &impl World, state: &mut EntityState) {
+        let c = &self.constants;
+        let bb = state.bounding_box();
+        state.in_water = self.in_fluid(world, &bb.inflate(-0.001, -0.401, -0.001), Fluid::Water);
+        state.in_lava = self.in_fluid(world, &bb.inflate(-0.1, -0.4, -0.1), Fluid::Lava);
+        state.on_ladder = self.is_on_ladder(world, state.position);
+
+        for v in state.velocity.iter_mut() {
+            if v.abs() < c.negligible_velocity {
+                *v = 0.0;
+            }
+        }
+
+        if state.controls.jump {
+            state.jump_ticks = state.jump_ticks.saturating_sub(1);
+            if state.in_water || state.in_lava {
+                state.velocity[1] += c.liquid_jump_acceleration;
+            } else if state.on_ground && state.jump_ticks == 0 {
+                let [x, y, z] = state.position;
+                let on_honey =
+                    self.block_name_at(world, x, y - 0.5000001, z) == Some("honey_block");
+                state.velocity[1] = c.jump_velocity
+                    * if on_honey {
+                        c.honey_block_jump_speed
+                    } else {
+                        1.0
+                    };
+                if state.controls.sprint {
+                    let yaw = state.yaw.to_radians();
+                    state.velocity[0] -= yaw.sin() * c.sprint_jump_boost;
+                    state.velocity[2] += yaw.cos() * c.sprint_jump_boost;
+                }
+                state.jump_ticks = c.jump_cooldown;
+            }
+        } else {
+            state.jump_ticks = 0;
+        }
+
+        let axis = |positive: bool, negative: bool| (positive as i32 - negative as i32) as f64;
+        let mut strafe = axis(state.controls.left, state.controls.right) * 0.98;
+        let mut forward = axis(state.controls.forward, state.controls.back) * 0.98;
+        if state.controls.sneak {
+            strafe *= c.sneak_speed;
+            forward *= c.sneak_speed;
+        }
+        self.travel(world, state, strafe, forward);
+    }
+
+    // Applies input, moves the entity and then applies gravity and drag.
+    fn travel(&self, world: &impl World, state: &mut EntityState, strafe: f64, forward: f64) {
+        let c = &self.constants;
+        if state.in_water || state.in_lava {
+            let last_y = state.position[1];
+            let inertia = if state.in_water {
+                c.water_inertia
+            } else {
+                c.lava_inertia
+            };
+            apply_heading(state, strafe, forward, c.liquid_acceleration);
+            let [dx, dy, dz] = state.velocity;
+            self.move_entity(world, state, dx, dy, dz);
+            state.velocity[1] *= inertia;
+            state.velocity[1] -= if state.in_water {
+                c.water_gravity
+            } else {
+                c.lava_gravity
+            };
+            state.velocity[0] *= inertia;
+            state.velocity[2] *= inertia;
+
+            // Swimming against a ledge at the surface hops out of the liquid.
+            let [vx, vy, vz] = state.velocity;
+            let hop = state
+                .bounding_box()
+                .offset(vx, vy + 0.6 - state.position[1] + last_y, vz);
+            if state.collided_horizontally && self.is_free(world, &hop) {
+                state.velocity[1] = c.out_of_liquid_impulse;
+            }
+            return;
+        }
+
+        let speed = c.walk_speed
+            * if state.controls.sprint {
+                c.sprint_multiplier
+            } else {
+                1.0
+            };
+        let mut inertia = c.airborne_inertia;
+        let mut acceleration = c.airborne_acceleration * speed / c.walk_speed;
+        if state.on_ground {
+            let [x, y, z] = state.position;
+            let slipperiness = self
+                .block_name_at(world, x, y - 1.0, z)
+                .map_or(c.default_slipperiness, |name| {
+                    slipperiness(name).unwrap_or(c.default_slipperiness)
+                });
+            inertia = slipperiness * c.airborne_inertia;
+            acceleration = speed * (0.1627714 / (inertia * inertia * inertia));
+        }
+        apply_heading(state, strafe, forward, acceleration);
+
+        if state.on_ladder {
+            let max = c.ladder_max_speed;
+            state.velocity[0] = state.velocity[0].clamp(-max, max);
+            state.velocity[2] = state.velocity[2].clamp(-max, max);
+            let floor = if state.controls.sneak { 0.0 } else { -max };
+            state.velocity[1] = state.velocity[1].max(floor);
+        }
+
+        let [dx, dy, dz] = state.velocity;
+        self.move_entity(world, state, dx, dy, dz);
+
+        if state.on_ladder
+            && (state.collided_horizontally || (c.climb_using_jump && state.controls.jump))
+        {
+            state.velocity[1] = c.ladder_climb_speed;
+        }
+
+        state.velocity[1] -= c.gravity;
+        state.velocity[1] *= c.air_drag;
+        state.velocity[0] *= inertia;
+        state.velocity[2] *= inertia;
+    }
+
+    // Moves the entity by up to (dx, dy, dz), stopping at collisions, and updates the contact
+    // flags and velocity.
+    fn move_entity(
+        &self,
+        world: &impl World,
+        state: &mut EntityState,
+        mut dx: f64,
+        mut dy: f64,
+        mut dz: f64,
+    ) {
+        let c = &self.constants;
+        if state.in_web {
+            dx *= 0.25;
+            dy *= 0.05;
+            dz *= 0.25;
+            state.velocity = [0.0; 3];
+            state.in_web = false;
+        }
+
+        let mut bb = state.bounding_box();
+        let (mut old_dx, old_dy, mut old_dz) = (dx, dy, dz);
+
+        // Sneaking on the ground never walks off an edge.
+        if state.controls.sneak && state.on_ground {
+            let step = 0.05;
+            let shrink = |d: f64| {
+                if d.abs() < step {
+                    0.0
+                } else {
+                    d - step * d.signum()
+                }
+            };
+            while dx != 0.0 && self.is_unsupported(world, &bb.offset(dx, -1.0, 0.0)) {
+                dx = shrink(dx);
+                old_dx = dx;
+            }
+            while dz != 0.0 && self.is_unsupported(world, &bb.offset(0.0, -1.0, dz)) {
+                dz = shrink(dz);
+                old_dz = dz;
+            }
+            while dx != 0.0 && dz != 0.0 && self.is_unsupported(world, &bb.offset(dx, -1.0, dz)) {
+                dx = shrink(dx);
+                dz = shrink(dz);
+                old_dx = dx;
+                old_dz = dz;
+            }
+        }
+
+        let obstacles = self.collision_boxes(world, &bb.expand_towards(dx, dy, dz));
+        let start = bb;
+        dy = obstacles.calculate_y_offset(&bb, dy);
+        bb = bb.offset(0.0, dy, 0.0);
+        dx = obstacles.calculate_x_offset(&bb, dx);
+        bb = bb.offset(dx, 0.0, 0.0);
+        dz = obstacles.calculate_z_offset(&bb, dz);
+        bb = bb.offset(0.0, 0.0, dz);
+
+        // Blocked horizontally: try stepping up the ledge and keep whichever gets further.
+        let was_grounded = state.on_ground || (dy != old_dy && old_dy < 0.0);
+        if c.step_height > 0.0 && was_grounded && (dx != old_dx || dz != old_dz) {
+            let (collided, collided_bb) = ([dx, dy, dz], bb);
+            let step = c.step_height;
+            let obstacles =
+                self.collision_boxes(world, &start.expand_towards(old_dx, step, old_dz));
+
+            // First candidate: rise as far as the whole horizontal path allows.
+            let swept = start.expand_towards(old_dx, 0.0, old_dz);
+            let dy1 = obstacles.calculate_y_offset(&swept, step);
+            let mut bb1 = start.offset(0.0, dy1, 0.0);
+            let dx1 = obstacles.calculate_x_offset(&bb1, old_dx);
+            bb1 = bb1.offset(dx1, 0.0, 0.0);
+            let dz1 = obstacles.calculate_z_offset(&bb1, old_dz);
+            bb1 = bb1.offset(0.0, 0.0, dz1);
+
+            // Second candidate: rise as far as the starting position allows.
+            let dy2 = obstacles.calculate_y_offset(&start, step);
+            let mut bb2 = start.offset(0.0, dy2, 0.0);
+            let dx2 = obstacles.calculate_x_offset(&bb2, old_dx);
+            bb2 = bb2.offset(dx2, 0.0, 0.0);
+            let dz2 = obstacles.calculate_z_offset(&bb2, old_dz);
+            bb2 = bb2.offset(0.0, 0.0, dz2);
+
+            (dx, dy, dz, bb) = if dx1 * dx1 + dz1 * dz1 > dx2 * dx2 + dz2 * dz2 {
+                (dx1, -dy1, dz1, bb1)
+            } else {
+                (dx2, -dy2, dz2, bb2)
+            };
+            // Settle back down onto the ledge.
+            dy = obstacles.calculate_y_offset(&bb, dy);
+            bb = bb.offset(0.0, dy, 0.0);
+
+            let [cx, _, cz] = collided;
+            if cx * cx + cz * cz >= dx * dx + dz * dz {
+                [dx, dy, dz] = collided;
+                bb = collided_bb;
+            }
+        }
+
+        state.position = [
+            (bb.min_x + bb.max_x) / 2.0,
+            bb.min_y,
+            (bb.min_z + bb.max_z) / 2.0,
+        ];
+        state.collided_horizontally = dx != old_dx || dz != old_dz;
+        state.collided_vertically = dy != old_dy;
+        state.on_ground = state.collided_vertically && old_dy < 0.0;
+
+        if dx != old_dx {
+            state.velocity[0] = 0.0;
+        }
+        if dz != old_dz {
+            state.velocity[2] = 0.0;
+        }
+        if dy != old_dy {
+            let [x, y, z] = state.position;
+            let on_slime = self.block_name_at(world, x, y - 0.2, z) == Some("slime_block");
+            state.velocity[1] = if on_slime && !state.controls.sneak {
+                -state.velocity[1]
+            } else {
+                0.0
+            };
+        }
+
+        // Blocks the entity touches after moving.
+        let touching = bb.inflate(-0.001, -0.001, -0.001);
+        for (x, y, z) in cells(&touching) {
+            match self.block_name(world, x, y, z) {
+                Some("cobweb" | "web") => state.in_web = true,
+                Some("soul_sand") if c.velocity_blocks_on_collision => {
+                    state.velocity[0] *= c.soul_sand_speed;
+                    state.velocity[2] *= c.soul_sand_speed;
+                }
+                _ => {}
+            }
+        }
+        if c.velocity_blocks_on_top {
+            let [x, y, z] = state.position;
+            let multiplier = match self.block_name_at(world, x, y - 0.5000001, z) {
+                Some("soul_sand") => c.soul_sand_speed,
+                Some("honey_block") => c.honey_block_speed,
+                _ => 1.0,
+            };
+            state.velocity[0] *= multiplier;
+            state.velocity[2] *= multiplier;
+        }
+    }
+
+    // The collision boxes of every loaded block that may intersect `query`, in world
+    // coordinates. Includes the layer below, since fences and walls reach 1.5 blocks up.
+    fn collision_boxes(&self, world: &impl World, query: &Aabb) -> VoxelShape {
+        let mut boxes = Vec::new();
+        for x in query.min_x.floor() as i32..=query.max_x.floor() as i32 {
+            for y in query.min_y.floor() as i32 - 1..=query.max_y.floor() as i32 {
+                for z in query.min_z.floor() as i32..=query.max_z.floor() as i32 {
+                    let Some(shape) = world.state_id(x, y, z).and_then(|id| self.shapes.get(&id))
+                    else {
+                        continue;
+                    };
+                    boxes.extend(
+                        VoxelShape::from_arrays(shape)
+                            .at_block(x, y, z)
+                            .boxes()
+                            .iter()
+                            .copied(),
+                    );
+                }
+            }
+        }
+        VoxelShape::from_boxes(boxes)
+    }
+
+    // Whether nothing below `bb` would hold up a sneaking entity.
+    fn is_unsupported(&self, world: &impl World, bb: &Aabb) -> bool {
+        !self.collision_boxes(world, bb).intersects(bb)
+    }
+
+    // Whether `bb` is clear of both collision boxes and liquid.
+    fn is_free(&self, world: &impl World, bb: &Aabb) -> bool {
+        !self.collision_boxes(world, bb).intersects(bb)
+            && !cells(bb).any(|(x, y, z)| self.fluid(world, x, y, z).is_some())
+    }
+
+    // Whether `bb` reaches into a fluid, counting only the filled part of partial blocks.
+    fn in_fluid(&self, world: &impl World, bb: &Aabb, fluid: Fluid) -> bool {
+        cells(bb).any(|(x, y, z)| match self.fluid(world, x, y, z) {
+            Some((found, height)) if found == fluid => {
+                fluid == Fluid::Lava || bb.max_y.ceil() >= y as f64 + 1.0 - height
+            }
+            _ => false,
+        })
+    }
+
+    // The fluid in a block and the fraction of the block it fills.
+    fn fluid(&self, world: &impl World, x: i32, y: i32, z: i32) -> Option<(Fluid, f64)> {
+        let state_id = world.state_id(x, y, z)?;
+        let block = self.blocks.get(&state_id)?;
+        let fluid = match block.name.as_str() {
+            "water" | "flowing_water" => Fluid::Water,
+            "lava" | "flowing_lava" => Fluid::Lava,
+            "seagrass" | "tall_seagrass" | "kelp" | "kelp_plant" | "bubble_column" => {
+                return Some((Fluid::Water, 1.0 / 9.0))
+            }
+            _ => {
+                let state = BlockState::decode(block, state_id)?;
+                return (state.property("waterlogged") == Some("true"))
+                    .then_some((Fluid::Water, 1.0 / 9.0));
+            }
+        };
+        // Levels 1-7 are flowing liquid getting shallower; 8+ is falling liquid, as high as a source.
+        let level = BlockState::decode(block, state_id)
+            .and_then(|state| state.property("level")?.parse::<u32>().ok())
+            .unwrap_or(0);
+        let depth = if level >= 8 { 0 } else { level };
+        Some((fluid, (depth + 1) as f64 / 9.0))
+    }
+
+    fn is_on_ladder(&self, world: &impl World, position: [f64; 3]) -> bool {
+        let [x, y, z] = position.map(|v| v.floor() as i32);
+        let Some(state) = self.state_at(world, x, y, z) else {
+            return false;
+        };
+        match state.name() {
+            "ladder" | "vine" => true,
+            name if self.constants.climbable_trapdoor && name.ends_with("_trapdoor") => {
+                state.property("open") == Some("true")
+                    && self.state_at(world, x, y - 1, z).is_some_and(|below| {
+                        below.name() == "ladder"
+                            && below.property("facing") == state.property("facing")
+                    })
+            }
+            _ => false,
+        }
+    }
+
+    fn state_at(&self, world: &impl World, x: i32, y: i32, z: i32) -> Option<BlockState> {
+        let state_id = world.state_id(x, y, z)?;
+        BlockState::decode(self.blocks.get(&state_id)?, state_id)
+    }
+
+    fn block_name(&self, world: &impl World, x: i32, y: i32, z: i32) -> Option<&'a str> {
+        world
+            .state_id(x, y, z)
+            .and_then(|id| self.blocks.get(&id))
+            .map(|block| block.name.as_str())
+    }
+
+    fn block_name_at(&self, world: &impl World, x: f64, y: f64, z: f64) -> Option<&'a str> {
+        self.block_name(world, x.floor() as i32, y.floor() as i32, z.floor() as i32)
+    }
+}
+
+// Adds input acceleration, relative to the entity's yaw, to its velocity.
+fn apply_heading(state: &mut EntityState, strafe: f64, forward: f64, acceleration: f64) {
+    let length = (strafe * strafe + forward * forward).sqrt();
+    if length < 0.01 {
+        return;
+    }
+    let scale = acceleration / length.max(1.0);
+    let (strafe, forward) = (strafe * scale, forward * scale);
+    let (sin, cos) = state.yaw.to_radians().sin_cos();
+    state.velocity[0] += strafe * cos - forward * sin;
+    state.velocity[2] += forward * cos + strafe * sin;
+}
+
+// Slipperiness of blocks that are not `PhysicsConstants::default_slipperiness`.
+fn slipperiness(block: &str) -> Option<f64> {
+    match block {
+        "ice" | "packed_ice" | "frosted_ice" => Some(0.98),
+        "blue_ice" => Some(0.989),
+        "slime_block" | "slime" => Some(0.8),
+        _ => None,
+    }
+}
+
+// The block positions whose cells overlap a box.
+fn cells(bb: &Aabb) -> impl Iterator<Item = (i32, i32, i32)> {
+    let (x0, x1) = (bb.min_x.floor() as i32, bb.max_x.ceil() as i32);
+    let (y0, y1) = (bb.min_y.floor() as i32, bb.max_y.ceil() as i32);
+    let (z0, z1) = (bb.min_z.floor() as i32, bb.max_z.ceil() as i32);
+    (x0..x1).flat_map(move |x| (y0..y1).flat_map(move |y| (z0..z1).map(move |z| (x, y, z))))
+}
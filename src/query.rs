@@ -0,0 +1,267 @@
+use crate::cached_data::IndexedData;
+use crate::error::{Edition, McDataError};
+use crate::features::{resolve_range_bound, RangeBound};
+use crate::version::{self, Version};
+use std::collections::{BTreeMap, HashSet};
+use std::str::FromStr;
+use std::sync::Arc;
+
+/// A comparison operator of a `VersionRange` comparator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Op {
+    Eq,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+}
+
+/// One resolved comparator, e.g. `>=1.8.8`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Comparator {
+    pub(crate) op: Op,
+    pub(crate) version: Version,
+}
+
+impl Comparator {
+    pub(crate) fn matches(&self, version: &Version) -> bool {
+        match self.op {
+            Op::Eq => *version == self.version,
+            Op::Gt => *version > self.version,
+            Op::Ge => *version >= self.version,
+            Op::Lt => *version < self.version,
+            Op::Le => *version <= self.version,
+        }
+    }
+}
+
+/// A range of versions of one edition, written like a semver `VersionReq`.
+///
+/// A range is a comma-separated list of comparators (`>=`, `>`, `<=`, `<`, `=`), all of which
+/// must match; a bare version means `=`, and `*` matches every version. Versions accept edition
+/// prefixes ("bedrock_1.20.0"), which must agree across comparators (PC if none is given).
+///
+/// A bound naming a major version ("1.21") covers the whole major: `<=1.21` includes 1.21.4,
+/// `>1.20` starts after the newest 1.20.x, and `=1.19` matches every 1.19.x.
+///
+/// Queries visit releases only, unless `include_snapshots` is set.
+///
+/// ```no_run
+/// use mcdata_rs::VersionRange;
+///
+/// let range: VersionRange = ">=1.8, <=1.21".parse()?;
+/// let stack_sizes = range.query(|data| {
+///     data.items_by_name.get("ender_pearl").map(|item| item.stack_size)
+/// })?;
+/// let with_warden = VersionRange::parse("*")?
+///     .versions_where(|data| data.entities_by_name.contains_key("warden"))?;
+/// # Ok::<(), mcdata_rs::McDataError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct VersionRange {
+    edition: Edition,
+    comparators: Vec<Comparator>,
+    include_snapshots: bool,
+}
+
+impl VersionRange {
+    /// Parses and resolves a version range.
+    ///
+    /// # Errors
+    /// Returns `McDataError::InvalidVersion` if the range is malformed or its comparators name
+    /// different editions, and `McDataError::UnknownVersion` if a version is not known.
+    pub fn parse(range: &str) -> Result<Self, McDataError> {
+        let (edition, parsed) = parse_comparators(range)?;
+        let edition = edition.unwrap_or(Edition::Pc);
+        let version_data = version::get_version_data(edition)?;
+
+        let mut comparators = Vec::with_capacity(parsed.len());
+        for (op, version_str) in parsed {
+            if version_data.by_major_version.contains_key(version_str) {
+                // A major bound covers every version of that major.
+                let bound =
+                    |which| resolve_range_bound(edition, &format!("{}_major", version_str), which);
+                let (oldest, newest) = (bound(RangeBound::Min)?, bound(RangeBound::Max)?);
+                match op {
+                    Op::Eq => {
+                        comparators.push(Comparator {
+                            op: Op::Ge,
+                            version: oldest,
+                        });
+                        comparators.push(Comparator {
+                            op: Op::Le,
+                            version: newest,
+                        });
+                    }
+                    Op::Ge | Op::Lt => comparators.push(Comparator {
+                        op,
+                        version: oldest,
+                    }),
+                    Op::Gt | Op::Le => comparators.push(Comparator {
+                        op,
+                        version: newest,
+                    }),
+                }
+            } else {
+                let prefixed = format!("{}_{}", edition.path_prefix(), version_str);
+                let version = version::resolve_version(&prefixed)?;
+                comparators.push(Comparator { op, version });
+            }
+        }
+        Ok(VersionRange {
+            edition,
+            comparators,
+            include_snapshots: false,
+        })
+    }
+
+    /// Whether snapshots and pre-releases are visited by queries. Off by default.
+    pub fn include_snapshots(mut self, include: bool) -> Self {
+        self.include_snapshots = include;
+        self
+    }
+
+    /// The edition of the range.
+    pub fn edition(&self) -> Edition {
+        self.edition
+    }
+
+    /// Whether a version lies within the range (regardless of its release type).
+    pub fn contains(&self, version: &Version) -> bool {
+        version.edition == self.edition && self.comparators.iter().all(|c| c.matches(version))
+    }
+
+    /// The known versions in the range, oldest first.
+    ///
+    /// # Errors
+    /// Returns `McDataError` if the version data of the edition cannot be loaded.
+    pub fn versions(&self) -> Result<Vec<Version>, McDataError> {
+        let version_data = version::get_version_data(self.edition)?;
+        let mut seen = HashSet::new();
+        let mut versions: Vec<Version> = version_data
+            .by_minecraft_version
+            .values()
+            .filter(|v| self.include_snapshots || v.release_type == "release")
+            .filter(|v| self.contains(v))
+            .filter(|v| seen.insert(v.minecraft_version.clone()))
+            .cloned()
+            .collect();
+        versions.sort();
+        Ok(versions)
+    }
+
+    /// Runs `f` over the data of every version in the range, oldest first, collecting the
+    /// results by version.
+    ///
+    /// Data already loaded by `mc_data` is reused. Other versions are loaded one at a time and
+    /// dropped after `f` returns, so the whole range is never held in memory at once.
+    ///
+    /// # Errors
+    /// Returns the first `McDataError` raised while loading a version.
+    pub fn query<T, F>(&self, mut f: F) -> Result<BTreeMap<Version, T>, McDataError>
+    where
+        F: FnMut(&IndexedData) -> T,
+    {
+        self.try_query(|data| Ok(f(data)))
+    }
+
+    /// Like `query`, but stops at the first error returned by `f`.
+    ///
+    /// # Errors
+    /// Returns the first `McDataError` raised while loading a version or returned by `f`.
+    pub fn try_query<T, F>(&self, mut f: F) -> Result<BTreeMap<Version, T>, McDataError>
+    where
+        F: FnMut(&IndexedData) -> Result<T, McDataError>,
+    {
+        let mut results = BTreeMap::new();
+        for version in self.versions()? {
+            let data = load_transient(&version)?;
+            results.insert(version, f(&data)?);
+        }
+        Ok(results)
+    }
+
+    /// The versions in the range whose data satisfies `predicate`, oldest first.
+    ///
+    /// # Errors
+    /// Returns the first `McDataError` raised while loading a version.
+    pub fn versions_where<F>(&self, mut predicate: F) -> Result<Vec<Version>, McDataError>
+    where
+        F: FnMut(&IndexedData) -> bool,
+    {
+        Ok(self
+            .query(|data| predicate(data))?
+            .into_iter()
+            .filter(|(_, matched)| *matched)
+            .map(|(version, _)| version)
+            .collect())
+    }
+}
+
+impl FromStr for VersionRange {
+    type Err = McDataError;
+
+    fn from_str(range: &str) -> Result<Self, Self::Err> {
+        VersionRange::parse(range)
+    }
+}
+
+// Returns cached data for a version, or loads it without adding it to the cache.
+fn load_transient(version: &Version) -> Result<Arc<IndexedData>, McDataError> {
+    if let Some(data) = crate::cached_entry(version)? {
+        return Ok(data);
+    }
+    log::debug!(
+        "Loading {} {} for a range query",
+        version.edition,
+        version.minecraft_version
+    );
+    Ok(Arc::new(IndexedData::load(version.clone())?))
+}
+
+// A parsed comparator whose version is not resolved yet.
+type RawComparator<'a> = (Op, &'a str);
+
+/// Splits a range into comparators, returning the edition named by prefixes (if any).
+pub(crate) fn parse_comparators(
+    range: &str,
+) -> Result<(Option<Edition>, Vec<RawComparator<'_>>), McDataError> {
+    let invalid = |reason: &str| {
+        McDataError::InvalidVersion(format!("Invalid version range '{}': {}", range, reason))
+    };
+    let mut edition = None;
+    let mut comparators = Vec::new();
+    for part in range.split(',').map(str::trim) {
+        if part.is_empty() {
+            return Err(invalid("empty comparator"));
+        }
+        if part == "*" {
+            continue;
+        }
+        let (op, rest) = [
+            (">=", Op::Ge),
+            ("<=", Op::Le),
+            (">", Op::Gt),
+            ("<", Op::Lt),
+            ("=", Op::Eq),
+        ]
+        .iter()
+        .find_map(|(symbol, op)| part.strip_prefix(symbol).map(|rest| (*op, rest)))
+        .unwrap_or((Op::Eq, part));
+
+        let (prefix, version_str) = version::split_edition_prefix(rest.trim());
+        if version_str.is_empty() || version_str.contains(char::is_whitespace) {
+            return Err(invalid(&format!("malformed comparator '{}'", part)));
+        }
+        if let Some(prefix) = prefix {
+            match edition {
+                Some(existing) if existing != prefix => {
+                    return Err(invalid("comparators name different editions"))
+                }
+                _ => edition = Some(prefix),
+            }
+        }
+        comparators.push((op, version_str));
+    }
+    Ok((edition, comparators))
+}
@@ -0,0 +1,425 @@
+use crate::block_state::StateLayout;
+use crate::cached_data::IndexedData;
+use crate::diff::{diff_category, DiffItem, Rename};
+use crate::error::McDataError;
+use crate::structs::Block;
+use std::collections::HashMap;
+
+/// The result of translating one ID to another version.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IdMapping {
+    /// An entry with the same name (and, for block states, the same property values).
+    Exact(u32),
+    /// An entry matched through a detected rename, or a block state whose properties
+    /// only partially match (unmatched properties take the target block's default values).
+    Approximate(u32),
+    /// A replacement supplied by the caller with `Remapper::fallback`.
+    Fallback(u32),
+    /// The entry has no counterpart in the other version.
+    Missing,
+}
+
+impl IdMapping {
+    /// The target ID, or `None` if the entry is missing.
+    pub fn id(&self) -> Option<u32> {
+        match *self {
+            IdMapping::Exact(id) | IdMapping::Approximate(id) | IdMapping::Fallback(id) => Some(id),
+            IdMapping::Missing => None,
+        }
+    }
+}
+
+/// The data categories a `Remapper` translates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RemapCategory {
+    /// Block state IDs (fallbacks are given by block name).
+    Blocks,
+    Items,
+    Entities,
+    Biomes,
+    Sounds,
+    Particles,
+}
+
+impl RemapCategory {
+    fn entry_kind(&self) -> &'static str {
+        match self {
+            RemapCategory::Blocks => "block",
+            RemapCategory::Items => "item",
+            RemapCategory::Entities => "entity",
+            RemapCategory::Biomes => "biome",
+            RemapCategory::Sounds => "sound",
+            RemapCategory::Particles => "particle",
+        }
+    }
+}
+
+/// Bidirectional ID translation for one data category.
+///
+/// Every ID of the source version has a forward entry and every ID of the target version a
+/// backward entry; entries without a counterpart are recorded as `IdMapping::Missing`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IdMap {
+    forward: HashMap<u32, IdMapping>,
+    backward: HashMap<u32, IdMapping>,
+}
+
+impl IdMap {
+    /// Translates an ID of the source version to the target version.
+    /// Returns `None` if the ID does not exist in the source version.
+    pub fn forward(&self, id: u32) -> Option<IdMapping> {
+        self.forward.get(&id).copied()
+    }
+
+    /// Translates an ID of the target version back to the source version.
+    /// Returns `None` if the ID does not exist in the target version.
+    pub fn backward(&self, id: u32) -> Option<IdMapping> {
+        self.backward.get(&id).copied()
+    }
+
+    /// Source IDs without a forward counterpart, in ascending order.
+    pub fn missing_forward(&self) -> Vec<u32> {
+        missing_ids(&self.forward)
+    }
+
+    /// Target IDs without a backward counterpart, in ascending order.
+    pub fn missing_backward(&self) -> Vec<u32> {
+        missing_ids(&self.backward)
+    }
+}
+
+fn missing_ids(map: &HashMap<u32, IdMapping>) -> Vec<u32> {
+    let mut ids: Vec<u32> = map
+        .iter()
+        .filter(|(_, mapping)| **mapping == IdMapping::Missing)
+        .map(|(id, _)| *id)
+        .collect();
+    ids.sort_unstable();
+    ids
+}
+
+/// Translates numeric IDs between two versions.
+///
+/// Entries are matched by name, falling back to the rename detection of `diff`. Block states
+/// are matched within the corresponding block by their property values; properties that only
+/// exist in the target version take the target block's default values.
+///
+/// ```no_run
+/// use mcdata_rs::{mc_data, RemapCategory, Remapper};
+///
+/// let (new, old) = (mc_data("1.19")?, mc_data("1.18.2")?);
+/// let remapper = Remapper::new(&new, &old)
+///     .fallback(RemapCategory::Blocks, "mangrove_log", "oak_log")?;
+/// let stone = new.blocks_by_name["stone"].default_state;
+/// assert!(remapper.block_states.forward(stone).and_then(|m| m.id()).is_some());
+/// # Ok::<(), mcdata_rs::McDataError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct Remapper {
+    from: IndexedData,
+    to: IndexedData,
+    pub block_states: IdMap,
+    pub items: IdMap,
+    pub entities: IdMap,
+    pub biomes: IdMap,
+    pub sounds: IdMap,
+    pub particles: IdMap,
+}
+
+impl Remapper {
+    /// Builds the ID maps between two versions.
+    pub fn new(from: &IndexedData, to: &IndexedData) -> Self {
+        log::debug!(
+            "Building ID remapping from {} {} to {} {}",
+            from.version.edition,
+            from.version.minecraft_version,
+            to.version.edition,
+            to.version.minecraft_version
+        );
+        Remapper {
+            block_states: map_block_states(&from.blocks_by_id, &to.blocks_by_id),
+            items: map_entries(&from.items_array, &to.items_array),
+            entities: map_entries(&from.entities_array, &to.entities_array),
+            biomes: map_entries(&from.biomes_array, &to.biomes_array),
+            sounds: map_entries(&from.sounds_array, &to.sounds_array),
+            particles: map_entries(&from.particles_array, &to.particles_array),
+            from: from.clone(),
+            to: to.clone(),
+        }
+    }
+
+    /// Maps the source entry `removed` to the target entry `replacement` wherever it has no
+    /// forward counterpart. For blocks, each state of `removed` is mapped to the state of
+    /// `replacement` with the most matching property values.
+    ///
+    /// Fallbacks only apply to the forward direction; build a `Remapper` with the versions
+    /// swapped to supply fallbacks for entries added in the target version.
+    ///
+    /// # Errors
+    /// Returns `McDataError::UnknownEntry` if `removed` does not exist in the source version or
+    /// `replacement` does not exist in the target version.
+    pub fn fallback(
+        mut self,
+        category: RemapCategory,
+        removed: &str,
+        replacement: &str,
+    ) -> Result<Self, McDataError> {
+        let unknown = |data: &IndexedData, name: &str| McDataError::UnknownEntry {
+            kind: category.entry_kind(),
+            name: name.to_string(),
+            edition: data.version.edition,
+            version: data.version.minecraft_version.clone(),
+        };
+
+        let (old_id, new_id) = match category {
+            RemapCategory::Blocks => {
+                let old = self
+                    .from
+                    .blocks_by_name
+                    .get(removed)
+                    .ok_or_else(|| unknown(&self.from, removed))?;
+                let new = self
+                    .to
+                    .blocks_by_name
+                    .get(replacement)
+                    .ok_or_else(|| unknown(&self.to, replacement))?;
+                let mut states = HashMap::new();
+                map_block(old, new, MatchKind::Fallback, &mut states);
+                for (id, mapping) in states {
+                    fill_missing(&mut self.block_states, id, mapping);
+                }
+                return Ok(self);
+            }
+            RemapCategory::Items => (
+                find_id(&self.from.items_array, removed),
+                find_id(&self.to.items_array, replacement),
+            ),
+            RemapCategory::Entities => (
+                find_id(&self.from.entities_array, removed),
+                find_id(&self.to.entities_array, replacement),
+            ),
+            RemapCategory::Biomes => (
+                find_id(&self.from.biomes_array, removed),
+                find_id(&self.to.biomes_array, replacement),
+            ),
+            RemapCategory::Sounds => (
+                find_id(&self.from.sounds_array, removed),
+                find_id(&self.to.sounds_array, replacement),
+            ),
+            RemapCategory::Particles => (
+                find_id(&self.from.particles_array, removed),
+                find_id(&self.to.particles_array, replacement),
+            ),
+        };
+        let old_id = old_id.ok_or_else(|| unknown(&self.from, removed))?;
+        let new_id = new_id.ok_or_else(|| unknown(&self.to, replacement))?;
+        let map = self.ids_mut(category);
+        fill_missing(map, old_id, IdMapping::Fallback(new_id));
+        Ok(self)
+    }
+
+    /// The ID map of a category.
+    pub fn ids(&self, category: RemapCategory) -> &IdMap {
+        match category {
+            RemapCategory::Blocks => &self.block_states,
+            RemapCategory::Items => &self.items,
+            RemapCategory::Entities => &self.entities,
+            RemapCategory::Biomes => &self.biomes,
+            RemapCategory::Sounds => &self.sounds,
+            RemapCategory::Particles => &self.particles,
+        }
+    }
+
+    fn ids_mut(&mut self, category: RemapCategory) -> &mut IdMap {
+        match category {
+            RemapCategory::Blocks => &mut self.block_states,
+            RemapCategory::Items => &mut self.items,
+            RemapCategory::Entities => &mut self.entities,
+            RemapCategory::Biomes => &mut self.biomes,
+            RemapCategory::Sounds => &mut self.sounds,
+            RemapCategory::Particles => &mut self.particles,
+        }
+    }
+}
+
+// Fallbacks never override a mapping that was found by matching.
+fn fill_missing(map: &mut IdMap, id: u32, mapping: IdMapping) {
+    if let Some(entry) = map.forward.get_mut(&id) {
+        if *entry == IdMapping::Missing {
+            *entry = mapping;
+        }
+    }
+}
+
+fn find_id<T: DiffItem>(entries: &[T], name: &str) -> Option<u32> {
+    entries.iter().find(|e| e.name() == name).map(|e| e.id())
+}
+
+fn reversed(renames: &[Rename]) -> Vec<Rename> {
+    renames
+        .iter()
+        .map(|r| Rename {
+            from: r.to.clone(),
+            to: r.from.clone(),
+            from_id: r.to_id,
+            to_id: r.from_id,
+        })
+        .collect()
+}
+
+fn map_entries<T: DiffItem>(from: &[T], to: &[T]) -> IdMap {
+    let renamed = diff_category(from, to).renamed;
+    IdMap {
+        forward: match_entries(from, to, &renamed),
+        backward: match_entries(to, from, &reversed(&renamed)),
+    }
+}
+
+fn match_entries<T: DiffItem>(from: &[T], to: &[T], renamed: &[Rename]) -> HashMap<u32, IdMapping> {
+    let to_by_name: HashMap<&str, u32> = to.iter().map(|e| (e.name(), e.id())).collect();
+    let renamed: HashMap<&str, u32> = renamed.iter().map(|r| (r.from.as_str(), r.to_id)).collect();
+    from.iter()
+        .map(|entry| {
+            let mapping = if let Some(&id) = to_by_name.get(entry.name()) {
+                IdMapping::Exact(id)
+            } else if let Some(&id) = renamed.get(entry.name()) {
+                IdMapping::Approximate(id)
+            } else {
+                IdMapping::Missing
+            };
+            (entry.id(), mapping)
+        })
+        .collect()
+}
+
+/// Maps the block states of two versions. Takes the indexed blocks rather than
+/// `blocks_array`, since only indexing gives pre-1.13 blocks their `id << 4 | metadata`
+/// state ranges.
+pub(crate) fn map_block_states(from: &HashMap<u32, Block>, to: &HashMap<u32, Block>) -> IdMap {
+    let sorted = |blocks: &HashMap<u32, Block>| {
+        let mut blocks: Vec<Block> = blocks.values().cloned().collect();
+        blocks.sort_by_key(|block| block.id);
+        blocks
+    };
+    let (from, to) = (sorted(from), sorted(to));
+    let renamed = diff_category(&from, &to).renamed;
+    IdMap {
+        forward: match_block_states(&from, &to, &renamed),
+        backward: match_block_states(&to, &from, &reversed(&renamed)),
+    }
+}
+
+fn match_block_states(from: &[Block], to: &[Block], renamed: &[Rename]) -> HashMap<u32, IdMapping> {
+    let to_by_name: HashMap<&str, &Block> = to.iter().map(|b| (b.name.as_str(), b)).collect();
+    let renamed: HashMap<&str, &str> = renamed
+        .iter()
+        .map(|r| (r.from.as_str(), r.to.as_str()))
+        .collect();
+    let mut states = HashMap::new();
+    for old in from {
+        if let Some(new) = to_by_name.get(old.name.as_str()) {
+            map_block(old, new, MatchKind::Name, &mut states);
+        } else if let Some(new) = renamed
+            .get(old.name.as_str())
+            .and_then(|n| to_by_name.get(n))
+        {
+            map_block(old, new, MatchKind::Rename, &mut states);
+        } else {
+            for id in old.min_state_id..=old.max_state_id {
+                states.insert(id, IdMapping::Missing);
+            }
+        }
+    }
+    states
+}
+
+// How the target block of a state mapping was found.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum MatchKind {
+    Name,
+    Rename,
+    Fallback,
+}
+
+// A state's property values, in the block's property order.
+type StateProperties = Vec<(String, String)>;
+
+/// Maps every state of `old` to the state of `new` with the most matching property values.
+pub(crate) fn map_block(
+    old: &Block,
+    new: &Block,
+    kind: MatchKind,
+    out: &mut HashMap<u32, IdMapping>,
+) {
+    let wrap = |id: u32, exact: bool| match kind {
+        MatchKind::Fallback => IdMapping::Fallback(id),
+        MatchKind::Name if exact => IdMapping::Exact(id),
+        _ => IdMapping::Approximate(id),
+    };
+    let old_count = old.max_state_id - old.min_state_id + 1;
+    let new_count = new.max_state_id - new.min_state_id + 1;
+
+    // Blocks without state definitions (e.g., pre-1.13 metadata) can only be matched by offset.
+    if old.states.is_empty() || new.states.is_empty() {
+        for offset in 0..old_count {
+            let mapping = if old_count == new_count {
+                wrap(new.min_state_id + offset, true)
+            } else {
+                wrap(new.default_state, false)
+            };
+            out.insert(old.min_state_id + offset, mapping);
+        }
+        return;
+    }
+
+    let new_states = state_properties(new);
+    let by_properties: HashMap<&StateProperties, u32> = new_states
+        .iter()
+        .enumerate()
+        .map(|(offset, properties)| (properties, new.min_state_id + offset as u32))
+        .collect();
+    let default_offset = new.default_state.saturating_sub(new.min_state_id) as usize;
+    let new_default = new_states.get(default_offset).cloned().unwrap_or_default();
+
+    for (offset, properties) in state_properties(old).into_iter().enumerate() {
+        let state_id = old.min_state_id + offset as u32;
+        if let Some(&id) = by_properties.get(&properties) {
+            out.insert(state_id, wrap(id, true));
+            continue;
+        }
+        // Score each candidate by shared values, breaking ties towards the default state.
+        let score = |candidate: &StateProperties| {
+            let shared = candidate.iter().filter(|p| properties.contains(p)).count();
+            let default = candidate.iter().filter(|p| new_default.contains(p)).count();
+            (shared, default)
+        };
+        let best = new_states
+            .iter()
+            .enumerate()
+            .max_by_key(|(offset, candidate)| (score(candidate), std::cmp::Reverse(*offset)))
+            .map(|(offset, _)| new.min_state_id + offset as u32)
+            .unwrap_or(new.default_state);
+        out.insert(state_id, wrap(best, false));
+    }
+}
+
+/// Decodes the property values of every state of a block, indexed by offset from
+/// `min_state_id`.
+pub(crate) fn state_properties(block: &Block) -> Vec<StateProperties> {
+    let layout = StateLayout::of(block);
+    (block.min_state_id..=block.max_state_id)
+        .map(|state_id| {
+            layout
+                .decode(state_id)
+                .map(|indices| {
+                    layout
+                        .properties
+                        .iter()
+                        .zip(indices)
+                        .map(|((name, values), index)| (name.clone(), values[index].clone()))
+                        .collect()
+                })
+                .unwrap_or_default()
+        })
+        .collect()
+}
@@ -0,0 +1,529 @@
+// Typed collision geometry over the indexed block shapes (`[x1, y1, z1, x2, y2, z2]` boxes in
+// block-local coordinates, where a full block spans 0..1 on every axis).
+
+// Tolerance for comparing box coordinates, which upstream data gives as decimal fractions.
+const EPSILON: f64 = 1e-7;
+
+/// One face of a box or block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Face {
+    /// -Y
+    Down,
+    /// +Y
+    Up,
+    /// -Z
+    North,
+    /// +Z
+    South,
+    /// -X
+    West,
+    /// +X
+    East,
+}
+
+impl Face {
+    /// All faces, in the order used by vanilla's `Direction`.
+    pub const ALL: [Face; 6] = [
+        Face::Down,
+        Face::Up,
+        Face::North,
+        Face::South,
+        Face::West,
+        Face::East,
+    ];
+
+    /// The unit vector pointing out of the face.
+    pub fn normal(&self) -> [i32; 3] {
+        match self {
+            Face::Down => [0, -1, 0],
+            Face::Up => [0, 1, 0],
+            Face::North => [0, 0, -1],
+            Face::South => [0, 0, 1],
+            Face::West => [-1, 0, 0],
+            Face::East => [1, 0, 0],
+        }
+    }
+
+    /// The face on the opposite side.
+    pub fn opposite(&self) -> Face {
+        match self {
+            Face::Down => Face::Up,
+            Face::Up => Face::Down,
+            Face::North => Face::South,
+            Face::South => Face::North,
+            Face::West => Face::East,
+            Face::East => Face::West,
+        }
+    }
+
+    // The axis (0 = X, 1 = Y, 2 = Z) the face is perpendicular to, and whether it faces positive.
+    fn axis(&self) -> (usize, bool) {
+        match self {
+            Face::Down => (1, false),
+            Face::Up => (1, true),
+            Face::North => (2, false),
+            Face::South => (2, true),
+            Face::West => (0, false),
+            Face::East => (0, true),
+        }
+    }
+}
+
+/// An axis-aligned bounding box.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Aabb {
+    pub min_x: f64,
+    pub min_y: f64,
+    pub min_z: f64,
+    pub max_x: f64,
+    pub max_y: f64,
+    pub max_z: f64,
+}
+
+/// Where a ray hits a box or shape.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RayHit {
+    /// The point of impact.
+    pub point: [f64; 3],
+    /// The face that was hit.
+    pub face: Face,
+    /// Distance from the ray origin, in multiples of the direction vector's length.
+    pub distance: f64,
+}
+
+impl Aabb {
+    /// Creates a box from its corners; coordinates are reordered so that min ≤ max.
+    pub fn new(x1: f64, y1: f64, z1: f64, x2: f64, y2: f64, z2: f64) -> Self {
+        Aabb {
+            min_x: x1.min(x2),
+            min_y: y1.min(y2),
+            min_z: z1.min(z2),
+            max_x: x1.max(x2),
+            max_y: y1.max(y2),
+            max_z: z1.max(z2),
+        }
+    }
+
+    /// Creates a box from the `[x1, y1, z1, x2, y2, z2]` layout of `blockCollisionShapes.json`.
+    pub fn from_array(coords: [f64; 6]) -> Self {
+        let [x1, y1, z1, x2, y2, z2] = coords;
+        Aabb::new(x1, y1, z1, x2, y2, z2)
+    }
+
+    /// The box in the `[x1, y1, z1, x2, y2, z2]` layout.
+    pub fn to_array(&self) -> [f64; 6] {
+        [
+            self.min_x, self.min_y, self.min_z, self.max_x, self.max_y, self.max_z,
+        ]
+    }
+
+    /// The unit cube of a full block.
+    pub fn full_cube() -> Self {
+        Aabb::new(0.0, 0.0, 0.0, 1.0, 1.0, 1.0)
+    }
+
+    fn min(&self) -> [f64; 3] {
+        [self.min_x, self.min_y, self.min_z]
+    }
+
+    fn max(&self) -> [f64; 3] {
+        [self.max_x, self.max_y, self.max_z]
+    }
+
+    fn from_min_max(min: [f64; 3], max: [f64; 3]) -> Self {
+        Aabb::new(min[0], min[1], min[2], max[0], max[1], max[2])
+    }
+
+    /// The box moved by the given amounts.
+    pub fn offset(&self, dx: f64, dy: f64, dz: f64) -> Aabb {
+        Aabb {
+            min_x: self.min_x + dx,
+            min_y: self.min_y + dy,
+            min_z: self.min_z + dz,
+            max_x: self.max_x + dx,
+            max_y: self.max_y + dy,
+            max_z: self.max_z + dz,
+        }
+    }
+
+    /// The box grown by the given amounts on both sides of each axis (negative amounts shrink it).
+    pub fn inflate(&self, x: f64, y: f64, z: f64) -> Aabb {
+        Aabb::new(
+            self.min_x - x,
+            self.min_y - y,
+            self.min_z - z,
+            self.max_x + x,
+            self.max_y + y,
+            self.max_z + z,
+        )
+    }
+
+    /// The box stretched along a movement vector, covering everything it sweeps through.
+    pub fn expand_towards(&self, dx: f64, dy: f64, dz: f64) -> Aabb {
+        let mut expanded = *self;
+        for (delta, min, max) in [
+            (dx, &mut expanded.min_x, &mut expanded.max_x),
+            (dy, &mut expanded.min_y, &mut expanded.max_y),
+            (dz, &mut expanded.min_z, &mut expanded.max_z),
+        ] {
+            if delta < 0.0 {
+                *min += delta;
+            } else {
+                *max += delta;
+            }
+        }
+        expanded
+    }
+
+    /// The size along each axis.
+    pub fn size(&self) -> [f64; 3] {
+        [
+            self.max_x - self.min_x,
+            self.max_y - self.min_y,
+            self.max_z - self.min_z,
+        ]
+    }
+
+    /// The center point.
+    pub fn center(&self) -> [f64; 3] {
+        [
+            (self.min_x + self.max_x) / 2.0,
+            (self.min_y + self.max_y) / 2.0,
+            (self.min_z + self.max_z) / 2.0,
+        ]
+    }
+
+    /// Whether the box has no volume.
+    pub fn is_empty(&self) -> bool {
+        self.size().iter().any(|s| *s < EPSILON)
+    }
+
+    /// Whether the boxes overlap with positive volume (touching faces do not count).
+    pub fn intersects(&self, other: &Aabb) -> bool {
+        self.min_x < other.max_x
+            && self.max_x > other.min_x
+            && self.min_y < other.max_y
+            && self.max_y > other.min_y
+            && self.min_z < other.max_z
+            && self.max_z > other.min_z
+    }
+
+    /// Whether a point lies inside the box or on its boundary.
+    pub fn contains(&self, point: [f64; 3]) -> bool {
+        (0..3).all(|axis| point[axis] >= self.min()[axis] && point[axis] <= self.max()[axis])
+    }
+
+    /// The overlapping region, or `None` if the boxes do not overlap.
+    pub fn intersection(&self, other: &Aabb) -> Option<Aabb> {
+        self.intersects(other).then(|| {
+            Aabb::new(
+                self.min_x.max(other.min_x),
+                self.min_y.max(other.min_y),
+                self.min_z.max(other.min_z),
+                self.max_x.min(other.max_x),
+                self.max_y.min(other.max_y),
+                self.max_z.min(other.max_z),
+            )
+        })
+    }
+
+    /// The smallest box containing both boxes.
+    pub fn union(&self, other: &Aabb) -> Aabb {
+        Aabb::new(
+            self.min_x.min(other.min_x),
+            self.min_y.min(other.min_y),
+            self.min_z.min(other.min_z),
+            self.max_x.max(other.max_x),
+            self.max_y.max(other.max_y),
+            self.max_z.max(other.max_z),
+        )
+    }
+
+    /// Clips a movement of `moving` along X so that it does not enter this box, like vanilla's
+    /// `calculateXOffset`. Boxes that do not overlap on the other axes never clip.
+    pub fn calculate_x_offset(&self, moving: &Aabb, offset: f64) -> f64 {
+        self.calculate_offset(moving, offset, 0)
+    }
+
+    /// Clips a movement of `moving` along Y; see `calculate_x_offset`.
+    pub fn calculate_y_offset(&self, moving: &Aabb, offset: f64) -> f64 {
+        self.calculate_offset(moving, offset, 1)
+    }
+
+    /// Clips a movement of `moving` along Z; see `calculate_x_offset`.
+    pub fn calculate_z_offset(&self, moving: &Aabb, offset: f64) -> f64 {
+        self.calculate_offset(moving, offset, 2)
+    }
+
+    fn calculate_offset(&self, moving: &Aabb, offset: f64, axis: usize) -> f64 {
+        let (min, max, moving_min, moving_max) =
+            (self.min(), self.max(), moving.min(), moving.max());
+        let overlaps_others = (0..3)
+            .filter(|other| *other != axis)
+            .all(|other| moving_max[other] > min[other] && moving_min[other] < max[other]);
+        if !overlaps_others {
+            return offset;
+        }
+        if offset > 0.0 && moving_max[axis] <= min[axis] {
+            offset.min(min[axis] - moving_max[axis])
+        } else if offset < 0.0 && moving_min[axis] >= max[axis] {
+            offset.max(max[axis] - moving_min[axis])
+        } else {
+            offset
+        }
+    }
+
+    /// Casts a ray from `origin` along `direction`, up to `max_distance` multiples of the
+    /// direction vector. Rays starting inside the box do not hit it.
+    pub fn raycast(
+        &self,
+        origin: [f64; 3],
+        direction: [f64; 3],
+        max_distance: f64,
+    ) -> Option<RayHit> {
+        let (min, max) = (self.min(), self.max());
+        let mut t_enter = f64::NEG_INFINITY;
+        let mut t_exit = f64::INFINITY;
+        let mut face = None;
+        for axis in 0..3 {
+            if direction[axis].abs() < f64::EPSILON {
+                if origin[axis] < min[axis] || origin[axis] > max[axis] {
+                    return None;
+                }
+                continue;
+            }
+            let (near, far, near_face) = if direction[axis] > 0.0 {
+                (min[axis], max[axis], negative_face(axis))
+            } else {
+                (max[axis], min[axis], negative_face(axis).opposite())
+            };
+            let t_near = (near - origin[axis]) / direction[axis];
+            let t_far = (far - origin[axis]) / direction[axis];
+            if t_near > t_enter {
+                t_enter = t_near;
+                face = Some(near_face);
+            }
+            t_exit = t_exit.min(t_far);
+        }
+        if t_enter > t_exit || t_enter < 0.0 || t_enter > max_distance {
+            return None;
+        }
+        Some(RayHit {
+            point: [
+                origin[0] + direction[0] * t_enter,
+                origin[1] + direction[1] * t_enter,
+                origin[2] + direction[2] * t_enter,
+            ],
+            face: face?,
+            distance: t_enter,
+        })
+    }
+}
+
+// The face on the negative side of an axis.
+fn negative_face(axis: usize) -> Face {
+    match axis {
+        0 => Face::West,
+        1 => Face::Down,
+        _ => Face::North,
+    }
+}
+
+/// The collision shape of a block state: a union of boxes.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct VoxelShape {
+    boxes: Vec<Aabb>,
+}
+
+impl VoxelShape {
+    /// A shape without boxes (e.g., air).
+    pub fn empty() -> Self {
+        VoxelShape::default()
+    }
+
+    /// The shape of a full block.
+    pub fn full_cube() -> Self {
+        VoxelShape {
+            boxes: vec![Aabb::full_cube()],
+        }
+    }
+
+    /// Creates a shape from boxes in the `blockCollisionShapes.json` layout, as stored in
+    /// `IndexedData::block_shapes_by_state_id`. Boxes without volume are dropped.
+    pub fn from_arrays(boxes: &[[f64; 6]]) -> Self {
+        VoxelShape::from_boxes(boxes.iter().map(|coords| Aabb::from_array(*coords)))
+    }
+
+    /// Creates a shape from boxes. Boxes without volume are dropped.
+    pub fn from_boxes(boxes: impl IntoIterator<Item = Aabb>) -> Self {
+        VoxelShape {
+            boxes: boxes.into_iter().filter(|b| !b.is_empty()).collect(),
+        }
+    }
+
+    /// The boxes making up the shape.
+    pub fn boxes(&self) -> &[Aabb] {
+        &self.boxes
+    }
+
+    /// Whether the shape has no boxes.
+    pub fn is_empty(&self) -> bool {
+        self.boxes.is_empty()
+    }
+
+    /// The shape moved by the given amounts.
+    pub fn offset(&self, dx: f64, dy: f64, dz: f64) -> VoxelShape {
+        VoxelShape {
+            boxes: self.boxes.iter().map(|b| b.offset(dx, dy, dz)).collect(),
+        }
+    }
+
+    /// The shape placed at a block position, turning block-local into world coordinates.
+    pub fn at_block(&self, x: i32, y: i32, z: i32) -> VoxelShape {
+        self.offset(x as f64, y as f64, z as f64)
+    }
+
+    /// The smallest box containing the whole shape, or `None` if it is empty.
+    pub fn bounds(&self) -> Option<Aabb> {
+        self.boxes.iter().copied().reduce(|a, b| a.union(&b))
+    }
+
+    /// The union of two shapes.
+    pub fn union(&self, other: &VoxelShape) -> VoxelShape {
+        VoxelShape {
+            boxes: self.boxes.iter().chain(&other.boxes).copied().collect(),
+        }
+    }
+
+    /// The region covered by both shapes.
+    pub fn intersection(&self, other: &VoxelShape) -> VoxelShape {
+        VoxelShape::from_boxes(
+            self.boxes
+                .iter()
+                .flat_map(|a| other.boxes.iter().filter_map(|b| a.intersection(b))),
+        )
+    }
+
+    /// Whether any box of the shape overlaps `aabb`.
+    pub fn intersects(&self, aabb: &Aabb) -> bool {
+        self.boxes.iter().any(|b| b.intersects(aabb))
+    }
+
+    /// Clips a movement of `moving` along X against every box; see `Aabb::calculate_x_offset`.
+    pub fn calculate_x_offset(&self, moving: &Aabb, offset: f64) -> f64 {
+        self.boxes
+            .iter()
+            .fold(offset, |offset, b| b.calculate_x_offset(moving, offset))
+    }
+
+    /// Clips a movement of `moving` along Y against every box.
+    pub fn calculate_y_offset(&self, moving: &Aabb, offset: f64) -> f64 {
+        self.boxes
+            .iter()
+            .fold(offset, |offset, b| b.calculate_y_offset(moving, offset))
+    }
+
+    /// Clips a movement of `moving` along Z against every box.
+    pub fn calculate_z_offset(&self, moving: &Aabb, offset: f64) -> f64 {
+        self.boxes
+            .iter()
+            .fold(offset, |offset, b| b.calculate_z_offset(moving, offset))
+    }
+
+    /// The nearest hit of a ray against any box of the shape; see `Aabb::raycast`.
+    pub fn raycast(
+        &self,
+        origin: [f64; 3],
+        direction: [f64; 3],
+        max_distance: f64,
+    ) -> Option<RayHit> {
+        self.boxes
+            .iter()
+            .filter_map(|b| b.raycast(origin, direction, max_distance))
+            .min_by(|a, b| a.distance.total_cmp(&b.distance))
+    }
+
+    /// Whether the shape fills its whole block (block-local coordinates).
+    pub fn is_full_cube(&self) -> bool {
+        covers(&self.boxes, Aabb::full_cube(), [0, 1, 2])
+    }
+
+    /// Whether the shape covers a whole face of its block, as needed to support e.g. torches
+    /// or to hide the neighbouring face (block-local coordinates).
+    pub fn is_face_solid(&self, face: Face) -> bool {
+        covers(&self.face_boxes(face), Aabb::full_cube(), face_axes(face))
+    }
+
+    /// Whether this shape's `face` and the neighbouring shape's opposite face together cover
+    /// the face between the two blocks, e.g. a bottom slab below a top slab. Vanilla uses this
+    /// to stop light between partial blocks (block-local coordinates).
+    pub fn face_occludes(&self, neighbour: &VoxelShape, face: Face) -> bool {
+        let mut boxes = self.face_boxes(face);
+        boxes.extend(neighbour.face_boxes(face.opposite()));
+        covers(&boxes, Aabb::full_cube(), face_axes(face))
+    }
+
+    // The boxes touching a face plane, stretched across the block along the face normal.
+    fn face_boxes(&self, face: Face) -> Vec<Aabb> {
+        let (axis, positive) = face.axis();
+        let plane = if positive { 1.0 } else { 0.0 };
+        self.boxes
+            .iter()
+            .filter(|b| {
+                let (min, max) = (b.min()[axis], b.max()[axis]);
+                min <= plane + EPSILON && max >= plane - EPSILON
+            })
+            .map(|b| {
+                let (mut min, mut max) = (b.min(), b.max());
+                min[axis] = 0.0;
+                max[axis] = 1.0;
+                Aabb::from_min_max(min, max)
+            })
+            .collect()
+    }
+}
+
+// The axes along a face, followed by its normal axis.
+fn face_axes(face: Face) -> [usize; 3] {
+    let (axis, _) = face.axis();
+    let others: Vec<usize> = (0..3).filter(|a| *a != axis).collect();
+    [others[0], others[1], axis]
+}
+
+impl From<&[[f64; 6]]> for VoxelShape {
+    fn from(boxes: &[[f64; 6]]) -> Self {
+        VoxelShape::from_arrays(boxes)
+    }
+}
+
+// Whether `boxes` together cover `target`, checked on the grid of all box edges along `axes`.
+fn covers(boxes: &[Aabb], target: Aabb, axes: [usize; 3]) -> bool {
+    let (target_min, target_max) = (target.min(), target.max());
+    let breakpoints = |axis: usize| {
+        let mut points: Vec<f64> = boxes
+            .iter()
+            .flat_map(|b| [b.min()[axis], b.max()[axis]])
+            .chain([target_min[axis], target_max[axis]])
+            .filter(|p| *p >= target_min[axis] - EPSILON && *p <= target_max[axis] + EPSILON)
+            .collect();
+        points.sort_by(f64::total_cmp);
+        points.dedup_by(|a, b| (*a - *b).abs() < EPSILON);
+        points
+    };
+    let grid = axes.map(breakpoints);
+    // Each grid cell is either fully inside or fully outside every box; test its center.
+    for x in grid[0].windows(2) {
+        for y in grid[1].windows(2) {
+            for z in grid[2].windows(2) {
+                let mut center = [0.0; 3];
+                center[axes[0]] = (x[0] + x[1]) / 2.0;
+                center[axes[1]] = (y[0] + y[1]) / 2.0;
+                center[axes[2]] = (z[0] + z[1]) / 2.0;
+                if !boxes.iter().any(|b| b.contains(center)) {
+                    return false;
+                }
+            }
+        }
+    }
+    true
+}
@@ -1,4 +1,5 @@
-use serde::Deserialize;
+use serde::{Deserialize, Deserializer};
+use serde_json::Value;
 use std::collections::HashMap;
 
 // Structs related to version information from protocolVersions.json.
@@ -38,9 +39,13 @@ pub struct Block {
     pub name: String,
     pub display_name: String,
     pub hardness: Option<f32>,
+    #[serde(default)]
     pub resistance: f32,
+    #[serde(default = "default_stack_size")]
     pub stack_size: u32,
+    #[serde(default)]
     pub diggable: bool,
+    #[serde(default = "default_bounding_box")]
     pub bounding_box: String, // Typically "block" or "empty"
     pub material: Option<String>,
     #[serde(default)]
@@ -76,6 +81,15 @@ fn default_state_id() -> u32 {
     0
 }
 
+// Defaults for fields some Bedrock versions omit.
+fn default_stack_size() -> u32 {
+    64
+}
+
+fn default_bounding_box() -> String {
+    "block".to_string()
+}
+
 #[derive(Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct BlockVariation {
@@ -84,17 +98,34 @@ pub struct BlockVariation {
     pub description: Option<String>,
 }
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Deserialize, Debug, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct BlockStateDefinition {
     pub name: String,
     #[serde(rename = "type")]
     pub state_type: String, // e.g., "bool", "enum", "int"
+    #[serde(alias = "num_values")]
     pub num_values: Option<u32>,
-    #[serde(default)]
+    // Bedrock lists numeric values as JSON numbers; they are kept as strings for consistency.
+    #[serde(default, deserialize_with = "deserialize_state_values")]
     pub values: Vec<String>,
 }
 
+// Accepts block state values given as strings, numbers or booleans.
+fn deserialize_state_values<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let values: Vec<Value> = Vec::deserialize(deserializer)?;
+    Ok(values
+        .into_iter()
+        .map(|value| match value {
+            Value::String(s) => s,
+            other => other.to_string(),
+        })
+        .collect())
+}
+
 // Structs for handling block drops, especially in older formats (pre-1.13)
 // where drops could be simple item IDs or more complex objects.
 
@@ -155,11 +186,15 @@ pub struct ItemVariation {
 pub struct Biome {
     pub id: u32,
     pub name: String,
+    #[serde(default)]
     pub category: String,
     pub temperature: f32,
     pub precipitation: Option<String>, // e.g., "none", "rain", "snow"
-    pub dimension: String,             // e.g., "overworld", "nether", "end"
+    #[serde(default)]
+    pub dimension: String, // e.g., "overworld", "nether", "end"
+    #[serde(default)]
     pub display_name: String,
+    #[serde(default)]
     pub color: i32,
     pub rainfall: Option<f32>,
     #[serde(default)]
@@ -184,8 +219,9 @@ pub struct Entity {
     pub id: u32,
     pub internal_id: Option<u32>, // Used in some older versions.
     pub name: String,
+    #[serde(default)]
     pub display_name: String,
-    #[serde(rename = "type")]
+    #[serde(rename = "type", default)]
     pub entity_type: String, // e.g., "mob", "object", "projectile"
     pub width: Option<f32>,
     pub height: Option<f32>,
@@ -196,7 +232,7 @@ pub struct Entity {
 
 // Structs for feature checking from features.json.
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Deserialize, Debug, Clone, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct Feature {
     pub name: String,
@@ -208,7 +244,7 @@ pub struct Feature {
     pub versions: Vec<String>, // Used if `values` and `version` are empty; expected [min, max].
 }
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Deserialize, Debug, Clone, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct FeatureValue {
     pub value: serde_json::Value, // The actual feature value (bool, string, number).
@@ -275,14 +311,14 @@ pub struct Instrument {
     pub name: String,
 }
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Deserialize, Debug, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct BlockLoot {
     pub block: String, // Block name
     pub drops: Vec<BlockLootDrop>,
 }
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Deserialize, Debug, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct BlockLootDrop {
     pub item: String, // Item name
@@ -336,14 +372,14 @@ pub struct WindowOpenedWith {
     pub id: u32,
 }
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Deserialize, Debug, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct EntityLoot {
     pub entity: String, // Entity name
     pub drops: Vec<EntityLootDrop>,
 }
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Deserialize, Debug, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct EntityLootDrop {
     pub item: String, // Item name
@@ -393,8 +429,10 @@ pub struct Enchantment {
     pub curse: bool,
     #[serde(default)]
     pub exclude: Vec<String>, // Names of mutually exclusive enchantments
+    #[serde(default)]
     pub category: String, // e.g., "weapon", "armor"
-    pub weight: u32,      // Rarity weight
+    #[serde(default)]
+    pub weight: u32, // Rarity weight
     #[serde(default)]
     pub tradeable: bool,
     #[serde(default)]
@@ -445,6 +483,12 @@ pub struct TintDatum {
     pub color: i32, // The tint color associated with these keys.
 }
 
+/// Tool speed multipliers from materials.json: material (e.g., "mineable/pickaxe") → item ID →
+/// speed multiplier. Entries with non-numeric item IDs or multipliers are skipped when loading.
+#[derive(Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(from = "HashMap<String, HashMap<String, Value>>")]
+pub struct Materials(pub HashMap<String, HashMap<u32, f32>>);
+
 #[derive(Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Legacy {
@@ -453,5 +497,65 @@ pub struct Legacy {
     pub items: HashMap<String, String>,
 }
 
-// Note: Commands and Materials are often loaded as raw `serde_json::Value`
-// due to their high variability across versions.
+// Bedrock-specific data structures.
+
+/// A Bedrock block state from blockStates.json. The array index is the block's runtime state ID.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BedrockBlockState {
+    pub name: String, // Block name without the "minecraft:" namespace
+    #[serde(default)]
+    pub states: HashMap<String, BedrockStateValue>,
+    #[serde(default)]
+    pub version: Option<u32>, // Block state format version
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BedrockStateValue {
+    #[serde(rename = "type")]
+    pub value_type: String, // e.g., "byte", "int", "string"
+    pub value: Value,
+}
+
+/// An entry of blockMappings.json, pairing a Java (pc) block state with its Bedrock (pe) equivalent.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockMapping {
+    pub pc: MappedBlockState,
+    pub pe: MappedBlockState,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MappedBlockState {
+    pub name: String,
+    #[serde(default)]
+    pub states: HashMap<String, Value>,
+}
+
+/// Default "Steve" skin data sent by Bedrock clients at login (steve.json).
+/// Only commonly used fields are typed; everything else is kept in `extra`.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct SteveSkin {
+    #[serde(default)]
+    pub skin_id: Option<String>,
+    #[serde(default)]
+    pub skin_data: Option<String>, // Base64-encoded RGBA pixels
+    #[serde(default)]
+    pub skin_image_width: Option<u32>,
+    #[serde(default)]
+    pub skin_image_height: Option<u32>,
+    #[serde(default)]
+    pub skin_resource_patch: Option<String>,
+    #[serde(default)]
+    pub skin_geometry_data: Option<String>,
+    #[serde(default)]
+    pub arm_size: Option<String>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+// Note: Commands are loaded as raw `serde_json::Value` due to their high variability
+// across versions.
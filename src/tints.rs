@@ -0,0 +1,132 @@
+use crate::block_state::BlockState;
+use crate::structs::{Biome, TintData};
+use serde_json::Value;
+
+// Corners of the grass and foliage colormaps: warm and wet, warm and dry, and cold.
+pub(crate) const GRASS_COLORMAP: [[f32; 3]; 3] = [
+    [71.0, 205.0, 51.0],
+    [191.0, 183.0, 85.0],
+    [128.0, 180.0, 151.0],
+];
+pub(crate) const FOLIAGE_COLORMAP: [[f32; 3]; 3] = [
+    [26.0, 191.0, 0.0],
+    [174.0, 164.0, 42.0],
+    [96.0, 161.0, 123.0],
+];
+
+// Vanilla's water colour for biomes that do not set their own.
+pub(crate) const DEFAULT_WATER_COLOR: i32 = 0x3F76E4;
+
+// Vanilla's downfall for biomes without one.
+const DEFAULT_RAINFALL: f32 = 0.5;
+
+/// What a block's tint depends on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TintSource {
+    Grass,
+    Foliage,
+    Water,
+    Redstone(u8),
+    Fixed(i32),
+}
+
+/// Works out how a block state is tinted, checking the `constant` tints of `tints.json` before
+/// the blocks vanilla colours by biome or state. Returns `None` for untinted blocks.
+pub(crate) fn tint_source(state: &BlockState, constant: Option<&TintData>) -> Option<TintSource> {
+    let name = state.name();
+    if let Some(color) = constant.and_then(|constant| listed_color(constant, name)) {
+        return Some(TintSource::Fixed(color));
+    }
+    let source = match name {
+        "grass_block" | "grass" | "short_grass" | "tall_grass" | "tallgrass" | "fern"
+        | "large_fern" | "potted_fern" | "sugar_cane" | "reeds" => TintSource::Grass,
+        "oak_leaves" | "jungle_leaves" | "acacia_leaves" | "dark_oak_leaves"
+        | "mangrove_leaves" | "leaves" | "leaves2" | "vine" => TintSource::Foliage,
+        "water" | "flowing_water" | "bubble_column" | "water_cauldron" => TintSource::Water,
+        "redstone_wire" => TintSource::Redstone(state.property("power")?.parse().ok()?),
+        // Constant colours, for data without a `constant` list.
+        "birch_leaves" => TintSource::Fixed(0x80A755),
+        "spruce_leaves" => TintSource::Fixed(0x619961),
+        "lily_pad" | "waterlily" => TintSource::Fixed(0x208030),
+        "attached_melon_stem" | "attached_pumpkin_stem" => TintSource::Fixed(0xE0C71C),
+        "melon_stem" | "pumpkin_stem" => {
+            let age: i32 = state.property("age")?.parse().ok()?;
+            TintSource::Fixed(rgb(
+                (age * 32) as f32,
+                (255 - age * 8) as f32,
+                (age * 4) as f32,
+            ))
+        }
+        _ => return None,
+    };
+    Some(source)
+}
+
+/// The colour a `tints.json` list gives a biome name or redstone power level, if any.
+pub(crate) fn listed_color(data: &TintData, key: &str) -> Option<i32> {
+    data.data
+        .iter()
+        .find(|datum| datum.keys.iter().any(|k| key_matches(k, key)))
+        .map(|datum| datum.color)
+}
+
+// Keys are biome or block names, with or without namespace, or power levels as numbers.
+fn key_matches(key: &Value, wanted: &str) -> bool {
+    match key {
+        Value::String(name) => name.strip_prefix("minecraft:").unwrap_or(name) == wanted,
+        Value::Number(number) => number.to_string() == wanted,
+        _ => false,
+    }
+}
+
+/// A biome's grass or foliage colour: its entry in `data`, else the colormap at the biome's
+/// temperature and rainfall, else the list's default.
+pub(crate) fn biome_color(
+    data: Option<&TintData>,
+    name: &str,
+    biome: Option<&Biome>,
+    colormap: &[[f32; 3]; 3],
+) -> Option<i32> {
+    data.and_then(|data| listed_color(data, name))
+        .or_else(|| {
+            biome.map(|biome| {
+                let rainfall = biome.rainfall.unwrap_or(DEFAULT_RAINFALL);
+                colormap_color(biome.temperature, rainfall, colormap)
+            })
+        })
+        .or_else(|| data.and_then(|data| data.default))
+}
+
+/// Interpolates a colormap the way its texture is laid out: the temperature and the rainfall
+/// scaled by temperature pick a point in the triangle between the three corner colours.
+pub(crate) fn colormap_color(temperature: f32, rainfall: f32, colormap: &[[f32; 3]; 3]) -> i32 {
+    let temperature = temperature.clamp(0.0, 1.0);
+    let rainfall = rainfall.clamp(0.0, 1.0) * temperature;
+    let weights = [rainfall, temperature - rainfall, 1.0 - temperature];
+    let channel = |c: usize| {
+        (0..3)
+            .map(|corner| weights[corner] * colormap[corner][c])
+            .sum()
+    };
+    rgb(channel(0), channel(1), channel(2))
+}
+
+/// Vanilla's redstone wire colour for a power level from 0 to 15.
+pub(crate) fn redstone_formula(power: u8) -> i32 {
+    let strength = f32::from(power.min(15)) / 15.0;
+    let red = strength * 0.6 + if power > 0 { 0.4 } else { 0.3 };
+    let green = (strength * strength * 0.7 - 0.5).clamp(0.0, 1.0);
+    let blue = (strength * strength * 0.6 - 0.7).clamp(0.0, 1.0);
+    // Vanilla truncates each channel rather than rounding it.
+    rgb(
+        (red * 255.0).floor(),
+        (green * 255.0).floor(),
+        (blue * 255.0).floor(),
+    )
+}
+
+// Packs channels from 0 to 255 into 0xRRGGBB.
+fn rgb(red: f32, green: f32, blue: f32) -> i32 {
+    let channel = |value: f32| value.round().clamp(0.0, 255.0) as i32;
+    channel(red) << 16 | channel(green) << 8 | channel(blue)
+}
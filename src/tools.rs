@@ -0,0 +1,113 @@
+use crate::structs::{Block, Materials};
+use serde_json::Value;
+use std::collections::HashMap;
+
+impl Materials {
+    /// The speed multipliers of a material, keyed by item ID.
+    pub fn get(&self, material: &str) -> Option<&HashMap<u32, f32>> {
+        self.0.get(material)
+    }
+
+    /// The speed multiplier of an item on a material, or `None` if the item is not an
+    /// effective tool for it.
+    pub fn multiplier(&self, material: &str, item_id: u32) -> Option<f32> {
+        self.get(material)?.get(&item_id).copied()
+    }
+
+    /// The items effective on a material with their speed multipliers, fastest first
+    /// (ties ordered by item ID).
+    pub fn effective_tools(&self, material: &str) -> Vec<(u32, f32)> {
+        let mut tools: Vec<(u32, f32)> = self
+            .get(material)
+            .map(|tools| tools.iter().map(|(id, speed)| (*id, *speed)).collect())
+            .unwrap_or_default();
+        tools.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+        tools
+    }
+}
+
+impl From<HashMap<String, HashMap<String, Value>>> for Materials {
+    fn from(raw: HashMap<String, HashMap<String, Value>>) -> Self {
+        let materials = raw
+            .into_iter()
+            .map(|(material, tools)| {
+                let tools = tools
+                    .into_iter()
+                    .filter_map(|(item, speed)| {
+                        let parsed = item.parse::<u32>().ok().zip(speed.as_f64());
+                        if parsed.is_none() {
+                            log::warn!(
+                                "Skipping malformed materials.json entry {}: {} = {}",
+                                material,
+                                item,
+                                speed
+                            );
+                        }
+                        parsed.map(|(item, speed)| (item, speed as f32))
+                    })
+                    .collect();
+                (material, tools)
+            })
+            .collect();
+        Materials(materials)
+    }
+}
+
+/// How well an item works on a block.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ToolEffect {
+    /// The item's speed multiplier on the block's material (1.0 if it is not an effective tool).
+    pub speed_multiplier: f32,
+    /// Whether the block drops when broken with the item.
+    pub harvests: bool,
+}
+
+/// Combines the block's harvest tools with the material speed multipliers for an item
+/// (`None` for an empty hand).
+pub(crate) fn tool_effect(
+    block: &Block,
+    materials: Option<&Materials>,
+    item_id: Option<u32>,
+) -> ToolEffect {
+    let speed_multiplier = block
+        .material
+        .as_deref()
+        .zip(materials)
+        .zip(item_id)
+        .and_then(|((material, materials), item)| materials.multiplier(material, item))
+        .unwrap_or(1.0);
+    ToolEffect {
+        speed_multiplier,
+        harvests: can_harvest(block, item_id),
+    }
+}
+
+/// Whether breaking `block` with the held item makes it drop. Blocks without harvest tools
+/// drop with anything.
+pub(crate) fn can_harvest(block: &Block, item_id: Option<u32>) -> bool {
+    block.harvest_tools.is_empty()
+        || item_id.is_some_and(|id| block.harvest_tools.contains_key(&id.to_string()))
+}
+
+/// Picks the best of `candidates` for breaking `block`: items that make it drop come first,
+/// then faster items, then earlier candidates.
+pub(crate) fn best_tool(
+    block: &Block,
+    materials: Option<&Materials>,
+    candidates: &[u32],
+) -> Option<u32> {
+    candidates
+        .iter()
+        .enumerate()
+        .max_by(|(ia, a), (ib, b)| {
+            let (a, b) = (
+                tool_effect(block, materials, Some(**a)),
+                tool_effect(block, materials, Some(**b)),
+            );
+            a.harvests
+                .cmp(&b.harvests)
+                .then(a.speed_multiplier.total_cmp(&b.speed_multiplier))
+                .then(ib.cmp(ia))
+        })
+        .map(|(_, id)| *id)
+}
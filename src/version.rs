@@ -1,5 +1,6 @@
 use crate::data_source;
 use crate::error::McDataError;
+use crate::known_versions;
 use crate::loader::load_data_from_path;
 use crate::structs::ProtocolVersionInfo;
 use once_cell::sync::OnceCell;
@@ -8,7 +9,10 @@ use std::collections::HashMap;
 use std::sync::Arc;
 
 /// Represents the Minecraft edition (PC/Java or Bedrock).
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+///
+/// Editions are ordered `Pc` before `Bedrock`. This ordering carries no meaning beyond
+/// giving `Version` a total order; use `Version::cmp_same_edition` for chronological comparisons.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Edition {
     Pc,
     Bedrock,
@@ -41,39 +45,60 @@ pub struct Version {
     pub major_version: String,
     /// The protocol version number.
     pub version: i32,
-    /// The data version number, used for reliable comparisons between versions of the same edition.
-    /// Higher data versions are newer. This is calculated if missing in source data.
-    pub data_version: i32,
+    /// The Mojang data version, used for reliable comparisons between versions of the same edition.
+    /// Higher data versions are newer. Taken from the source data, or from a built-in table of
+    /// known data versions if missing there. `None` for versions predating data versions
+    /// (PC releases before 1.9) and for Bedrock, which has no equivalent.
+    pub data_version: Option<i32>,
+    /// A stable chronological ordinal for PC versions that predate data versions, derived from
+    /// release dates. Only set when `data_version` is `None`; higher ordinals are newer.
+    pub legacy_ordinal: Option<u32>,
     /// The edition (PC or Bedrock).
     pub edition: Edition,
     /// The release type (e.g., "release", "snapshot").
     pub release_type: String,
 }
 
-// Implement comparison operators based on `data_version`.
-// Versions from different editions are considered incomparable.
+// `Version` has a lawful total order so it can be used in sorted collections such as `BTreeMap`.
+// Versions are ordered by edition first, then by `data_version` (versions without one sort first),
+// then by `legacy_ordinal`, then by protocol version.
+// The remaining fields only break ties, keeping `Ord` consistent with the derived `Eq`.
 impl PartialOrd for Version {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        if self.edition != other.edition {
-            None // Cannot compare across editions.
-        } else {
-            // Compare based on data_version.
-            self.data_version.partial_cmp(&other.data_version)
-        }
+        Some(self.cmp(other))
     }
 }
 impl Ord for Version {
     fn cmp(&self, other: &Self) -> Ordering {
-        self.partial_cmp(other).unwrap_or_else(|| {
-            // If editions differ, log a warning and treat them as equal for sorting purposes,
-            // although semantically they are incomparable.
-            log::warn!(
-                "Comparing Version structs from different editions ({:?} vs {:?})",
-                self.edition,
-                other.edition
-            );
-            Ordering::Equal
-        })
+        self.edition
+            .cmp(&other.edition)
+            .then_with(|| self.data_version.cmp(&other.data_version))
+            .then_with(|| self.legacy_ordinal.cmp(&other.legacy_ordinal))
+            .then_with(|| self.version.cmp(&other.version))
+            .then_with(|| self.minecraft_version.cmp(&other.minecraft_version))
+            .then_with(|| self.major_version.cmp(&other.major_version))
+            .then_with(|| self.release_type.cmp(&other.release_type))
+    }
+}
+
+impl Version {
+    /// Compares two versions chronologically, requiring both to belong to the same edition.
+    ///
+    /// Unlike the `Ord` implementation, which orders editions against each other so that
+    /// versions can be sorted, this refuses to compare a PC version with a Bedrock version.
+    ///
+    /// # Errors
+    /// Returns `McDataError::EditionMismatch` if the editions differ.
+    pub fn cmp_same_edition(&self, other: &Version) -> Result<Ordering, McDataError> {
+        if self.edition != other.edition {
+            return Err(McDataError::EditionMismatch {
+                left: self.edition,
+                left_version: self.minecraft_version.clone(),
+                right: other.edition,
+                right_version: other.minecraft_version.clone(),
+            });
+        }
+        Ok(self.cmp(other))
     }
 }
 
@@ -91,15 +116,15 @@ pub struct VersionData {
     /// Major version keys map to the latest *release* within that major version.
     pub by_minecraft_version: HashMap<String, Version>,
     /// Maps major version string (e.g., "1.18") to a list of all `Version`s within that major version,
-    /// sorted newest first (by data_version).
+    /// sorted newest first.
     pub by_major_version: HashMap<String, Vec<Version>>,
     /// Maps protocol version number to a list of `Version`s sharing that protocol number,
-    /// sorted newest first (by data_version).
+    /// sorted newest first.
     pub by_protocol_version: HashMap<i32, Vec<Version>>,
 }
 
-/// Loads `protocolVersions.json` for the given edition, calculates missing `data_version`s,
-/// and indexes the data into a `VersionData` struct.
+/// Loads `protocolVersions.json` for the given edition, fills in missing `data_version`s from
+/// the built-in table, and indexes the data into a `VersionData` struct.
 fn load_and_index_versions(edition: Edition) -> Result<Arc<VersionData>, McDataError> {
     log::debug!(
         "Attempting to load protocolVersions.json for {:?}...",
@@ -110,23 +135,7 @@ fn load_and_index_versions(edition: Edition) -> Result<Arc<VersionData>, McDataE
     let path = data_root.join(path_str);
 
     // Load the raw version info from the JSON file.
-    let mut raw_versions: Vec<ProtocolVersionInfo> = load_data_from_path(&path)?;
-
-    // Calculate `data_version` if missing. This is crucial for reliable comparisons.
-    // We assign decreasing negative numbers based on reverse protocol version order.
-    // Sort by protocol version descending first to ensure consistent assignment.
-    raw_versions.sort_by(|a, b| b.version.cmp(&a.version));
-    for (i, v) in raw_versions.iter_mut().enumerate() {
-        if v.data_version.is_none() {
-            // Assign a synthetic, negative data_version for older entries lacking one.
-            v.data_version = Some(-(i as i32));
-            log::trace!(
-                "Assigned synthetic data_version {} to {}",
-                v.data_version.unwrap(),
-                v.minecraft_version
-            );
-        }
-    }
+    let raw_versions: Vec<ProtocolVersionInfo> = load_data_from_path(&path)?;
 
     // Initialize index maps.
     let mut by_mc_ver = HashMap::new();
@@ -135,16 +144,28 @@ fn load_and_index_versions(edition: Edition) -> Result<Arc<VersionData>, McDataE
 
     // Process each raw version entry and populate the indexes.
     for raw in raw_versions {
-        // Ensure data_version was present or calculated.
-        let data_version = raw.data_version.ok_or_else(|| {
-            McDataError::Internal(format!("Missing dataVersion for {}", raw.minecraft_version))
-        })?;
+        // Prefer the upstream data version, falling back to the built-in table.
+        let data_version = raw
+            .data_version
+            .or_else(|| known_versions::known_data_version(edition, &raw.minecraft_version));
+        // Versions without a data version are ordered by their release chronology instead.
+        let legacy_ordinal = match data_version {
+            Some(_) => None,
+            None => known_versions::legacy_ordinal(edition, &raw.minecraft_version),
+        };
+        if edition == Edition::Pc && data_version.is_none() && legacy_ordinal.is_none() {
+            log::debug!(
+                "No data version or legacy ordinal known for {}, ordering by protocol only",
+                raw.minecraft_version
+            );
+        }
 
         let v = Version {
             minecraft_version: raw.minecraft_version.clone(),
             major_version: raw.major_version.clone(),
             version: raw.version,
             data_version,
+            legacy_ordinal,
             edition,
             release_type: raw.release_type.clone(),
         };
@@ -159,9 +180,8 @@ fn load_and_index_versions(edition: Edition) -> Result<Arc<VersionData>, McDataE
             .and_modify(|existing| {
                 // Update only if the current version `v` is newer AND is a release,
                 // OR if `v` is newer and the existing entry is not a release (prefer releases).
-                if (v.data_version > existing.data_version && v.release_type == "release")
-                    || (v.data_version > existing.data_version
-                        && existing.release_type != "release")
+                if v > *existing
+                    && (v.release_type == "release" || existing.release_type != "release")
                 {
                     *existing = v.clone();
                 }
@@ -178,7 +198,7 @@ fn load_and_index_versions(edition: Edition) -> Result<Arc<VersionData>, McDataE
         by_proto.entry(raw.version).or_default().push(v);
     }
 
-    // Sort the vectors within the maps by data_version descending (newest first).
+    // Sort the vectors within the maps newest first.
     for versions in by_major.values_mut() {
         versions.sort_unstable_by(|a, b| b.cmp(a));
     }
@@ -290,25 +310,147 @@ pub fn resolve_version(version_str: &str) -> Result<Version, McDataError> {
         version_str,
         edition
     );
-    Err(McDataError::InvalidVersion(version_str.to_string()))
+    let known: Vec<&str> = version_data
+        .by_major_version
+        .values()
+        .flatten()
+        .map(|v| v.minecraft_version.as_str())
+        .collect();
+    let mut suggestions = suggest_versions(version_part, &known, MAX_SUGGESTIONS);
+
+    // An unprefixed version that only exists in Bedrock is most likely a Bedrock version.
+    if split_edition_prefix(version_str).0.is_none() {
+        if let Ok(bedrock_data) = get_version_data(Edition::Bedrock) {
+            if bedrock_data.by_minecraft_version.contains_key(version_part) {
+                suggestions.insert(0, format!("bedrock_{}", version_part));
+                suggestions.truncate(MAX_SUGGESTIONS);
+            }
+        }
+    }
+
+    Err(McDataError::UnknownVersion {
+        version: version_str.to_string(),
+        edition,
+        suggestions,
+    })
+}
+
+// Edition prefixes accepted in version strings (matched case-insensitively).
+const EDITION_PREFIXES: &[(&str, Edition)] = &[
+    ("pc_", Edition::Pc),
+    ("java_", Edition::Pc),
+    ("je_", Edition::Pc),
+    ("bedrock_", Edition::Bedrock),
+    ("be_", Edition::Bedrock),
+    ("pe_", Edition::Bedrock),
+    ("mcpe_", Edition::Bedrock),
+];
+
+// Number of suggestions carried by `McDataError::UnknownVersion`.
+const MAX_SUGGESTIONS: usize = 3;
+
+/// Splits a recognised edition prefix (e.g., "pc_", "java_", "be_") off a version string.
+/// Returns `None` for the edition if no prefix is present.
+pub(crate) fn split_edition_prefix(version_str: &str) -> (Option<Edition>, &str) {
+    for (prefix, edition) in EDITION_PREFIXES {
+        if version_str.len() >= prefix.len()
+            && version_str.is_char_boundary(prefix.len())
+            && version_str[..prefix.len()].eq_ignore_ascii_case(prefix)
+        {
+            return (Some(*edition), &version_str[prefix.len()..]);
+        }
+    }
+    (None, version_str)
 }
 
 /// Parses a version string, extracting the edition and the version part.
-/// Defaults to PC edition if no prefix ("pc_" or "bedrock_") is found.
+/// Defaults to PC edition if no edition prefix is found.
 fn parse_version_string(version_str: &str) -> Result<(Edition, &str), McDataError> {
-    if let Some(stripped) = version_str.strip_prefix("pc_") {
-        Ok((Edition::Pc, stripped))
-    } else if let Some(stripped) = version_str.strip_prefix("bedrock_") {
-        Ok((Edition::Bedrock, stripped))
-    } else {
-        // Assume PC edition if no prefix is present.
-        // The subsequent `resolve_version` logic will determine if the version part is valid for PC.
-        log::trace!(
-            "Assuming PC edition for version string '{}' (no prefix found)",
-            version_str
-        );
-        Ok((Edition::Pc, version_str))
+    match split_edition_prefix(version_str) {
+        (Some(edition), stripped) => Ok((edition, stripped)),
+        (None, _) => {
+            // Assume PC edition if no prefix is present.
+            // The subsequent `resolve_version` logic will determine if the version part is valid for PC.
+            log::trace!(
+                "Assuming PC edition for version string '{}' (no prefix found)",
+                version_str
+            );
+            Ok((Edition::Pc, version_str))
+        }
+    }
+}
+
+/// Returns up to `limit` known versions closest to an unresolvable input, best match first.
+///
+/// Candidates are ranked by edit distance to the input (after normalising separators such as
+/// "1,18,2"), then by numeric closeness within the same major version, preferring newer versions
+/// on ties. Candidates that are neither textually nor numerically close are dropped.
+pub(crate) fn suggest_versions(input: &str, known: &[&str], limit: usize) -> Vec<String> {
+    let normalized: String = input
+        .trim()
+        .to_ascii_lowercase()
+        .chars()
+        .map(|c| if c == ',' { '.' } else { c })
+        .collect();
+    let max_distance = std::cmp::max(2, normalized.chars().count() / 3);
+    let input_parts = numeric_parts(&normalized);
+
+    let mut ranked: Vec<(usize, u64, &str)> = known
+        .iter()
+        .filter_map(|candidate| {
+            let distance = levenshtein(&normalized, candidate);
+            let gap = match (&input_parts, numeric_parts(candidate)) {
+                (Some(a), Some(b)) if a.len() >= 2 && b.len() >= 2 && a[..2] == b[..2] => {
+                    let patch_a = a.get(2).copied().unwrap_or(0);
+                    let patch_b = b.get(2).copied().unwrap_or(0);
+                    Some(patch_a.abs_diff(patch_b) as u64)
+                }
+                _ => None,
+            };
+            if distance <= max_distance || gap.is_some() {
+                Some((distance, gap.unwrap_or(u64::MAX), *candidate))
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    ranked.sort_by(|a, b| {
+        a.0.cmp(&b.0)
+            .then(a.1.cmp(&b.1))
+            .then_with(|| compare_version_strings(b.2, a.2))
+    });
+    let mut suggestions: Vec<String> = Vec::with_capacity(limit);
+    for (_, _, candidate) in ranked {
+        if !suggestions.iter().any(|s| s == candidate) {
+            suggestions.push(candidate.to_string());
+        }
+        if suggestions.len() == limit {
+            break;
+        }
+    }
+    suggestions
+}
+
+// Parses a purely numeric dotted version ("1.18.2") into its parts.
+fn numeric_parts(version: &str) -> Option<Vec<u32>> {
+    version.split('.').map(|part| part.parse().ok()).collect()
+}
+
+// Levenshtein edit distance between two strings, by character.
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b_chars: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b_chars.len()).collect();
+    let mut current = vec![0; b_chars.len() + 1];
+    for (i, ca) in a.chars().enumerate() {
+        current[0] = i + 1;
+        for (j, cb) in b_chars.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
     }
+    previous[b_chars.len()]
 }
 
 /// Returns a sorted list of all known specific Minecraft version strings for an edition.
@@ -326,22 +468,26 @@ pub fn get_supported_versions(edition: Edition) -> Result<Vec<String>, McDataErr
         .collect();
 
     // Sort the versions. A simple numeric comparison of parts usually works well.
-    versions.sort_by(|a, b| {
-        let parts_a: Vec<Option<u32>> = a.split('.').map(|s| s.parse().ok()).collect();
-        let parts_b: Vec<Option<u32>> = b.split('.').map(|s| s.parse().ok()).collect();
-        let len = std::cmp::max(parts_a.len(), parts_b.len());
-        for i in 0..len {
-            // Treat missing parts or non-numeric parts as 0 for comparison.
-            let val_a = parts_a.get(i).cloned().flatten().unwrap_or(0);
-            let val_b = parts_b.get(i).cloned().flatten().unwrap_or(0);
-            match val_a.cmp(&val_b) {
-                Ordering::Equal => continue, // If parts are equal, compare the next part.
-                other => return other,       // Otherwise, return the comparison result.
-            }
-        }
-        Ordering::Equal // If all parts are equal, the versions are considered equal.
-    });
+    versions.sort_by(|a, b| compare_version_strings(a, b));
 
     // Return sorted list (oldest first).
     Ok(versions)
 }
+
+/// Compares two version strings (e.g., "1.16.5" and "1.16.40") part by part numerically.
+/// Missing or non-numeric parts are treated as 0.
+pub(crate) fn compare_version_strings(a: &str, b: &str) -> Ordering {
+    let parts_a: Vec<Option<u32>> = a.split('.').map(|s| s.parse().ok()).collect();
+    let parts_b: Vec<Option<u32>> = b.split('.').map(|s| s.parse().ok()).collect();
+    let len = std::cmp::max(parts_a.len(), parts_b.len());
+    for i in 0..len {
+        // Treat missing parts or non-numeric parts as 0 for comparison.
+        let val_a = parts_a.get(i).cloned().flatten().unwrap_or(0);
+        let val_b = parts_b.get(i).cloned().flatten().unwrap_or(0);
+        match val_a.cmp(&val_b) {
+            Ordering::Equal => continue, // If parts are equal, compare the next part.
+            other => return other,       // Otherwise, return the comparison result.
+        }
+    }
+    Ordering::Equal // If all parts are equal, the versions are considered equal.
+}
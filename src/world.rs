@@ -0,0 +1,26 @@
+/// A source of block states supplied by the caller, e.g. a bot's loaded chunks.
+///
+/// Closures `Fn(i32, i32, i32) -> Option<u32>` implement the trait, so small worlds can be
+/// described inline:
+///
+/// ```
+/// use mcdata_rs::World;
+///
+/// let stone = 1;
+/// let flat = |_x: i32, y: i32, _z: i32| Some(if y < 64 { stone } else { 0 });
+/// assert_eq!(flat.state_id(0, 63, 0), Some(stone));
+/// ```
+pub trait World {
+    /// The state ID of the block at a position, or `None` if the block is not loaded.
+    /// Unloaded blocks are treated as air.
+    fn state_id(&self, x: i32, y: i32, z: i32) -> Option<u32>;
+}
+
+impl<F> World for F
+where
+    F: Fn(i32, i32, i32) -> Option<u32>,
+{
+    fn state_id(&self, x: i32, y: i32, z: i32) -> Option<u32> {
+        self(x, y, z)
+    }
+}
@@ -1,5 +1,8 @@
 use mcdata_rs::*; // Use the crate itself
+use proptest::prelude::*;
 use serde_json::Value;
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
 use std::sync::Arc;
 
 // Helper to initialize logging for test output.
@@ -254,6 +257,233 @@ fn feature_support() {
     );
 }
 
+#[test]
+fn typed_feature_flags() {
+    use mcdata_rs::{
+        DimensionIsAnInt, FeatureFlag, MetadataIxOfItem, TallWorld, TheFlattening,
+        ALL_FEATURE_NAMES,
+    };
+    setup();
+    let data_1_18 = mc_data("1.18.2").unwrap();
+    let data_1_15 = mc_data("1.15.2").unwrap();
+
+    assert!(!data_1_15.feature::<TallWorld>().unwrap());
+    assert!(data_1_18.feature::<TallWorld>().unwrap());
+    assert!(data_1_15.feature::<DimensionIsAnInt>().unwrap());
+    assert_eq!(data_1_18.feature::<MetadataIxOfItem>().unwrap(), Some(8));
+    assert_eq!(data_1_15.feature::<MetadataIxOfItem>().unwrap(), Some(7));
+
+    // Typed values agree with the string API.
+    assert_eq!(
+        Value::Bool(data_1_18.feature::<TheFlattening>().unwrap()),
+        data_1_18.support_feature(TheFlattening::NAME).unwrap()
+    );
+
+    // Every typed flag names a feature defined upstream.
+    for name in ALL_FEATURE_NAMES {
+        data_1_18
+            .feature_value(name)
+            .unwrap_or_else(|e| panic!("Typed feature '{}' failed: {}", name, e));
+    }
+
+    match data_1_18.feature_value("someRandomFeatureName") {
+        Err(McDataError::UnknownFeature { name, edition }) => {
+            assert_eq!(name, "someRandomFeatureName");
+            assert_eq!(edition, Edition::Pc);
+        }
+        other => panic!("Expected UnknownFeature error, got {:?}", other),
+    }
+}
+
+#[test]
+fn enumerate_features() {
+    setup();
+    let data = mc_data("1.18.2").unwrap();
+    let features = data.features().expect("Failed to enumerate features");
+    assert!(!features.is_empty());
+
+    // Names are unique and agree with single-name lookups.
+    let mut names: Vec<&str> = features.iter().map(|f| f.name.as_str()).collect();
+    names.sort_unstable();
+    names.dedup();
+    assert_eq!(names.len(), features.len(), "Duplicate feature names");
+
+    let tall_world = features
+        .iter()
+        .find(|f| f.name == "tallWorld")
+        .expect("tallWorld missing");
+    assert_eq!(tall_world.value, Value::Bool(true));
+    assert!(tall_world.range.is_some(), "Active feature without a range");
+
+    let dim_int = features
+        .iter()
+        .find(|f| f.name == "dimensionIsAnInt")
+        .expect("dimensionIsAnInt missing");
+    assert_eq!(dim_int.value, Value::Bool(false));
+    assert_eq!(dim_int.range, None);
+
+    for feature in &features {
+        assert_eq!(
+            feature.value,
+            data.support_feature(&feature.name).unwrap(),
+            "Mismatch for {}",
+            feature.name
+        );
+    }
+}
+
+#[test]
+fn feature_timeline_segments() {
+    setup();
+    let timeline = feature_timeline("tallWorld", Edition::Pc).expect("Failed to build timeline");
+    assert!(timeline.len() >= 2, "tallWorld should flip at least once");
+
+    // Segments are contiguous, ordered, and alternate in value.
+    for pair in timeline.windows(2) {
+        assert!(pair[0].last < pair[1].first);
+        assert_ne!(pair[0].value, pair[1].value);
+    }
+    assert_eq!(timeline[0].value, Value::Bool(false));
+
+    let first = first_version_supporting("tallWorld", Edition::Pc)
+        .unwrap()
+        .expect("tallWorld is never supported");
+    let data = mc_data(&first.minecraft_version).unwrap();
+    assert_eq!(
+        data.support_feature("tallWorld").unwrap(),
+        Value::Bool(true)
+    );
+    assert!(first <= resolved_version("1.18"));
+
+    let last = last_version_supporting("dimensionIsAnInt", Edition::Pc)
+        .unwrap()
+        .expect("dimensionIsAnInt is never supported");
+    assert!(last < resolved_version("1.18"));
+
+    assert!(matches!(
+        feature_timeline("someRandomFeatureName", Edition::Pc),
+        Err(McDataError::UnknownFeature { .. })
+    ));
+}
+
+// Resolves a version string through the public API.
+fn resolved_version(version: &str) -> Version {
+    mc_data(version).unwrap().version.clone()
+}
+
+#[test]
+fn lint_upstream_features() {
+    setup();
+    let bedrock = mc_data("bedrock_1.20.10").unwrap();
+    let pc = mc_data("1.20.4").unwrap();
+    let names = |data: &IndexedData| -> Vec<String> {
+        data.features()
+            .unwrap()
+            .into_iter()
+            .map(|feature| feature.name)
+            .collect()
+    };
+
+    // The typed flags are features the crate relies on: they must be defined and lint clean.
+    let pc_names = names(&pc);
+    for name in ALL_FEATURE_NAMES {
+        assert!(
+            pc_names.iter().any(|defined| defined == name),
+            "Typed feature '{}' is not defined upstream",
+            name
+        );
+    }
+    let diagnostics = lint_features(Edition::Pc).expect("Failed to lint features");
+    let flagged: Vec<_> = diagnostics
+        .iter()
+        .filter(|diagnostic| ALL_FEATURE_NAMES.contains(&diagnostic.feature.as_str()))
+        .collect();
+    assert!(
+        flagged.is_empty(),
+        "Typed features have lint errors: {:?}",
+        flagged
+    );
+
+    // Every diagnostic names a feature the edition actually defines.
+    for (edition, data) in [(Edition::Pc, &pc), (Edition::Bedrock, &bedrock)] {
+        let names = names(data);
+        for diagnostic in lint_features(edition).expect("Failed to lint features") {
+            assert!(
+                names.contains(&diagnostic.feature),
+                "{:?} diagnostic for unknown feature: {}",
+                edition,
+                diagnostic
+            );
+        }
+    }
+
+    // Whatever the lint found, lookups still succeed for every feature.
+    for feature in pc.features().unwrap() {
+        pc.support_feature(&feature.name)
+            .unwrap_or_else(|e| panic!("Lookup of '{}' failed: {}", feature.name, e));
+    }
+}
+
+#[test]
+fn feature_overlays() {
+    use std::io::Write;
+    setup();
+    let data = mc_data("1.18.2").unwrap();
+    // Names are unique to this test, since overlays are global.
+    assert!(matches!(
+        data.feature_value("overlayTestQuirk"),
+        Err(McDataError::UnknownFeature { .. })
+    ));
+
+    FeatureOverlay::new(Edition::Pc)
+        .enabled("overlayTestQuirk", "1.16", "latest")
+        .valued(
+            "overlayTestLimit",
+            [
+                (Value::from(1), "1.8".to_string(), "1.15.2".to_string()),
+                (Value::from(2), "1.16".to_string(), "latest".to_string()),
+            ],
+        )
+        .register()
+        .unwrap();
+
+    // Already loaded data sees the overlay, and so does data fetched from the cache.
+    assert_eq!(
+        data.support_feature("overlayTestQuirk").unwrap(),
+        Value::Bool(true)
+    );
+    let data = mc_data("1.18.2").unwrap();
+    assert_eq!(
+        data.feature_value("overlayTestLimit").unwrap(),
+        Value::from(2)
+    );
+    assert_eq!(
+        mc_data("1.15.2")
+            .unwrap()
+            .support_feature("overlayTestQuirk")
+            .unwrap(),
+        Value::Bool(false)
+    );
+
+    // Overlays load from files in the features.json schema.
+    let path = std::env::temp_dir().join("mcdata_rs_overlay_test.json");
+    let mut file = std::fs::File::create(&path).unwrap();
+    write!(
+        file,
+        r#"[{{"name": "overlayTestFromFile", "versions": ["1.17", "latest"]}}]"#
+    )
+    .unwrap();
+    FeatureOverlay::from_json_file(Edition::Pc, &path)
+        .unwrap()
+        .register()
+        .unwrap();
+    assert_eq!(
+        data.support_feature("overlayTestFromFile").unwrap(),
+        Value::Bool(true)
+    );
+    let _ = std::fs::remove_file(&path);
+}
+
 #[test]
 fn cache_hit() {
     setup();
@@ -290,11 +520,40 @@ fn invalid_version_error() {
         version
     );
     match result.err().unwrap() {
-        McDataError::InvalidVersion(v) => assert_eq!(v, version),
-        e => panic!("Expected InvalidVersion error, got {:?}", e),
+        McDataError::UnknownVersion { version: v, .. } => assert_eq!(v, version),
+        e => panic!("Expected UnknownVersion error, got {:?}", e),
     }
 }
 
+#[test]
+fn version_typo_suggestions() {
+    setup();
+    match mc_data("1.18.3") {
+        Err(McDataError::UnknownVersion {
+            edition,
+            suggestions,
+            ..
+        }) => {
+            assert_eq!(edition, Edition::Pc);
+            assert_eq!(suggestions.first().map(String::as_str), Some("1.18.2"));
+        }
+        other => panic!("Expected UnknownVersion error, got {:?}", other.map(|_| ())),
+    }
+
+    match mc_data("1,18,2") {
+        Err(e @ McDataError::UnknownVersion { .. }) => {
+            assert!(e.to_string().contains("did you mean 1.18.2"), "{}", e)
+        }
+        other => panic!("Expected UnknownVersion error, got {:?}", other.map(|_| ())),
+    }
+
+    // Edition aliases resolve like the canonical prefixes.
+    let java = mc_data("java_1.18.2").expect("Failed to load java_1.18.2");
+    assert_eq!(java.version.minecraft_version, "1.18.2");
+    let bedrock = mc_data("be_1.18.30").expect("Failed to load be_1.18.30");
+    assert_eq!(bedrock.version.edition, Edition::Bedrock);
+}
+
 #[test]
 fn supported_versions_list() {
     setup();
@@ -305,9 +564,13 @@ fn supported_versions_list() {
     assert!(pc_versions.iter().any(|v| v == "1.8.8"));
     assert!(pc_versions.iter().any(|v| v == "1.20.4")); // Check a more recent one
 
-    // Add similar check for Bedrock when supported and data is available.
-    // let bedrock_versions = supported_versions(Edition::Bedrock).expect("Failed to get Bedrock versions");
-    // assert!(!bedrock_versions.is_empty(), "Bedrock versions list is empty");
+    let bedrock_versions =
+        supported_versions(Edition::Bedrock).expect("Failed to get Bedrock versions");
+    assert!(
+        !bedrock_versions.is_empty(),
+        "Bedrock versions list is empty"
+    );
+    assert!(bedrock_versions.iter().any(|v| v == "1.18.30"));
 }
 
 #[test]
@@ -393,8 +656,1000 @@ fn block_shapes() {
     assert!(data.block_shapes_by_name.get("not_a_real_block").is_none());
 }
 
+// Builds a `Version` without loading any data, for ordering tests.
+fn make_version(
+    edition: Edition,
+    minecraft_version: &str,
+    protocol: i32,
+    data_version: Option<i32>,
+) -> Version {
+    Version {
+        minecraft_version: minecraft_version.to_string(),
+        major_version: minecraft_version
+            .rsplit_once('.')
+            .map_or(minecraft_version, |(major, _)| major)
+            .to_string(),
+        version: protocol,
+        data_version,
+        legacy_ordinal: None,
+        edition,
+        release_type: "release".to_string(),
+    }
+}
+
+fn arb_version() -> impl Strategy<Value = Version> {
+    (
+        prop_oneof![Just(Edition::Pc), Just(Edition::Bedrock)],
+        prop_oneof![Just("1.18.2"), Just("1.19"), Just("1.20.10"), Just("1.8.8")],
+        0..4i32,
+        proptest::option::of(0..3i32),
+        proptest::option::of(0..3u32),
+        prop_oneof![Just("release"), Just("snapshot")],
+    )
+        .prop_map(
+            |(edition, mc, protocol, data_version, legacy_ordinal, release_type)| {
+                let mut v = make_version(edition, mc, protocol, data_version);
+                v.legacy_ordinal = legacy_ordinal;
+                v.release_type = release_type.to_string();
+                v
+            },
+        )
+}
+
+#[test]
+fn version_order_separates_editions() {
+    let pc = make_version(Edition::Pc, "1.18.2", 758, Some(2975));
+    let bedrock = make_version(Edition::Bedrock, "1.18.30", 503, None);
+
+    // Entries from different editions must not collapse into one key.
+    let mut map = BTreeMap::new();
+    map.insert(pc.clone(), "pc");
+    map.insert(bedrock.clone(), "bedrock");
+    assert_eq!(map.len(), 2);
+    assert_eq!(map.get(&pc), Some(&"pc"));
+    assert_eq!(map.get(&bedrock), Some(&"bedrock"));
+
+    // Sorting groups editions together, each in chronological order.
+    let mut versions = [
+        make_version(Edition::Bedrock, "1.19.0", 527, None),
+        make_version(Edition::Pc, "1.19", 759, Some(3105)),
+        bedrock.clone(),
+        pc.clone(),
+    ];
+    versions.sort();
+    let names: Vec<_> = versions
+        .iter()
+        .map(|v| format!("{}_{}", v.edition, v.minecraft_version))
+        .collect();
+    assert_eq!(
+        names,
+        ["pc_1.18.2", "pc_1.19", "bedrock_1.18.30", "bedrock_1.19.0"]
+    );
+}
+
+#[test]
+fn cmp_same_edition_rejects_cross_edition() {
+    let pc_old = make_version(Edition::Pc, "1.16.5", 754, Some(2586));
+    let pc_new = make_version(Edition::Pc, "1.18.2", 758, Some(2975));
+    let bedrock = make_version(Edition::Bedrock, "1.18.30", 503, None);
+
+    assert_eq!(pc_old.cmp_same_edition(&pc_new).unwrap(), Ordering::Less);
+    assert_eq!(pc_new.cmp_same_edition(&pc_old).unwrap(), Ordering::Greater);
+    assert_eq!(pc_new.cmp_same_edition(&pc_new).unwrap(), Ordering::Equal);
+    match pc_new.cmp_same_edition(&bedrock) {
+        Err(McDataError::EditionMismatch { left, right, .. }) => {
+            assert_eq!(left, Edition::Pc);
+            assert_eq!(right, Edition::Bedrock);
+        }
+        other => panic!("Expected EditionMismatch error, got {:?}", other),
+    }
+}
+
+proptest! {
+    #[test]
+    fn version_partial_ord_matches_ord(a in arb_version(), b in arb_version()) {
+        prop_assert_eq!(a.partial_cmp(&b), Some(a.cmp(&b)));
+        prop_assert_eq!(a.cmp(&b), b.cmp(&a).reverse());
+        prop_assert_eq!(a.cmp(&b) == Ordering::Equal, a == b);
+    }
+
+    #[test]
+    fn version_ord_is_transitive(a in arb_version(), b in arb_version(), c in arb_version()) {
+        if a <= b && b <= c {
+            prop_assert!(a <= c);
+        }
+    }
+
+    #[test]
+    fn version_ord_puts_edition_first(a in arb_version(), b in arb_version()) {
+        if a.edition != b.edition {
+            prop_assert_eq!(a.cmp(&b), a.edition.cmp(&b.edition));
+            prop_assert!(a.cmp_same_edition(&b).is_err());
+        } else {
+            prop_assert_eq!(a.cmp_same_edition(&b).unwrap(), a.cmp(&b));
+        }
+    }
+}
+
+#[test]
+fn edition_correspondence() {
+    setup();
+    let java = closest_java_version("1.20.10").expect("Failed to map Bedrock 1.20.10");
+    assert_eq!(java.edition, Edition::Pc);
+    assert_eq!(java.minecraft_version, "1.20.1");
+
+    let java = closest_java_version("bedrock_1.18.30").expect("Failed to map Bedrock 1.18.30");
+    assert_eq!(java.minecraft_version, "1.18.2");
+
+    let bedrock = closest_bedrock_version("1.20.1").expect("Failed to map Java 1.20.1");
+    assert_eq!(bedrock.edition, Edition::Bedrock);
+    assert!(
+        bedrock.minecraft_version.starts_with("1.20."),
+        "Java 1.20.1 should map into Bedrock 1.20.x, got {}",
+        bedrock.minecraft_version
+    );
+
+    // Snapshots and pre-releases map like the last release before them, not by string order.
+    for snapshot in ["23w31a", "1.20.2-pre1"] {
+        let mapped = closest_bedrock_version(snapshot)
+            .unwrap_or_else(|e| panic!("Failed to map Java {}: {}", snapshot, e));
+        assert_eq!(mapped, bedrock, "Java {} should map like 1.20.1", snapshot);
+    }
+
+    // Versions older than the curated table have no counterpart.
+    match closest_bedrock_version("1.8.8") {
+        Err(McDataError::NoEditionCounterpart { target, .. }) => {
+            assert_eq!(target, Edition::Bedrock)
+        }
+        other => panic!("Expected NoEditionCounterpart error, got {:?}", other),
+    }
+}
+
+#[test]
+fn load_specific_bedrock_version_1_20_10() {
+    setup();
+    let version = "bedrock_1.20.10";
+    let data = mc_data(version).unwrap_or_else(|e| panic!("Failed to load {}: {:?}", version, e));
+
+    assert_eq!(data.version.minecraft_version, "1.20.10");
+    assert_eq!(data.version.edition, Edition::Bedrock);
+
+    // Core data shared with PC.
+    assert!(
+        data.blocks_by_name.contains_key("stone"),
+        "Stone block not found"
+    );
+    assert!(
+        data.items_by_name.contains_key("stick"),
+        "Stick item not found"
+    );
+    assert!(!data.biomes_array.is_empty(), "Biomes array is empty");
+    assert!(!data.entities_array.is_empty(), "Entities array is empty");
+
+    // Bedrock-specific data.
+    assert!(!data.block_states.is_empty(), "Block states are empty");
+    assert!(
+        data.block_states.iter().any(|s| s.name.ends_with("stone")),
+        "No stone block state found"
+    );
+    assert!(data.proto_yml.is_some(), "proto.yml is missing");
+}
+
+#[test]
+fn load_bedrock_version_matrix() {
+    setup();
+    // One version per era of Bedrock data layout, each loaded through a different path.
+    for version in [
+        "bedrock_1.16.201",
+        "bedrock_1.17.10",
+        "bedrock_1.18.30",
+        "bedrock_1.19.1",
+        "bedrock_1.20.0",
+        "bedrock_1.21.0",
+    ] {
+        let data =
+            mc_data(version).unwrap_or_else(|e| panic!("Failed to load {}: {:?}", version, e));
+        assert_eq!(data.version.edition, Edition::Bedrock, "{}", version);
+        assert!(!data.blocks_array.is_empty(), "{}: blocks empty", version);
+        assert!(!data.items_array.is_empty(), "{}: items empty", version);
+    }
+}
+
+#[test]
+fn load_every_bedrock_version() {
+    setup();
+    let versions = supported_versions(Edition::Bedrock).expect("Failed to get Bedrock versions");
+    for version in versions {
+        let prefixed = format!("bedrock_{}", version);
+        let data =
+            mc_data(&prefixed).unwrap_or_else(|e| panic!("Failed to load {}: {:?}", prefixed, e));
+        assert_eq!(data.version.edition, Edition::Bedrock);
+        assert!(!data.blocks_array.is_empty(), "{}: blocks empty", prefixed);
+    }
+}
+
+#[test]
+fn pc_versions_have_no_bedrock_data() {
+    setup();
+    let data = mc_data("1.18.2").unwrap();
+    assert!(data.block_states.is_empty());
+    assert!(data.block_mappings.is_empty());
+    assert!(data.steve.is_none());
+    assert!(data.proto_yml.is_none());
+}
+
+#[test]
+fn diff_between_versions() {
+    setup();
+    let old = mc_data("1.18.2").unwrap();
+    let new = mc_data("1.19").unwrap();
+    let report = diff(&old, &new).expect("Failed to diff 1.18.2 against 1.19");
+
+    assert_eq!(report.from, "pc_1.18.2");
+    assert_eq!(report.to, "pc_1.19");
+    assert!(report.blocks.added.iter().any(|b| b.name == "mangrove_log"));
+    assert!(report.entities.added.iter().any(|e| e.name == "warden"));
+    assert!(report.blocks.removed.iter().all(|b| b.name != "stone"));
+    // 1.19 shifts block state IDs, so at least some blocks report a changed state range.
+    assert!(report
+        .block_changes
+        .iter()
+        .any(|b| b.changes.iter().any(|c| c.property == "stateRange")));
+
+    let markdown = report.to_markdown();
+    assert!(markdown.contains("## Blocks"));
+    assert!(markdown.contains("`mangrove_log`"));
+    let json: serde_json::Value = serde_json::from_str(&report.to_json()).unwrap();
+    assert_eq!(json["to"], "pc_1.19");
+
+    // Diffing a version against itself reports nothing.
+    let same = diff(&old, &old).unwrap();
+    assert!(same.blocks.is_empty() && same.block_changes.is_empty());
+    assert!(same.feature_changes.is_empty());
+}
+
+#[test]
+fn remap_ids_between_versions() {
+    setup();
+    let old = mc_data("1.18.2").unwrap();
+    let new = mc_data("1.19").unwrap();
+    let remapper = Remapper::new(&new, &old);
+
+    let stone_new = new.blocks_by_name["stone"].default_state;
+    let stone_old = old.blocks_by_name["stone"].default_state;
+    assert_eq!(
+        remapper.block_states.forward(stone_new),
+        Some(IdMapping::Exact(stone_old))
+    );
+    assert_eq!(
+        remapper.block_states.backward(stone_old),
+        Some(IdMapping::Exact(stone_new))
+    );
+
+    // Blocks added in 1.19 have no 1.18.2 counterpart until a fallback is supplied.
+    let mangrove = &new.blocks_by_name["mangrove_log"];
+    assert_eq!(
+        remapper.block_states.forward(mangrove.default_state),
+        Some(IdMapping::Missing)
+    );
+    assert!(remapper
+        .block_states
+        .missing_forward()
+        .contains(&mangrove.default_state));
+    let warden = new.entities_by_name["warden"].id;
+    assert_eq!(remapper.entities.forward(warden), Some(IdMapping::Missing));
+
+    let remapper = remapper
+        .fallback(RemapCategory::Blocks, "mangrove_log", "oak_log")
+        .unwrap()
+        .fallback(RemapCategory::Entities, "warden", "zombie")
+        .unwrap();
+    let oak = &old.blocks_by_name["oak_log"];
+    assert_eq!(
+        remapper.block_states.forward(mangrove.default_state),
+        Some(IdMapping::Fallback(oak.default_state))
+    );
+    assert_eq!(
+        remapper.entities.forward(warden),
+        Some(IdMapping::Fallback(old.entities_by_name["zombie"].id))
+    );
+
+    match remapper.fallback(RemapCategory::Items, "not_an_item", "stone") {
+        Err(McDataError::UnknownEntry { kind, name, .. }) => {
+            assert_eq!(kind, "item");
+            assert_eq!(name, "not_an_item");
+        }
+        other => panic!("Expected UnknownEntry, got {:?}", other.map(|_| ())),
+    }
+}
+
+#[test]
+fn remap_pre_flattening_block_states() {
+    setup();
+    let old = mc_data("1.8.8").unwrap();
+    let new = mc_data("1.12.2").unwrap();
+    let remapper = Remapper::new(&old, &new);
+
+    // Metadata variants keep their `id << 4 | metadata` state IDs.
+    let granite = 1 << 4 | 1;
+    let red_wool = 35 << 4 | 14;
+    assert_eq!(
+        remapper.block_states.forward(granite),
+        Some(IdMapping::Exact(granite))
+    );
+    assert_eq!(
+        remapper.block_states.backward(red_wool),
+        Some(IdMapping::Exact(red_wool))
+    );
+    // End rods (ID 198) were added in 1.9.
+    assert_eq!(
+        remapper.block_states.backward(198 << 4 | 2),
+        Some(IdMapping::Missing)
+    );
+}
+
+#[test]
+fn resolve_renamed_names() {
+    setup();
+    let data = mc_data("1.20.4").unwrap();
+    let resolved = data
+        .canonical_name(AliasKind::Block, "grass")
+        .unwrap()
+        .expect("grass should resolve in 1.20.4");
+    assert_eq!(resolved.name, "short_grass");
+    assert_eq!(
+        resolved.renamed_in.map(|v| v.minecraft_version),
+        Some("1.20.3".to_string())
+    );
+    assert_eq!(
+        data.block_by_alias("grass_path")
+            .unwrap()
+            .map(|b| b.name.as_str()),
+        Some("dirt_path")
+    );
+    assert_eq!(
+        data.entity_by_alias("zombie_pigman")
+            .unwrap()
+            .map(|e| e.name.as_str()),
+        Some("zombified_piglin")
+    );
+    assert!(data.item_by_alias("not_an_item").unwrap().is_none());
+
+    // Newer names resolve backwards in versions predating the rename.
+    let old = mc_data("1.15.2").unwrap();
+    assert_eq!(
+        old.entity_by_alias("zombified_piglin")
+            .unwrap()
+            .map(|e| e.name.as_str()),
+        Some("zombie_pigman")
+    );
+
+    // Renames detected between versions can be registered as derived aliases.
+    let derived = AliasTable::derive(&[&old, &data]).unwrap();
+    assert!(derived
+        .aliases()
+        .iter()
+        .any(|a| a.old_name == "zombie_pigman" && a.new_name == "zombified_piglin"));
+}
+
+#[test]
+fn resolve_reused_names_by_source_version() {
+    setup();
+    let legacy = mc_data("1.12.2").unwrap();
+    let modern = mc_data("1.20.4").unwrap();
+    let resolve = |data: &IndexedData, kind: AliasKind, name: &str, source: &IndexedData| {
+        data.canonical_name_from(kind, name, &source.version)
+            .unwrap()
+            .map(|resolved| resolved.name)
+    };
+
+    // 1.12 names take their 1.12 meaning, even where later versions reuse them.
+    let forward = [
+        ("grass", "grass_block"),
+        ("tallgrass", "short_grass"),
+        ("snow", "snow_block"),
+        ("snow_layer", "snow"),
+    ];
+    for (old, new) in forward {
+        assert_eq!(
+            resolve(&modern, AliasKind::Block, old, &legacy).as_deref(),
+            Some(new)
+        );
+    }
+    assert_eq!(
+        resolve(&modern, AliasKind::Item, "grass", &legacy).as_deref(),
+        Some("grass_block")
+    );
+    assert_eq!(
+        resolve(
+            &mc_data("1.16.5").unwrap(),
+            AliasKind::Block,
+            "tallgrass",
+            &legacy
+        )
+        .as_deref(),
+        Some("grass")
+    );
+    // And back.
+    for (old, new) in forward {
+        assert_eq!(
+            resolve(&legacy, AliasKind::Block, new, &modern).as_deref(),
+            Some(old)
+        );
+    }
+    assert!(modern
+        .canonical_name_from(
+            AliasKind::Block,
+            "grass",
+            &mc_data("bedrock_1.20.10").unwrap().version
+        )
+        .is_err());
+
+    // Pre-1.13 flower pots become a block per plant.
+    let poppy = modern.flower_pot_by_contents("rose").unwrap().unwrap();
+    assert_eq!(poppy.name, "potted_poppy");
+    assert_eq!(
+        poppy.renamed_in.map(|v| v.major_version),
+        Some("1.13".to_string())
+    );
+    assert_eq!(
+        modern
+            .flower_pot_by_contents("houstonia")
+            .unwrap()
+            .map(|resolved| resolved.name),
+        Some("potted_azure_bluet".to_string())
+    );
+    assert_eq!(
+        legacy
+            .flower_pot_by_contents("rose")
+            .unwrap()
+            .map(|resolved| resolved.name),
+        Some("flower_pot".to_string())
+    );
+    assert!(modern.flower_pot_by_contents("poppy").unwrap().is_none());
+}
+
+#[test]
+fn query_version_range() {
+    setup();
+    let range = VersionRange::parse(">=1.8, <=1.12").unwrap();
+    let versions = range.versions().unwrap();
+    let names: Vec<&str> = versions
+        .iter()
+        .map(|v| v.minecraft_version.as_str())
+        .collect();
+    assert_eq!(names.first(), Some(&"1.8"));
+    assert!(names.contains(&"1.12.2"));
+    assert!(!names.contains(&"1.13"));
+    assert!(versions.iter().all(|v| v.release_type == "release"));
+
+    let stack_sizes = range
+        .query(|data| data.items_by_name.get("ender_pearl").map(|i| i.stack_size))
+        .unwrap();
+    assert_eq!(stack_sizes.len(), versions.len());
+    assert!(stack_sizes.values().all(|size| *size == Some(16)));
+
+    let with_warden = VersionRange::parse(">=1.18, <=1.19.2")
+        .unwrap()
+        .versions_where(|data| data.entities_by_name.contains_key("warden"))
+        .unwrap();
+    assert_eq!(
+        with_warden.first().map(|v| v.minecraft_version.as_str()),
+        Some("1.19")
+    );
+
+    assert!(matches!(
+        VersionRange::parse(">=1.8, <1.999"),
+        Err(McDataError::UnknownVersion { .. })
+    ));
+}
+
+#[test]
+fn block_state_round_trip() {
+    setup();
+    let data = mc_data("1.16.5").unwrap();
+    let id = data
+        .state_id_for(
+            "oak_stairs",
+            [("facing", "east"), ("half", "top"), ("waterlogged", "true")],
+        )
+        .unwrap();
+    let state = data.block_state(id).unwrap();
+    assert_eq!(state.name(), "oak_stairs");
+    assert_eq!(state.property("facing"), Some("east"));
+    assert_eq!(state.property("half"), Some("top"));
+    assert_eq!(state.property("shape"), Some("straight")); // From the default state.
+    assert_eq!(state.property("waterlogged"), Some("true"));
+    assert!(matches!(
+        data.state_id_for("stone", [("facing", "north")]),
+        Err(McDataError::UnknownBlockProperty { .. })
+    ));
+    assert!(matches!(
+        data.state_id_for("not_a_block", std::iter::empty::<(&str, &str)>()),
+        Err(McDataError::UnknownEntry { .. })
+    ));
+
+    // Every state of every block round-trips in all post-flattening releases.
+    VersionRange::parse(">=1.13")
+        .unwrap()
+        .try_query(|data| {
+            for block in data.blocks_array.iter() {
+                assert_eq!(
+                    data.block_state(block.default_state)
+                        .map(|s| s.is_default()),
+                    Some(true)
+                );
+                for state_id in block.min_state_id..=block.max_state_id {
+                    let state = data.block_state(state_id).unwrap_or_else(|| {
+                        panic!("{} state {} does not decode", block.name, state_id)
+                    });
+                    let properties: Vec<(&str, &str)> = state.properties().collect();
+                    assert_eq!(data.state_id_for(&block.name, properties)?, state_id);
+                }
+            }
+            Ok(())
+        })
+        .unwrap();
+}
+
+#[test]
+fn block_state_strings() {
+    setup();
+    let data = mc_data("1.16.5").unwrap();
+    let state = data
+        .parse_block_state("minecraft:oak_stairs[facing=north,half=top]")
+        .unwrap();
+    assert_eq!(state.property("facing"), Some("north"));
+    assert_eq!(state.property("half"), Some("top"));
+    assert_eq!(state.property("shape"), Some("straight"));
+    assert_eq!(
+        data.format_block_state(state.state_id()).as_deref(),
+        Some("minecraft:oak_stairs[facing=north,half=top,shape=straight,waterlogged=false]")
+    );
+
+    let stone = data.parse_block_state("stone").unwrap();
+    assert_eq!(stone.state_id(), data.blocks_by_name["stone"].default_state);
+    assert_eq!(stone.to_string(), "minecraft:stone");
+
+    assert_eq!(
+        data.parse_block_state("stone[facing=north]")
+            .unwrap_err()
+            .to_string(),
+        "Unknown property `facing` for `stone`"
+    );
+    assert!(matches!(
+        data.parse_block_state("oak_stairs[half=middle]"),
+        Err(McDataError::InvalidBlockPropertyValue { .. })
+    ));
+    assert!(matches!(
+        data.parse_block_state("minecraft:not_a_block"),
+        Err(McDataError::UnknownEntry { .. })
+    ));
+
+    // Every formatted state parses back to itself.
+    for block in data.blocks_array.iter() {
+        for state_id in block.min_state_id..=block.max_state_id {
+            let formatted = data.format_block_state(state_id).unwrap();
+            assert_eq!(
+                data.parse_block_state(&formatted).unwrap().state_id(),
+                state_id,
+                "{}",
+                formatted
+            );
+        }
+    }
+}
+
+#[test]
+fn dig_times_match_vanilla() {
+    setup();
+    let data = mc_data("1.18.2").unwrap();
+    let stone = &data.blocks_by_name["stone"];
+    let dirt = &data.blocks_by_name["dirt"];
+    let holding = |item: &str| DigOptions {
+        held_item: Some(data.items_by_name[item].id),
+        ..Default::default()
+    };
+    let millis = |block, options: &DigOptions| data.dig_time(block, options).map(|d| d.as_millis());
+
+    assert_eq!(millis(stone, &DigOptions::default()), Some(7500));
+    assert_eq!(millis(stone, &holding("wooden_pickaxe")), Some(1150));
+    assert_eq!(millis(stone, &holding("diamond_pickaxe")), Some(300));
+    assert_eq!(millis(dirt, &DigOptions::default()), Some(750));
+    assert_eq!(millis(dirt, &holding("diamond_shovel")), Some(100));
+    // Obsidian takes 9.4 s with a diamond pickaxe, as on the Minecraft Wiki.
+    let obsidian = &data.blocks_by_name["obsidian"];
+    assert_eq!(millis(obsidian, &holding("diamond_pickaxe")), Some(9400));
+    assert_eq!(
+        millis(&data.blocks_by_name["bedrock"], &holding("diamond_pickaxe")),
+        None
+    );
+}
+
+#[test]
+fn tool_effectiveness_queries() {
+    setup();
+    let data = mc_data("1.18.2").unwrap();
+    let item = |name: &str| data.items_by_name[name].id;
+
+    let pickaxes: Vec<&str> = data
+        .effective_tools("mineable/pickaxe")
+        .into_iter()
+        .map(|(item, _)| item.name.as_str())
+        .collect();
+    assert!(pickaxes.contains(&"diamond_pickaxe"));
+    assert!(!pickaxes.contains(&"diamond_shovel"));
+    // Gold is the fastest pickaxe material.
+    assert_eq!(pickaxes.first(), Some(&"golden_pickaxe"));
+
+    let iron_ore = &data.blocks_by_name["iron_ore"];
+    assert!(!data.can_harvest(iron_ore, Some(item("wooden_pickaxe"))));
+    assert!(data.can_harvest(iron_ore, Some(item("stone_pickaxe"))));
+    assert!(!data.can_harvest(iron_ore, None));
+    assert!(data.can_harvest(&data.blocks_by_name["dirt"], None));
+
+    let inventory = [
+        item("golden_pickaxe"),
+        item("stone_pickaxe"),
+        item("diamond_shovel"),
+    ];
+    // Golden pickaxes are faster, but only the stone pickaxe makes iron ore drop.
+    assert_eq!(
+        data.best_tool(iron_ore, &inventory),
+        Some(item("stone_pickaxe"))
+    );
+    assert_eq!(
+        data.best_tool(&data.blocks_by_name["dirt"], &inventory),
+        Some(item("diamond_shovel"))
+    );
+    let effect = data.tool_effect(iron_ore, Some(item("golden_pickaxe")));
+    assert_eq!(effect.speed_multiplier, 12.0);
+    assert!(!effect.harvests);
+}
+
+#[test]
+fn collision_shapes_of_block_states() {
+    setup();
+    let data = mc_data("1.18.2").unwrap();
+    let stone = data.blocks_by_name["stone"].default_state;
+    assert!(data.is_full_cube(stone));
+    assert!(Face::ALL
+        .iter()
+        .all(|face| data.is_face_solid(stone, *face)));
+
+    let air = data.blocks_by_name["air"].default_state;
+    assert!(!data.is_full_cube(air));
+    assert!(data
+        .collision_shape(air)
+        .is_none_or(|shape| shape.is_empty()));
+
+    // The default oak slab is a bottom slab.
+    let slab = data.default_collision_shape("oak_slab").unwrap();
+    assert!(!slab.is_full_cube());
+    assert!(slab.is_face_solid(Face::Down));
+    assert!(!slab.is_face_solid(Face::Up));
+    let slab_state = data.blocks_by_name["oak_slab"].default_state;
+    assert_eq!(data.collision_shape(slab_state), Some(slab.clone()));
+
+    // A player above a slab placed at y = 64 lands on its top.
+    let player = Aabb::new(0.2, 65.0, 0.2, 0.8, 66.8, 0.8);
+    assert_eq!(
+        slab.at_block(0, 64, 0).calculate_y_offset(&player, -3.0),
+        -0.5
+    );
+
+    let hit = data
+        .collision_shape(stone)
+        .unwrap()
+        .raycast([0.5, 0.5, -2.0], [0.0, 0.0, 1.0], 10.0)
+        .unwrap();
+    assert_eq!(hit.face, Face::North);
+    assert_eq!(hit.point, [0.5, 0.5, 0.0]);
+}
+
+#[cfg(feature = "physics")]
+#[test]
+fn physics_falls_and_walks_on_real_shapes() {
+    setup();
+    let data = mc_data("1.18.2").unwrap();
+    let physics = Physics::new(&data).unwrap();
+    assert_eq!(physics.constants().gravity, 0.08);
+    let stone = data.blocks_by_name["stone"].default_state;
+    let slab = data.blocks_by_name["oak_slab"].default_state;
+    let air = data.blocks_by_name["air"].default_state;
+    let world = move |_x: i32, y: i32, z: i32| {
+        Some(match (y, z) {
+            (..=63, _) => stone,
+            (64, 5..) => slab,
+            _ => air,
+        })
+    };
+
+    let zombie = &data.entities_by_name["zombie"];
+    let mut state = EntityState::for_entity(zombie, [0.5, 70.0, 0.5]);
+    assert_eq!(state.width, zombie.width.unwrap() as f64);
+    for _ in 0..40 {
+        physics.simulate(&world, &mut state);
+    }
+    assert!(state.on_ground);
+    assert_eq!(state.position[1], 64.0);
+
+    // Walking south steps onto the bottom slabs.
+    state.controls.forward = true;
+    for _ in 0..40 {
+        physics.simulate(&world, &mut state);
+    }
+    assert_eq!(state.position[1], 64.5);
+    assert!(state.position[2] > 6.0);
+
+    // Real water slows a sinking player to a fraction of the speed of falling through air.
+    let water = data.blocks_by_name["water"].default_state;
+    let lake = move |_x: i32, y: i32, _z: i32| Some(if y < 90 { water } else { air });
+    let mut player = EntityState::player([0.5, 85.0, 0.5]);
+    for _ in 0..40 {
+        physics.simulate(&lake, &mut player);
+    }
+    assert!(player.velocity[1] < 0.0 && player.velocity[1] > -0.15);
+}
+
+#[cfg(feature = "physics")]
+#[test]
+fn physics_constants_follow_versions() {
+    setup();
+    let constants =
+        |version: &str| PhysicsConstants::for_version(&mc_data(version).unwrap()).unwrap();
+
+    let v1_8 = constants("1.8.8");
+    assert!(!v1_8.climbable_trapdoor);
+    let v1_12 = constants("1.12.2");
+    assert_eq!((v1_12.water_gravity, v1_12.lava_gravity), (0.02, 0.02));
+    assert!(v1_12.climbable_trapdoor && v1_12.velocity_blocks_on_collision);
+    assert!(!v1_12.climb_using_jump);
+    let v1_13 = constants("1.13.2");
+    assert_eq!(v1_13.water_gravity, 0.08 / 16.0);
+    assert_eq!(v1_13.water_gravity, 0.005);
+    assert_eq!(constants("1.20.4").lava_gravity, 0.08 / 4.0);
+    let v1_14 = constants("1.14.4");
+    assert!(v1_14.climb_using_jump && v1_14.velocity_blocks_on_collision);
+    let v1_15 = constants("1.15.2");
+    assert!(v1_15.velocity_blocks_on_top && !v1_15.velocity_blocks_on_collision);
+
+    // From 1.15, walking on soul sand is slower than walking on stone.
+    let data = mc_data("1.15.2").unwrap();
+    let physics = Physics::new(&data).unwrap();
+    let air = data.blocks_by_name["air"].default_state;
+    let walk = |floor: u32| {
+        let world = move |_x: i32, y: i32, _z: i32| Some(if y < 64 { floor } else { air });
+        let mut player = EntityState::player([0.5, 64.0, 0.5]);
+        player.controls.forward = true;
+        for _ in 0..40 {
+            physics.simulate(&world, &mut player);
+        }
+        player.position[2]
+    };
+    let on_stone = walk(data.blocks_by_name["stone"].default_state);
+    let on_soul_sand = walk(data.blocks_by_name["soul_sand"].default_state);
+    assert!(
+        on_soul_sand < on_stone * 0.6,
+        "soul sand {} vs stone {}",
+        on_soul_sand,
+        on_stone
+    );
+}
+
+#[test]
+fn chunk_section_palettes() {
+    setup();
+    assert_eq!(
+        mc_data("1.12.2").unwrap().global_palette_bits().unwrap(),
+        13
+    );
+    assert_eq!(
+        mc_data("1.13.2").unwrap().global_palette_bits().unwrap(),
+        14
+    );
+    assert_eq!(
+        mc_data("1.18.2").unwrap().global_palette_bits().unwrap(),
+        15
+    );
+
+    for version in ["1.15.2", "1.16.5", "1.18.2"] {
+        let data = mc_data(version).unwrap();
+        let stone = data.blocks_by_name["stone"].default_state;
+        let air = data.blocks_by_name["air"].default_state;
+        let mut section = ChunkSection::filled(air);
+        for x in 0..SECTION_SIZE {
+            for z in 0..SECTION_SIZE {
+                section.set(x, 0, z, stone);
+            }
+        }
+        let packed = data.encode_section(&section).unwrap();
+        assert_eq!(packed.bits_per_entry, 4);
+        // 1.16 pads longs, which makes no difference at 4 bits per entry.
+        assert_eq!(packed.data.len(), 256);
+        assert_eq!(data.decode_section(&packed).unwrap(), section);
+
+        let states = data.section_block_states(&section).unwrap();
+        assert_eq!(states[0].name(), "stone");
+        assert_eq!(states[SECTION_VOLUME - 1].name(), "air");
+
+        let uniform = data.encode_section(&ChunkSection::filled(air)).unwrap();
+        let single_valued = version == "1.18.2";
+        assert_eq!(uniform.bits_per_entry == 0, single_valued);
+    }
+
+    // At 5+ bits per entry, the 1.16 layout needs more longs than the spanning one.
+    let wide: Vec<u32> = (0..SECTION_VOLUME as u32).map(|i| i % 20 + 1).collect();
+    let section = ChunkSection::from_state_ids(wide).unwrap();
+    let old = mc_data("1.15.2").unwrap().encode_section(&section).unwrap();
+    let new = mc_data("1.16.5").unwrap().encode_section(&section).unwrap();
+    assert_eq!((old.bits_per_entry, old.data.len()), (5, 320));
+    assert_eq!((new.bits_per_entry, new.data.len()), (5, 342));
+
+    // The layout follows data versions; features.json gains no invented entries.
+    let features = mc_data("1.18.2").unwrap().features().unwrap();
+    assert!(!features.iter().any(|f| f.name == "nonSpanningPaletteLongs"));
+    let bedrock = mc_data("bedrock_1.20.10").unwrap();
+    assert!(matches!(
+        bedrock.encode_section(&ChunkSection::filled(0)),
+        Err(McDataError::InvalidChunkSection { .. })
+    ));
+    // Sections were not paletted before 1.9.
+    let old = mc_data("1.8.8").unwrap();
+    assert!(matches!(
+        old.encode_section(&ChunkSection::filled(0)),
+        Err(McDataError::InvalidChunkSection { .. })
+    ));
+    let packed = PackedSection {
+        bits_per_entry: 0,
+        palette: Palette::Single(0),
+        data: Vec::new(),
+    };
+    assert!(matches!(
+        old.decode_section(&packed),
+        Err(McDataError::InvalidChunkSection { .. })
+    ));
+}
+
+#[test]
+fn legacy_id_conversion() {
+    setup();
+    let data = mc_data("1.20.4").unwrap();
+    let legacy = LegacyConverter::new(&data).unwrap();
+
+    assert_eq!(legacy.block(1, 0).unwrap().name(), "stone");
+    assert_eq!(legacy.block(1, 1).unwrap().name(), "granite");
+    assert_eq!(legacy.block(35, 14).unwrap().name(), "red_wool");
+    let log = legacy.block(17, 4).unwrap();
+    assert_eq!((log.name(), log.property("axis")), ("oak_log", Some("x")));
+    // Renamed since 1.13: grass_path became dirt_path in 1.17.
+    assert_eq!(legacy.block(208, 0).unwrap().name(), "dirt_path");
+    // Unknown metadata falls back to the ID's metadata 0.
+    assert_eq!(legacy.block(1, 15).unwrap().name(), "stone");
+    // Walls' `north=false` became `north=none` in 1.16, so it keeps the default value.
+    let wall = legacy.block(139, 0).unwrap();
+    assert_eq!(
+        (wall.name(), wall.property("north")),
+        ("cobblestone_wall", Some("none"))
+    );
+    assert_eq!(
+        legacy.block(139, 1).unwrap().name(),
+        "mossy_cobblestone_wall"
+    );
+
+    let red_wool = data.blocks_by_name["red_wool"].default_state;
+    assert_eq!(legacy.legacy_block(red_wool), Some((35, 14)));
+    let waterlogged = data
+        .parse_block_state("oak_stairs[waterlogged=true]")
+        .unwrap();
+    assert_eq!(legacy.legacy_block(waterlogged.state_id()), None);
+
+    let iron_shovel = legacy.item(256, 0).unwrap();
+    assert_eq!(iron_shovel.name, "iron_shovel");
+    // Tool metadata is damage, which falls back to the undamaged item.
+    assert_eq!(legacy.item(256, 37).unwrap().id, iron_shovel.id);
+    assert_eq!(legacy.legacy_item(iron_shovel.id), Some((256, 0)));
+
+    assert!(matches!(
+        LegacyConverter::new(&mc_data("1.12.2").unwrap()),
+        Err(McDataError::LegacyConversionUnavailable { .. })
+    ));
+}
+
+#[test]
+fn light_propagation() {
+    setup();
+    let data = mc_data("1.20.4").unwrap();
+    let lighting = Lighting::new(&data);
+    let state = |name: &str| data.parse_block_state(name).unwrap().state_id();
+    let (stone, torch, slab) = (
+        state("stone"),
+        state("torch"),
+        state("oak_slab[type=bottom]"),
+    );
+    assert_eq!(lighting.emission(torch), 14);
+    assert_eq!(lighting.emission(state("furnace[lit=true]")), 13);
+    assert_eq!(lighting.emission(state("furnace[lit=false]")), 0);
+    assert_eq!(lighting.opacity(stone), 15);
+    assert_eq!(lighting.opacity(state("glass")), 0);
+
+    // A torch in a stone room roofed with bottom slabs at y = 68.
+    let world = move |x: i32, y: i32, z: i32| {
+        Some(match (x, y, z) {
+            (0, 64, 0) => torch,
+            (_, 68, _) if x.abs() <= 6 && z.abs() <= 6 => slab,
+            (_, ..=63, _) => stone,
+            _ => 0,
+        })
+    };
+    let light = lighting.compute(&world, [-4, 64, -4], [4, 80, 4]);
+    assert_eq!(light.block_light(0, 64, 0), Some(14));
+    assert_eq!(light.block_light(4, 64, 0), Some(10));
+    assert_eq!(light.sky_light(0, 69, 0), Some(15));
+    // Sky light only reaches under the roof from its edges, 7 blocks away.
+    assert_eq!(light.sky_light(0, 67, 0), Some(8));
+
+    // Trapdoors, carpets and hoppers let sky light straight through; snow layers do not.
+    for (roof, below) in [
+        ("oak_trapdoor[open=false,half=bottom]", 15),
+        ("white_carpet", 15),
+        ("hopper", 15),
+        ("snow[layers=1]", 0),
+    ] {
+        let roof = state(roof);
+        let world = move |_x: i32, y: i32, _z: i32| {
+            Some(match y {
+                ..=63 => stone,
+                68 => roof,
+                _ => 0,
+            })
+        };
+        let light = lighting.compute(&world, [0, 64, 0], [0, 80, 0]);
+        assert_eq!(light.sky_light(0, 67, 0), Some(below), "under {}", roof);
+    }
+}
+
+#[test]
+fn biome_tints() {
+    setup();
+    let data = mc_data("1.20.4").unwrap();
+    let state = |name: &str| data.parse_block_state(name).unwrap().state_id();
+
+    let plains_grass = data.grass_color("plains").unwrap();
+    assert_eq!(data.grass_color("minecraft:plains"), Some(plains_grass));
+    assert_ne!(data.grass_color("desert"), Some(plains_grass));
+    // Swamps list their own grass colour rather than using the colormap.
+    assert_eq!(data.grass_color("swamp"), Some(0x6A7039));
+    assert!(data.foliage_color("jungle").is_some());
+    assert_eq!(data.water_color("plains"), 0x3F76E4);
+    assert_ne!(data.water_color("swamp"), 0x3F76E4);
+    assert_eq!(data.redstone_color(0), Some(0x4C0000));
+    assert_eq!(data.redstone_color(16), None);
+
+    assert_eq!(
+        data.block_tint(state("grass_block"), "plains"),
+        Some(plains_grass)
+    );
+    assert_eq!(
+        data.block_tint(state("oak_leaves"), "jungle"),
+        data.foliage_color("jungle")
+    );
+    assert_eq!(
+        data.block_tint(state("water"), "swamp"),
+        Some(data.water_color("swamp"))
+    );
+    assert_eq!(
+        data.block_tint(state("redstone_wire[power=15]"), "plains"),
+        data.redstone_color(15)
+    );
+    // Birch leaves keep one colour everywhere.
+    let birch = data.block_tint(state("birch_leaves"), "plains");
+    assert!(birch.is_some());
+    assert_eq!(data.block_tint(state("birch_leaves"), "jungle"), birch);
+    assert_eq!(data.block_tint(state("stone"), "plains"), None);
+}
+
 // Potential future tests:
-// - Bedrock edition loading and data checks.
 // - Specific data points in various versions (e.g., recipe shapes, entity properties).
 // - Edge cases like snapshot versions if needed.
 // - More robust state ID lookups for shape tests.